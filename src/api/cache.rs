@@ -0,0 +1,153 @@
+// Persistent cache of fetched questions, so reopening a recently viewed question doesn't hit the
+// network. Every question is stored as the JSON returned by the API in its own file:
+// {user cache dir}/stackbloatless/questions/{site}/{id}.json
+// Next to them are the list of sites, sites.json, their icons: icons/{site}.png, and the created
+// API filters by their fields: filters.json
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use relm4::gtk::glib;
+use serde::{Deserialize, Serialize};
+use serde_json as json;
+
+use super::stackexchange::Id;
+
+// How long a cached question is used before fetching it again, in seconds.
+const QUESTION_TTL: i64 = 60 * 60;
+// Sites are rarely added, so their list is fetched once a week.
+const SITES_TTL: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    // When the item was fetched, as a Unix timestamp.
+    pub fetched: i64,
+    pub item: json::Value,
+}
+
+impl CacheEntry {
+    /// Fetched less than `QUESTION_TTL` ago, older entries are only used when offline.
+    pub fn is_fresh(&self) -> bool {
+        now() - self.fetched <= QUESTION_TTL
+    }
+}
+
+pub struct Cache {
+    dir: PathBuf,
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+impl Cache {
+    pub fn new() -> Self {
+        Self {
+            dir: glib::user_cache_dir().join("stackbloatless"),
+        }
+    }
+
+    fn questions_dir(&self) -> PathBuf {
+        self.dir.join("questions")
+    }
+
+    fn path(&self, site: &str, id: Id) -> PathBuf {
+        self.questions_dir().join(site).join(format!("{id}.json"))
+    }
+
+    fn read(path: &PathBuf) -> Option<CacheEntry> {
+        json::from_slice(&fs::read(path).ok()?).ok()
+    }
+
+    /// The cache is best-effort, so failures are only logged.
+    fn write(path: &PathBuf, content: &[u8]) {
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, content));
+
+        if let Err(error) = result {
+            eprintln!("Failed to cache {}: {error}", path.display());
+        }
+    }
+
+    fn write_entry(path: &PathBuf, item: &json::Value) {
+        let entry = CacheEntry {
+            fetched: now(),
+            item: item.clone(),
+        };
+
+        match json::to_vec(&entry) {
+            Ok(content) => Self::write(path, &content),
+            Err(error) => eprintln!("Failed to cache {}: {error}", path.display()),
+        }
+    }
+
+    /// The cached question, no matter how old it is.
+    pub fn get(&self, site: &str, id: Id) -> Option<CacheEntry> {
+        Self::read(&self.path(site, id))
+    }
+
+    /// Save a freshly fetched question.
+    pub fn insert(&self, site: &str, id: Id, item: &json::Value) {
+        Self::write_entry(&self.path(site, id), item);
+    }
+
+    /// The cached list of sites if it was fetched less than `SITES_TTL` ago, or at any time when
+    /// `stale` is true.
+    pub fn get_sites(&self, stale: bool) -> Option<json::Value> {
+        Self::read(&self.dir.join("sites.json"))
+            .filter(|entry| stale || now() - entry.fetched <= SITES_TTL)
+            .map(|entry| entry.item)
+    }
+
+    pub fn insert_sites(&self, sites: &json::Value) {
+        Self::write_entry(&self.dir.join("sites.json"), sites);
+    }
+
+    fn filters(&self) -> HashMap<String, String> {
+        fs::read(self.dir.join("filters.json"))
+            .ok()
+            .and_then(|content| json::from_slice(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// The filter created for the `include` fields, filters never expire.
+    pub fn get_filter(&self, include: &str) -> Option<String> {
+        self.filters().remove(include)
+    }
+
+    pub fn insert_filter(&self, include: &str, filter: &str) {
+        let mut filters = self.filters();
+        filters.insert(include.to_owned(), filter.to_owned());
+
+        match json::to_vec(&filters) {
+            Ok(content) => Self::write(&self.dir.join("filters.json"), &content),
+            Err(error) => eprintln!("Failed to cache the API filters: {error}"),
+        }
+    }
+
+    /// Where the icon of `site` is saved, the file might not exist yet.
+    pub fn icon_path(&self, site: &str) -> PathBuf {
+        self.dir.join("icons").join(format!("{site}.png"))
+    }
+
+    pub fn insert_icon(&self, site: &str, icon: &[u8]) {
+        Self::write(&self.icon_path(site), icon);
+    }
+
+    /// Forget a question, for example after voting on it.
+    pub fn remove(&self, site: &str, id: Id) {
+        let _ = fs::remove_file(self.path(site, id));
+    }
+
+    /// Forget every question, cached questions include the votes of the logged in user.
+    pub fn clear(&self) {
+        let _ = fs::remove_dir_all(self.questions_dir());
+    }
+}
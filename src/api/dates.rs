@@ -0,0 +1,40 @@
+// Formatting of the dates returned by the API, they are all Unix timestamps in UTC.
+
+use relm4::gtk::glib;
+use time::OffsetDateTime;
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+// Older dates are shown in full, "5 months ago" isn't precise enough.
+const MONTH: i64 = 30 * DAY;
+
+/// The date and time in the local time zone, for example: 2023-06-01 14:05
+pub fn format_absolute(date: OffsetDateTime) -> String {
+    // The time crate can't get the local offset once threads are running, GLib can.
+    glib::DateTime::from_unix_local(date.unix_timestamp())
+        .and_then(|date_time| date_time.format("%Y-%m-%d %H:%M"))
+        .map(|formatted| formatted.to_string())
+        .unwrap_or_default()
+}
+
+/// How long ago `date` was, or how long until it for future dates, for example: 5 minutes ago,
+/// in 3 days. Dates further than a month away are formatted like `format_absolute`.
+pub fn format_relative(date: OffsetDateTime) -> String {
+    let seconds = (OffsetDateTime::now_utc() - date).whole_seconds();
+
+    let (amount, unit) = match seconds.abs() {
+        distance if distance < MINUTE => return "just now".to_owned(),
+        distance if distance < HOUR => (distance / MINUTE, "minute"),
+        distance if distance < DAY => (distance / HOUR, "hour"),
+        distance if distance < MONTH => (distance / DAY, "day"),
+        _ => return format_absolute(date),
+    };
+    let plural = if amount == 1 { "" } else { "s" };
+
+    if seconds < 0 {
+        format!("in {amount} {unit}{plural}")
+    } else {
+        format!("{amount} {unit}{plural} ago")
+    }
+}
@@ -0,0 +1,119 @@
+// Atom feeds of StackExchange sites, like https://stackoverflow.com/feeds/tag/rust
+// They don't count against the API quota.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use super::stackexchange::{Id, StackExchangeError};
+
+/// A question listed in a feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+    pub question_id: Id,
+    pub title: String,
+    // RFC 3339 date, for example: 2023-06-01T12:00:00Z
+    pub published: String,
+}
+
+/// Entries being parsed, the id is required but the other fields might be missing.
+#[derive(Default)]
+struct PartialEntry {
+    question_id: Option<Id>,
+    title: String,
+    published: String,
+}
+
+pub fn parse_feed(xml: &str) -> Result<Vec<FeedEntry>, StackExchangeError> {
+    let invalid_feed = |error: quick_xml::Error| StackExchangeError::Json(error.to_string());
+
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut entries = Vec::new();
+    let mut entry: Option<PartialEntry> = None;
+    let mut element = Vec::new();
+
+    loop {
+        match reader.read_event().map_err(invalid_feed)? {
+            Event::Start(start) if start.name().as_ref() == b"entry" => {
+                entry = Some(PartialEntry::default());
+            }
+            Event::Start(start) => element = start.name().as_ref().to_vec(),
+            Event::Text(text) => {
+                let Some(entry) = &mut entry else {
+                    continue;
+                };
+                let text = text.unescape().map_err(invalid_feed)?;
+
+                match element.as_slice() {
+                    // Short link of the question, for example: https://stackoverflow.com/q/123456
+                    b"id" => {
+                        entry.question_id =
+                            text.rsplit('/').next().and_then(|id| id.parse::<Id>().ok());
+                    }
+                    b"title" => entry.title = text.into_owned(),
+                    b"published" => entry.published = text.into_owned(),
+                    _ => {}
+                }
+            }
+            Event::End(end) if end.name().as_ref() == b"entry" => {
+                if let Some(PartialEntry {
+                    question_id: Some(question_id),
+                    title,
+                    published,
+                }) = entry.take()
+                {
+                    entries.push(FeedEntry {
+                        question_id,
+                        title,
+                        published,
+                    });
+                }
+            }
+            Event::End(_) => element.clear(),
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fixture_feed() {
+        let entries = parse_feed(include_str!("../../fixtures/feed.xml")).unwrap();
+
+        // The entry without a question id is skipped.
+        assert_eq!(
+            entries,
+            vec![
+                FeedEntry {
+                    question_id: 2,
+                    title: "Why does Vec<T> need T: Clone & Copy here?".to_owned(),
+                    published: "2023-06-02T09:00:00Z".to_owned(),
+                },
+                FeedEntry {
+                    question_id: 1,
+                    title: "Fixture: create a vector with a capacity".to_owned(),
+                    published: "2023-06-01T12:00:00Z".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_empty_feed() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?><feed><title>Empty</title></feed>"#;
+
+        assert_eq!(parse_feed(xml).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn parse_invalid_feed() {
+        assert!(parse_feed("<feed><entry></feed>").is_err());
+    }
+}
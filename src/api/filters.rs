@@ -0,0 +1,208 @@
+// Custom API filters, to only include the fields that are rendered and keep payloads small.
+// Docs: https://api.stackexchange.com/docs/filters
+
+// Included in every filter.
+const WRAPPER_FIELDS: &[&str] = &[
+    ".backoff",
+    ".error_id",
+    ".error_message",
+    ".error_name",
+    ".has_more",
+    ".items",
+    ".quota_max",
+    ".quota_remaining",
+];
+
+// Full questions with their answers and comments.
+const QUESTION_FIELDS: &[&str] = &[
+    // Question
+    "question.accepted_answer_id",
+    "question.answer_count",
+    "question.answers",
+    "question.body_markdown",
+    "question.bounty_amount",
+    "question.bounty_closes_date",
+    "question.closed_date",
+    "question.closed_reason",
+    "question.comment_count",
+    "question.comments",
+    "question.creation_date",
+    "question.downvoted",
+    "question.favorited",
+    "question.is_answered",
+    "question.last_activity_date",
+    "question.last_edit_date",
+    "question.link",
+    "question.owner",
+    "question.question_id",
+    "question.score",
+    "question.tags",
+    "question.title",
+    "question.upvoted",
+    "question.view_count",
+    // Answer
+    "answer.answer_id",
+    "answer.body_markdown",
+    "answer.comment_count",
+    "answer.comments",
+    "answer.creation_date",
+    "answer.downvoted",
+    "answer.is_accepted",
+    "answer.last_activity_date",
+    "answer.last_edit_date",
+    "answer.owner",
+    "answer.score",
+    "answer.upvoted",
+    // Comment
+    "comment.body_markdown",
+    "comment.comment_id",
+    "comment.creation_date",
+    "comment.edited",
+    "comment.owner",
+    "comment.post_id",
+    "comment.score",
+    // User
+    "shallow_user.display_name",
+    "shallow_user.link",
+    "shallow_user.reputation",
+    "shallow_user.user_id",
+];
+
+// Enough to list questions without their content.
+const QUESTION_SUMMARY_FIELDS: &[&str] = &[
+    "question.answer_count",
+    "question.bounty_amount",
+    "question.closed_reason",
+    "question.is_answered",
+    "question.question_id",
+    "question.score",
+    "question.tags",
+    "question.title",
+];
+
+// Enough to tell if a question changed since it was loaded.
+const QUESTION_ACTIVITY_FIELDS: &[&str] = &[
+    "question.answer_count",
+    "question.last_activity_date",
+    "question.question_id",
+];
+
+// User profiles.
+const USER_FIELDS: &[&str] = &[
+    "badge_count.bronze",
+    "badge_count.gold",
+    "badge_count.silver",
+    "user.badge_counts",
+    "user.display_name",
+    "user.link",
+    "user.location",
+    "user.reputation",
+];
+
+// Tags a user is active in.
+const TOP_TAG_FIELDS: &[&str] = &[
+    "top_tag.answer_count",
+    "top_tag.answer_score",
+    "top_tag.question_count",
+    "top_tag.tag_name",
+];
+
+// Enough to list answers with the title of their questions.
+const ANSWER_SUMMARY_FIELDS: &[&str] = &[
+    "answer.is_accepted",
+    "answer.question_id",
+    "answer.score",
+    "answer.title",
+];
+
+// Tags with their usage.
+const TAG_FIELDS: &[&str] = &["tag.count"];
+
+// Short description of tags.
+const TAG_WIKI_FIELDS: &[&str] = &["tag_wiki.excerpt"];
+
+// Edit history of posts.
+const REVISION_FIELDS: &[&str] = &[
+    "revision.comment",
+    "revision.creation_date",
+    "revision.is_rollback",
+    "revision.revision_number",
+    "revision.user",
+    "shallow_user.display_name",
+    "shallow_user.user_id",
+];
+
+// Search results with the matched text.
+const SEARCH_EXCERPT_FIELDS: &[&str] = &[
+    "search_excerpt.answer_count",
+    "search_excerpt.answer_id",
+    "search_excerpt.excerpt",
+    "search_excerpt.is_answered",
+    "search_excerpt.item_type",
+    "search_excerpt.question_id",
+    "search_excerpt.score",
+    "search_excerpt.tags",
+    "search_excerpt.title",
+];
+
+// Sites with their icons.
+const SITE_FIELDS: &[&str] = &["site.api_site_parameter", "site.icon_url"];
+
+// Inbox items of the logged in user.
+const INBOX_FIELDS: &[&str] = &[
+    "inbox_item.answer_id",
+    "inbox_item.creation_date",
+    "inbox_item.item_type",
+    "inbox_item.link",
+    "inbox_item.question_id",
+    "inbox_item.site",
+    "inbox_item.title",
+    "site.api_site_parameter",
+];
+
+// Reputation changes of the logged in user.
+const REPUTATION_HISTORY_FIELDS: &[&str] = &[
+    "reputation_history.creation_date",
+    "reputation_history.reputation_change",
+    "reputation_history.reputation_history_type",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Filter {
+    Questions,
+    QuestionSummaries,
+    QuestionActivity,
+    Users,
+    TopTags,
+    AnswerSummaries,
+    Tags,
+    TagWikis,
+    Revisions,
+    SearchExcerpts,
+    Sites,
+    Inbox,
+    ReputationHistory,
+}
+
+impl Filter {
+    /// Value of the `include` parameter of `/filters/create`.
+    pub fn include(&self) -> String {
+        let fields = match self {
+            Filter::Questions => QUESTION_FIELDS,
+            Filter::QuestionSummaries => QUESTION_SUMMARY_FIELDS,
+            Filter::QuestionActivity => QUESTION_ACTIVITY_FIELDS,
+            Filter::Users => USER_FIELDS,
+            Filter::TopTags => TOP_TAG_FIELDS,
+            Filter::AnswerSummaries => ANSWER_SUMMARY_FIELDS,
+            Filter::Tags => TAG_FIELDS,
+            Filter::TagWikis => TAG_WIKI_FIELDS,
+            Filter::Revisions => REVISION_FIELDS,
+            Filter::SearchExcerpts => SEARCH_EXCERPT_FIELDS,
+            Filter::Sites => SITE_FIELDS,
+            Filter::Inbox => INBOX_FIELDS,
+            Filter::ReputationHistory => REPUTATION_HISTORY_FIELDS,
+        };
+
+        [WRAPPER_FIELDS, fields].concat().join(";")
+    }
+}
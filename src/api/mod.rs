@@ -1 +1,7 @@
+mod cache;
+pub mod dates;
+pub mod feeds;
+mod filters;
+pub mod provider;
+pub mod search;
 pub mod stackexchange;
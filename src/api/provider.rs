@@ -0,0 +1,191 @@
+// Where the GUI gets questions from: the API, or JSON fixtures to work on the GUI and the uri
+// parser without network access or spending the quota.
+
+use std::fs;
+use std::path::PathBuf;
+
+use relm4::async_trait::async_trait;
+use serde_json as json;
+
+use super::stackexchange::{
+    parse_uri, Id, Question, StackExchange, StackExchangeError, UriLink, UriTarget,
+};
+
+#[async_trait(?Send)]
+pub trait QuestionProvider {
+    async fn get_questions(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<Question>, StackExchangeError>;
+
+    /// Like `get_questions`, without serving cached copies that are still recent.
+    async fn refresh_questions(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<Question>, StackExchangeError> {
+        self.get_questions(site, ids).await
+    }
+
+    /// Id of the question `answer_id` answers.
+    async fn get_answer_question_id(
+        &self,
+        site: &str,
+        answer_id: Id,
+    ) -> Result<Id, StackExchangeError>;
+
+    /// Find the questions a uri points to, answer links are resolved to their question.
+    async fn resolve_uri(&self, uri: &str) -> Result<UriTarget, StackExchangeError> {
+        match parse_uri(uri)? {
+            UriLink::Questions {
+                site,
+                question_ids,
+                answer_id,
+            } => Ok(UriTarget {
+                site,
+                question_ids,
+                answer_id,
+            }),
+            UriLink::Answer { site, answer_id } => Ok(UriTarget {
+                question_ids: vec![self.get_answer_question_id(&site, answer_id).await?],
+                site,
+                answer_id: Some(answer_id),
+            }),
+            // Searches don't point to questions, they're opened in their own page.
+            UriLink::Search { .. } => Err(StackExchangeError::InvalidUri(uri.to_owned())),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl QuestionProvider for StackExchange {
+    async fn get_questions(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<Question>, StackExchangeError> {
+        StackExchange::get_questions(self, site, ids).await
+    }
+
+    async fn refresh_questions(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<Question>, StackExchangeError> {
+        StackExchange::refresh_questions(self, site, ids).await
+    }
+
+    async fn get_answer_question_id(
+        &self,
+        site: &str,
+        answer_id: Id,
+    ) -> Result<Id, StackExchangeError> {
+        StackExchange::get_answer_question_id(self, site, answer_id).await
+    }
+}
+
+/// Questions read from JSON files, each one is a question as returned by the API:
+/// {dir}/{site}/{id}.json
+/// See the `fixtures` directory for an example.
+pub struct FixtureProvider {
+    dir: PathBuf,
+}
+
+impl FixtureProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Fixtures from the directory in `STACKBLOATLESS_FIXTURES`, if it's set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os("STACKBLOATLESS_FIXTURES").map(Self::new)
+    }
+
+    fn read_question(&self, path: PathBuf, site: &str) -> Result<Question, StackExchangeError> {
+        let content = fs::read(path).map_err(|_| StackExchangeError::NotFound)?;
+
+        let mut question: Question = json::from_slice(&content)?;
+        question.site = site.to_owned();
+
+        Ok(question)
+    }
+}
+
+#[async_trait(?Send)]
+impl QuestionProvider for FixtureProvider {
+    async fn get_questions(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<Question>, StackExchangeError> {
+        let mut questions = Vec::with_capacity(ids.len());
+
+        // Missing questions are skipped like deleted ones in the API.
+        for id in ids {
+            match self.read_question(self.dir.join(site).join(format!("{id}.json")), site) {
+                Ok(question) => questions.push(question),
+                Err(StackExchangeError::NotFound) => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(questions)
+    }
+
+    async fn get_answer_question_id(
+        &self,
+        site: &str,
+        answer_id: Id,
+    ) -> Result<Id, StackExchangeError> {
+        let entries =
+            fs::read_dir(self.dir.join(site)).map_err(|_| StackExchangeError::NotFound)?;
+
+        // Look for the answer in every question of the site.
+        entries
+            .filter_map(|entry| self.read_question(entry.ok()?.path(), site).ok())
+            .find(|question| {
+                question
+                    .answers
+                    .iter()
+                    .flatten()
+                    .any(|answer| answer.answer_id == answer_id)
+            })
+            .map(|question| question.question_id)
+            .ok_or(StackExchangeError::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    fn fixtures() -> FixtureProvider {
+        FixtureProvider::new(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures"))
+    }
+
+    #[test]
+    fn load_fixture_questions() {
+        // The missing question is skipped.
+        let questions = block_on(fixtures().get_questions("stackoverflow", &[1, 2])).unwrap();
+
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].question_id, 1);
+        assert_eq!(questions[0].site, "stackoverflow");
+        assert_eq!(
+            questions[0].title,
+            "Fixture: create a vector with a capacity"
+        );
+    }
+
+    #[test]
+    fn resolve_fixture_answer_uri() {
+        let target = block_on(fixtures().resolve_uri("stackexchange://so/a/2")).unwrap();
+
+        assert_eq!(target.site, "stackoverflow");
+        assert_eq!(target.question_ids, vec![1]);
+        assert_eq!(target.answer_id, Some(2));
+    }
+}
@@ -0,0 +1,167 @@
+// Search terms with the operators of the search of StackExchange sites, for example:
+// [rust] [lifetimes] user:12345 isaccepted:yes score:5 "exact phrase" free text
+// They are translated to the parameters of the advanced search.
+// Docs: https://api.stackexchange.com/docs/advanced-search
+
+use super::stackexchange::Id;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    // Free text, including quoted phrases and operators without a parameter like `score:5`,
+    // the API matches it like the site search does.
+    pub text: String,
+    pub tagged: Vec<String>,
+    pub user: Option<Id>,
+    pub accepted: Option<bool>,
+}
+
+/// Split on whitespace, keeping quoted phrases together with their quotes.
+fn split_terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut term = String::new();
+    let mut quoted = false;
+
+    for character in query.chars() {
+        match character {
+            '"' => {
+                quoted = !quoted;
+                term.push(character);
+            }
+            _ if character.is_whitespace() && !quoted => {
+                if !term.is_empty() {
+                    terms.push(std::mem::take(&mut term));
+                }
+            }
+            _ => term.push(character),
+        }
+    }
+
+    if !term.is_empty() {
+        terms.push(term);
+    }
+
+    terms
+}
+
+fn parse_yes_no(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "yes" | "true" | "1" => Some(true),
+        "no" | "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+impl SearchQuery {
+    pub fn parse(query: &str) -> Self {
+        let mut search = Self::default();
+        let mut text = Vec::new();
+
+        for term in split_terms(query) {
+            if let Some(tag) = term
+                .strip_prefix('[')
+                .and_then(|term| term.strip_suffix(']'))
+                .filter(|tag| !tag.is_empty())
+            {
+                search.tagged.push(tag.to_owned());
+                continue;
+            }
+
+            // Invalid operators are searched as text.
+            let parsed = match term.split_once(':') {
+                Some(("user", id)) => id.parse().ok().map(|id| search.user = Some(id)),
+                Some(("isaccepted", value)) => {
+                    parse_yes_no(value).map(|accepted| search.accepted = Some(accepted))
+                }
+                _ => None,
+            };
+
+            if parsed.is_none() {
+                text.push(term);
+            }
+        }
+
+        search.text = text.join(" ");
+        search
+    }
+
+    /// Query parameters of `/search/advanced` and `/search/excerpts`.
+    pub fn parameters(&self) -> Vec<(&'static str, String)> {
+        let mut parameters = Vec::new();
+
+        if !self.text.is_empty() {
+            parameters.push(("q", self.text.clone()));
+        }
+        if !self.tagged.is_empty() {
+            parameters.push(("tagged", self.tagged.join(";")));
+        }
+        if let Some(user) = self.user {
+            parameters.push(("user", user.to_string()));
+        }
+        if let Some(accepted) = self.accepted {
+            parameters.push(("accepted", accepted.to_string()));
+        }
+
+        parameters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tags() {
+        let query = SearchQuery::parse("[rust] lifetimes [borrow-checker] []");
+
+        assert_eq!(query.tagged, vec!["rust", "borrow-checker"]);
+        assert_eq!(query.text, "lifetimes []");
+    }
+
+    #[test]
+    fn parse_operators() {
+        let query = SearchQuery::parse("user:12345 isaccepted:yes score:5 wifi");
+
+        assert_eq!(query.user, Some(12345));
+        assert_eq!(query.accepted, Some(true));
+        // Operators without a parameter are left to the API.
+        assert_eq!(query.text, "score:5 wifi");
+
+        assert_eq!(SearchQuery::parse("isaccepted:no").accepted, Some(false));
+
+        // Invalid values are searched as text.
+        let query = SearchQuery::parse("user:me isaccepted:maybe");
+        assert_eq!(query.user, None);
+        assert_eq!(query.accepted, None);
+        assert_eq!(query.text, "user:me isaccepted:maybe");
+    }
+
+    #[test]
+    fn parse_quoted_phrases() {
+        let query = SearchQuery::parse(r#"[rust] "exact  phrase [not-a-tag]" text"#);
+
+        assert_eq!(query.tagged, vec!["rust"]);
+        assert_eq!(query.text, r#""exact  phrase [not-a-tag]" text"#);
+    }
+
+    #[test]
+    fn parse_empty_query() {
+        assert_eq!(SearchQuery::parse(""), SearchQuery::default());
+        assert_eq!(SearchQuery::parse("   "), SearchQuery::default());
+        assert!(SearchQuery::default().parameters().is_empty());
+    }
+
+    #[test]
+    fn parameters() {
+        let query = SearchQuery::parse("[rust] [lifetimes] user:12345 isaccepted:yes text");
+
+        assert_eq!(
+            query.parameters(),
+            vec![
+                ("q", "text".to_owned()),
+                ("tagged", "rust;lifetimes".to_owned()),
+                ("user", "12345".to_owned()),
+                ("accepted", "true".to_owned()),
+            ]
+        );
+    }
+}
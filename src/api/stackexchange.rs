@@ -1,124 +1,2562 @@
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Read;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use relm4::gtk::{gio, glib, prelude::*};
 use reqwest::Url;
 use serde::Deserialize;
 use serde_json as json;
+use time::OffsetDateTime;
+use tokio::sync::{AcquireError, OwnedSemaphorePermit, Semaphore};
+
+use super::cache::Cache;
+use super::feeds::{self, FeedEntry};
+use super::filters::Filter;
+use super::search::SearchQuery;
+use crate::settings::Settings;
 
 const API_ENDPOINT: &str = "https://api.stackexchange.com/2.3";
 
-// API filters to just include fields we need.
-const API_QUESTIONS_FILTER: &str =
-    "EElmT9iE*eL20pftmjJrJa1RzdE9QOwek0yS*Tk9VsC59YEekmluvpWi71mN)yEJu00ci5W";
-// const API_SEARCH_FILTER: &str = "";
+// OAuth 2.0 implicit flow for desktop applications.
+// Docs: https://api.stackexchange.com/docs/authentication
+const OAUTH_DIALOG: &str = "https://stackoverflow.com/oauth/dialog";
+const OAUTH_REDIRECT_URI: &str = "https://stackoverflow.com/oauth/login_success";
+// `write_access` is needed for voting, `no_expiry` avoids asking the user to log in every day.
+const OAUTH_SCOPE: &str = "read_inbox,write_access,no_expiry";
+
+// Application credentials registered on https://stackapps.com, provided at build time.
+const API_CLIENT_ID: Option<&str> = option_env!("STACKBLOATLESS_CLIENT_ID");
+const API_KEY: Option<&str> = option_env!("STACKBLOATLESS_API_KEY");
 
 // When it's not big enough some results might be missing.
 const API_SITE_PAGESIZE: &str = "100";
+// Only the most relevant related or linked questions are shown.
+const API_RELATED_PAGESIZE: &str = "10";
+// Number of questions in lists like tag pages.
+const API_LIST_PAGESIZE: &str = "30";
+// Number of top tags and recent answers shown in user profiles.
+const API_PROFILE_PAGESIZE: &str = "10";
+const API_SUGGESTION_PAGESIZE: &str = "5";
+// Most ids a single vectorized request accepts, more need several requests.
+const API_MAX_IDS: usize = 100;
+// The least recently used responses are dropped beyond it.
+const MAX_CACHED_RESPONSES: usize = 200;
 
 pub type Id = u32; // Since all operations are in strings not integers.
 pub type Uri = String;
-type Date = i64;
+pub type Date = OffsetDateTime;
+
+/// Replace HTML entities, like `&lt;`, with the characters they stand for.
+pub fn decode_html_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| (&rest[1..end], end));
+
+        let character = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let code = match name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => name.strip_prefix('#')?.parse().ok(),
+                };
+                code.and_then(char::from_u32)
+            }
+        });
+
+        match (character, entity) {
+            (Some(character), Some((_, end))) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+// The API escapes text fields like HTML, even `body_markdown` which is the markdown source of
+// posts. Titles are decoded once too, so they can be shown and saved as is.
+fn deserialize_decoded<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    Ok(decode_html_entities(&String::deserialize(deserializer)?))
+}
+
+fn deserialize_optional_decoded<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(
+        Option::<String>::deserialize(deserializer)?
+            .map(|markdown| decode_html_entities(&markdown)),
+    )
+}
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct User {
-    display_name: String,
-    link: Option<String>, // Url
-    reputation: Option<u32>,
-    user_id: Option<Id>,
+    pub display_name: String,
+    pub link: Option<String>, // Url
+    pub reputation: Option<u32>,
+    // Missing for anonymous and deleted users.
+    pub user_id: Option<Id>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct BadgeCounts {
+    pub bronze: u32,
+    pub gold: u32,
+    pub silver: u32,
+}
+
+/// A user with the information shown in their profile.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UserProfile {
+    pub badge_counts: BadgeCounts,
+    pub display_name: String,
+    pub link: String, // Url
+    pub location: Option<String>,
+    pub reputation: u32,
+    // The API site parameter this user was fetched from, it's not part of the response.
+    #[serde(skip)]
+    pub site: String,
+}
+
+/// A tag a user is active in.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TopTag {
+    pub answer_count: u32,
+    pub answer_score: i32,
+    pub question_count: u32,
+    pub tag_name: String,
+}
+
+/// An answer without its content, used for lists of answers.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnswerSummary {
+    pub is_accepted: bool,
+    pub question_id: Id,
+    pub score: i32,
+    // Title of the question.
+    #[serde(deserialize_with = "deserialize_decoded")]
+    pub title: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Comment {
+    #[serde(default, deserialize_with = "deserialize_optional_decoded")]
     pub body_markdown: Option<String>,
-    comment_id: Id,
-    creation_date: Date,
-    owner: User,
-    post_id: Id,
+    pub comment_id: Id,
+    #[serde(with = "time::serde::timestamp")]
+    pub creation_date: Date,
+    #[serde(default)]
+    pub edited: bool,
+    pub owner: User,
+    pub post_id: Id,
     pub score: i32,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Answer {
-    answer_id: Id,
+    pub answer_id: Id,
+    #[serde(deserialize_with = "deserialize_decoded")]
     pub body_markdown: String,
-    comment_count: u32,
+    pub comment_count: u32,
     pub comments: Option<Vec<Comment>>,
-    creation_date: Date,
-    is_accepted: bool,
-    last_activity_date: Date,
-    owner: User,
+    #[serde(with = "time::serde::timestamp")]
+    pub creation_date: Date,
+    // Only included for authenticated requests.
+    #[serde(default)]
+    pub downvoted: bool,
+    pub is_accepted: bool,
+    #[serde(with = "time::serde::timestamp")]
+    pub last_activity_date: Date,
+    // Missing for answers that were never edited.
+    #[serde(default, with = "time::serde::timestamp::option")]
+    pub last_edit_date: Option<Date>,
+    pub owner: User,
     pub score: i32,
+    // Only included for authenticated requests.
+    #[serde(default)]
+    pub upvoted: bool,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Question {
+    pub accepted_answer_id: Option<Id>,
     pub answer_count: u32,
     pub answers: Option<Vec<Answer>>,
+    #[serde(deserialize_with = "deserialize_decoded")]
     pub body_markdown: String,
+    // Only included while a bounty is open.
+    pub bounty_amount: Option<u32>,
+    #[serde(default, with = "time::serde::timestamp::option")]
+    pub bounty_closes_date: Option<Date>,
+    // Only included for closed questions, for example: Duplicate or Needs more focus
+    #[serde(default, with = "time::serde::timestamp::option")]
+    pub closed_date: Option<Date>,
+    pub closed_reason: Option<String>,
     pub comment_count: u32,
     pub comments: Option<Vec<Comment>>,
-    creation_date: Date,
+    #[serde(with = "time::serde::timestamp")]
+    pub creation_date: Date,
+    // Only included for authenticated requests.
+    #[serde(default)]
+    pub downvoted: bool,
+    // Only included for authenticated requests.
+    #[serde(default)]
+    pub favorited: bool,
     pub is_answered: bool,
-    last_activity_date: Date,
-    link: String, // Url
-    owner: User,
-    question_id: Id,
+    #[serde(with = "time::serde::timestamp")]
+    pub last_activity_date: Date,
+    // Missing for questions that were never edited.
+    #[serde(default, with = "time::serde::timestamp::option")]
+    pub last_edit_date: Option<Date>,
+    pub link: String, // Url
+    pub owner: User,
+    pub question_id: Id,
     pub score: i32,
-    tags: Vec<String>,
+    pub tags: Vec<String>,
+    #[serde(deserialize_with = "deserialize_decoded")]
     pub title: String,
-    view_count: u32,
+    // Only included for authenticated requests.
+    #[serde(default)]
+    pub upvoted: bool,
+    pub view_count: u32,
+    // The API site parameter this question was fetched from, it's not part of the response.
+    #[serde(skip)]
+    pub site: String,
+    // When the question was fetched, if it's an old copy from the cache.
+    #[serde(skip)]
+    pub cached: Option<Date>,
 }
 
-pub struct StackExchange {
-    reqwest_client: reqwest::Client,
+/// A question without its content, used for lists of questions.
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestionSummary {
+    pub answer_count: u32,
+    // Only included while a bounty is open.
+    pub bounty_amount: Option<u32>,
+    // Only included for closed questions.
+    pub closed_reason: Option<String>,
+    pub is_answered: bool,
+    pub question_id: Id,
+    pub score: i32,
+    pub tags: Vec<String>,
+    #[serde(deserialize_with = "deserialize_decoded")]
+    pub title: String,
+    // The API site parameter this question was fetched from, it's not part of the response.
+    #[serde(skip)]
+    pub site: String,
 }
 
-impl StackExchange {
-    pub fn new() -> Self {
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct QuestionActivity {
+    pub answer_count: u32,
+    #[serde(with = "time::serde::timestamp")]
+    pub last_activity_date: Date,
+    pub question_id: Id,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TagInfo {
+    // Number of questions with this tag.
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TagWiki {
+    // Missing for tags without a wiki.
+    pub excerpt: Option<String>,
+}
+
+/// A search result, either a question or one of its answers.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchExcerpt {
+    // Answers only have the number of answers of their question.
+    #[serde(default)]
+    pub answer_count: u32,
+    pub answer_id: Option<Id>,
+    // HTML escaped text around the matches, which are wrapped in <span class="highlight">.
+    pub excerpt: String,
+    #[serde(default)]
+    pub is_answered: bool,
+    pub question_id: Id,
+    pub score: i32,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // HTML escaped title of the question.
+    pub title: String,
+    // The API site parameter this result was fetched from, it's not part of the response.
+    #[serde(skip)]
+    pub site: String,
+}
+
+/// A StackExchange site.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Site {
+    pub api_site_parameter: String,
+    pub icon_url: String, // Url
+}
+
+/// A change of the reputation of the logged in user.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReputationChange {
+    #[serde(with = "time::serde::timestamp")]
+    pub creation_date: Date,
+    pub reputation_change: i32,
+    // Why it changed, for example: post_upvoted, answer_accepted or bounty_earned
+    pub reputation_history_type: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxItemType {
+    Comment,
+    NewAnswer,
+    ChatMessage,
+    PostNotice,
+    ModeratorMessage,
+    MetaQuestion,
+    #[serde(other)]
+    Other,
+}
+
+impl InboxItemType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InboxItemType::Comment => "Comment",
+            InboxItemType::NewAnswer => "New answer",
+            InboxItemType::ChatMessage => "Chat message",
+            InboxItemType::PostNotice => "Post notice",
+            InboxItemType::ModeratorMessage => "Moderator message",
+            InboxItemType::MetaQuestion => "Meta question",
+            InboxItemType::Other => "Message",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct InboxSite {
+    pub api_site_parameter: String,
+}
+
+/// A new answer, comment or reply in the inbox of the logged in user.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InboxItem {
+    pub answer_id: Option<Id>,
+    #[serde(with = "time::serde::timestamp")]
+    pub creation_date: Date,
+    pub item_type: InboxItemType,
+    pub link: Option<String>, // Url
+    pub question_id: Option<Id>,
+    // Missing for items outside of sites, like chat messages.
+    pub site: Option<InboxSite>,
+    // HTML escaped.
+    pub title: String,
+}
+
+impl InboxItem {
+    /// The question of the item, scrolled to the answer if it's about one.
+    pub fn uri(&self) -> Option<Uri> {
+        let site = &self.site.as_ref()?.api_site_parameter;
+
+        match (self.answer_id, self.question_id) {
+            (Some(answer_id), _) => Some(format!("stackexchange://{site}/a/{answer_id}")),
+            (None, Some(question_id)) => Some(format!("stackexchange://{site}/{question_id}")),
+            _ => parse_uri(self.link.as_deref()?)
+                .ok()
+                .map(|link| link.to_uri()),
+        }
+    }
+}
+
+/// A parsed uri, answer links still need their question to be looked up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UriLink {
+    Questions {
+        site: String,
+        question_ids: Vec<Id>,
+        // An answer of the question to scroll to.
+        answer_id: Option<Id>,
+    },
+    Answer {
+        site: String,
+        answer_id: Id,
+    },
+    // Search terms, with the operators of `SearchQuery`.
+    Search {
+        site: String,
+        query: String,
+    },
+}
+
+impl UriLink {
+    /// The link as a `stackexchange://` uri, which is how web links are opened.
+    pub fn to_uri(&self) -> Uri {
+        match self {
+            UriLink::Questions {
+                site,
+                question_ids,
+                answer_id: None,
+            } => format!("stackexchange://{site}/{}", join_ids(question_ids)),
+            UriLink::Questions {
+                site,
+                question_ids,
+                answer_id: Some(answer_id),
+            } => format!(
+                "stackexchange://{site}/{}#{answer_id}",
+                join_ids(question_ids)
+            ),
+            UriLink::Answer { site, answer_id } => format!("stackexchange://{site}/a/{answer_id}"),
+            UriLink::Search { site, query } => {
+                let mut uri = Url::parse(&format!("stackexchange://{site}/search")).unwrap();
+                uri.query_pairs_mut().append_pair("q", query);
+                uri.to_string()
+            }
+        }
+    }
+}
+
+/// What a uri points to.
+#[derive(Debug, Clone)]
+pub struct UriTarget {
+    pub site: String,
+    pub question_ids: Vec<Id>,
+    /// For answer links, the answer to scroll to.
+    pub answer_id: Option<Id>,
+}
+
+/// An edit of a post.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Revision {
+    // Edit summary.
+    pub comment: Option<String>,
+    #[serde(with = "time::serde::timestamp")]
+    pub creation_date: Date,
+    pub is_rollback: bool,
+    // Missing for edits that only changed tags.
+    pub revision_number: Option<u32>,
+    // Missing for community edits.
+    pub user: Option<User>,
+}
+
+/// Order of lists of questions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuestionSort {
+    Votes,
+    /// Questions with a lot of recent activity.
+    Hot,
+    /// By creation date.
+    Newest,
+    /// By last activity, like new answers and edits.
+    Active,
+}
+
+impl QuestionSort {
+    fn as_str(&self) -> &'static str {
+        match self {
+            QuestionSort::Votes => "votes",
+            QuestionSort::Hot => "hot",
+            QuestionSort::Newest => "creation",
+            QuestionSort::Active => "activity",
+        }
+    }
+}
+
+/// How other questions are related to a question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    /// Linked from or to the question, often duplicates.
+    Linked,
+    /// Similar questions picked by StackExchange.
+    Related,
+}
+
+impl Relation {
+    fn endpoint(&self) -> &'static str {
+        match self {
+            Relation::Linked => "linked",
+            Relation::Related => "related",
+        }
+    }
+}
+
+/// The kind of a post, since questions and answers have different endpoints for the same action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostKind {
+    Question,
+    Answer,
+}
+
+impl PostKind {
+    fn endpoint(&self) -> &'static str {
+        match self {
+            PostKind::Question => "questions",
+            PostKind::Answer => "answers",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vote {
+    Up,
+    Down,
+}
+
+impl Vote {
+    fn action(&self) -> &'static str {
+        match self {
+            Vote::Up => "upvote",
+            Vote::Down => "downvote",
+        }
+    }
+}
+
+/// Size of the responses before and after decompression, to verify the saved bandwidth.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransferStats {
+    pub requests: u32,
+    pub compressed_bytes: u64,
+    pub decompressed_bytes: u64,
+}
+
+/// Remaining requests of the daily API quota, it's shared by all sites.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub remaining: u32,
+    pub max: u32,
+}
+
+/// Length limits of comments, in characters.
+pub const COMMENT_MIN_LENGTH: usize = 15;
+pub const COMMENT_MAX_LENGTH: usize = 600;
+
+/// Requests sent at the same time by default, others wait for one of them to finish.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// How long to wait for the API before giving up on a request attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// To establish the connection.
+    pub connect: Duration,
+    /// For the whole request, including reading the response.
+    pub request: Duration,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            connect: Duration::from_secs(10),
+            request: Duration::from_secs(30),
+        }
+    }
+}
+
+/// How failed requests are retried, for timeouts, server errors and throttle violations.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Including the first attempt.
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
         Self {
-            reqwest_client: reqwest::Client::builder().gzip(true).build().unwrap(),
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
         }
     }
+}
 
-    pub async fn get_questions_from_uri(&self, uri: &str) -> Result<Vec<Question>, String> {
-        // Accept uris of form: stackexchange://{site}/{questions ids}
-        // For example: stackexchange://stackoverflow/123456;7891011;121314
-        let uri = Url::parse(uri).unwrap();
+impl RetryPolicy {
+    /// Exponential backoff with full jitter, so retries from many tabs don't hit the API at once.
+    fn delay(&self, attempt: u32) -> Duration {
+        let max_delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(self.max_delay);
 
-        // TODO: Check if shame is stackexchange or not.
-        // TODO: Check if questions ids are valid.
+        // A random number without pulling a dependency for it.
+        let random = RandomState::new().build_hasher().finish();
 
-        self.get_questions(uri.domain().unwrap(), uri.path()).await
+        max_delay.mul_f64((random % 1000) as f64 / 1000.0)
     }
+}
 
-    async fn get_questions(&self, site: &str, ids: &str) -> Result<Vec<Question>, String> {
-        // Docs: https://api.stackexchange.com/docs/questions-by-ids
-        //
-        // `ids` are in form of a path with multiple ids separated by ;
+/// Errors returned by the API, by their `error_id`.
+/// Docs: https://api.stackexchange.com/docs/error-handling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    BadParameter,
+    AccessTokenRequired,
+    InvalidAccessToken,
+    AccessDenied,
+    NoMethod,
+    KeyRequired,
+    AccessTokenCompromised,
+    WriteFailed,
+    DuplicateRequest,
+    InternalError,
+    ThrottleViolation,
+    TemporarilyUnavailable,
+    Unknown(u64),
+}
+
+impl ApiErrorKind {
+    pub fn from_id(id: u64) -> Self {
+        match id {
+            400 => Self::BadParameter,
+            401 => Self::AccessTokenRequired,
+            402 => Self::InvalidAccessToken,
+            403 => Self::AccessDenied,
+            404 => Self::NoMethod,
+            405 => Self::KeyRequired,
+            406 => Self::AccessTokenCompromised,
+            407 => Self::WriteFailed,
+            409 => Self::DuplicateRequest,
+            500 => Self::InternalError,
+            502 => Self::ThrottleViolation,
+            503 => Self::TemporarilyUnavailable,
+            id => Self::Unknown(id),
+        }
+    }
+
+    /// The same request might succeed later.
+    pub fn is_transient(self) -> bool {
+        matches!(
+            self,
+            Self::InternalError | Self::ThrottleViolation | Self::TemporarilyUnavailable
+        )
+    }
+
+    /// The access token is missing or can't be used anymore, logging in again fixes it.
+    pub fn needs_login(self) -> bool {
+        matches!(
+            self,
+            Self::AccessTokenRequired | Self::InvalidAccessToken | Self::AccessTokenCompromised
+        )
+    }
+
+    /// What the user can do about it.
+    pub fn hint(self) -> &'static str {
+        match self {
+            Self::AccessTokenRequired | Self::InvalidAccessToken | Self::AccessTokenCompromised => {
+                "Your session has expired or was revoked, log in again to continue."
+            }
+            Self::AccessDenied => "Your account isn't allowed to do this on this site.",
+            Self::ThrottleViolation => {
+                "Too many requests were sent. Wait a few minutes, or lower the parallel requests \
+                in the preferences. The daily quota resets at midnight UTC."
+            }
+            Self::InternalError | Self::TemporarilyUnavailable => {
+                "StackExchange is having trouble, try again in a few minutes."
+            }
+            Self::DuplicateRequest | Self::WriteFailed => {
+                "The change wasn't saved, it might have been done already. Reload and try again."
+            }
+            Self::BadParameter | Self::NoMethod | Self::KeyRequired | Self::Unknown(_) => {
+                "This is most likely a bug, please report it with the message above."
+            }
+        }
+    }
+}
+
+/// Errors of the StackExchange client.
+#[derive(Debug, Clone)]
+pub enum StackExchangeError {
+    /// The request didn't reach the API, or the response was interrupted.
+    Network(String),
+    /// The response couldn't be parsed.
+    Json(String),
+    /// An error returned by the API.
+    /// Docs: https://api.stackexchange.com/docs/error-handling
+    Api {
+        kind: ApiErrorKind,
+        name: String,
+        message: String,
+    },
+    /// Nothing matched the request, like deleted questions.
+    NotFound,
+    InvalidUri(String),
+    /// Logging in is required, or it failed.
+    Authentication(String),
+    /// Offline mode is enabled and the content isn't cached.
+    Offline,
+}
+
+impl std::fmt::Display for StackExchangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StackExchangeError::Network(error) => write!(f, "Network error: {error}"),
+            StackExchangeError::Json(error) => write!(f, "Invalid response: {error}"),
+            StackExchangeError::Api { name, message, .. } => {
+                write!(f, "{}", if message.is_empty() { name } else { message })
+            }
+            StackExchangeError::NotFound => write!(f, "Not found or deleted"),
+            StackExchangeError::InvalidUri(uri) => write!(f, "Invalid link: {uri}"),
+            StackExchangeError::Authentication(error) => write!(f, "{error}"),
+            StackExchangeError::Offline => write!(f, "Not available offline"),
+        }
+    }
+}
+
+impl std::error::Error for StackExchangeError {}
+
+impl From<reqwest::Error> for StackExchangeError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_decode() {
+            StackExchangeError::Json(error.to_string())
+        } else {
+            StackExchangeError::Network(error.to_string())
+        }
+    }
+}
+
+impl From<json::Error> for StackExchangeError {
+    fn from(error: json::Error) -> Self {
+        StackExchangeError::Json(error.to_string())
+    }
+}
+
+/// Failure of a single request attempt.
+enum AttemptError {
+    /// Worth retrying, `backoff` is the time the API asked to wait.
+    Transient {
+        error: StackExchangeError,
+        backoff: Option<Duration>,
+    },
+    Permanent(StackExchangeError),
+}
+
+impl AttemptError {
+    fn from_reqwest(error: reqwest::Error) -> Self {
+        if error.is_timeout() || error.is_connect() {
+            Self::Transient {
+                error: error.into(),
+                backoff: None,
+            }
+        } else {
+            Self::Permanent(error.into())
+        }
+    }
+}
+
+/// Parse the error returned by the API if any.
+fn api_error(value: &json::Value) -> Option<StackExchangeError> {
+    let id = value.get("error_id")?.as_u64()?;
+
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(|field| field.as_str())
+            .unwrap_or_default()
+            .to_owned()
+    };
+
+    Some(StackExchangeError::Api {
+        kind: ApiErrorKind::from_id(id),
+        name: field("error_name"),
+        message: field("error_message"),
+    })
+}
+
+/// Decode a response body the measuring client left encoded. GIO's decompressor is used, since
+/// the one of reqwest can't be reached.
+fn decompress(body: &[u8], content_encoding: &str) -> std::io::Result<Vec<u8>> {
+    let format = match content_encoding {
+        "gzip" => gio::ZlibCompressorFormat::Gzip,
+        "deflate" => gio::ZlibCompressorFormat::Zlib,
+        _ => return Ok(body.to_vec()),
+    };
+
+    let stream = gio::ConverterInputStream::new(
+        &gio::MemoryInputStream::from_bytes(&glib::Bytes::from(body)),
+        &gio::ZlibDecompressor::new(format),
+    );
+    let mut decompressed = Vec::new();
+    stream.into_read().read_to_end(&mut decompressed)?;
+
+    Ok(decompressed)
+}
+
+/// A response kept to answer conditional requests.
+struct CachedResponse {
+    etag: String,
+    value: json::Value,
+    last_used: Instant,
+}
+
+/// The url of a request without the access token, so cached responses aren't kept by it. They
+/// are still only used if the API tells they're unchanged for the current user.
+fn response_cache_key(url: &Url) -> String {
+    let mut key = url.clone();
+    key.query_pairs_mut()
+        .clear()
+        .extend_pairs(url.query_pairs().filter(|(name, _)| name != "access_token"));
+
+    key.to_string()
+}
+
+type BackoffHandler = Box<dyn Fn(Duration)>;
+
+/// Credentials of the logged in user.
+#[derive(Clone)]
+struct Auth {
+    access_token: String,
+}
+
+pub struct StackExchange {
+    // The client is shared by the whole app, so its settings can change while requests are sent.
+    // Clients are cloned out before sending, borrows aren't held across awaits.
+    reqwest_client: RefCell<reqwest::Client>,
+    // Doesn't decompress responses by itself, used to measure their compressed size.
+    measuring_reqwest_client: RefCell<reqwest::Client>,
+    // Both clients are built with these.
+    proxy: RefCell<Option<String>>,
+    timeouts: Cell<Timeouts>,
+    measure_compression: Cell<bool>,
+    transfer_stats: Cell<TransferStats>,
+    // From the last response.
+    quota: Cell<Option<Quota>>,
+    pub retry_policy: RetryPolicy,
+    auth: RefCell<Option<Auth>>,
+    // Key of the user, sent with anonymous requests. Logged in requests use the app's key.
+    api_key: RefCell<Option<String>>,
+    // Created once per kind with `Filter::include()`, then kept in the disk cache and reused
+    // until its fields change.
+    filters: RefCell<HashMap<Filter, String>>,
+    // Responses that had an ETag, by `response_cache_key`.
+    cached_responses: RefCell<HashMap<String, CachedResponse>>,
+    // Recently fetched questions, kept on disk.
+    cache: Cache,
+    // Every site, loaded once from the cache or the API.
+    sites: RefCell<Option<Vec<Site>>>,
+    // Only serve cached questions, without any request.
+    offline: Cell<bool>,
+    // Don't write fetched questions to the disk cache.
+    private: Cell<bool>,
+    // Every request holds a permit while it's sent, which limits how many are sent at once.
+    // Requests already waiting keep the previous semaphore when the limit changes.
+    request_slots: RefCell<Arc<Semaphore>>,
+    // Called with the time the API asked to wait before hitting a method again.
+    backoff_handler: RefCell<Option<BackoffHandler>>,
+    // When each API method can be requested again, by `api_method`.
+    backoff_deadlines: RefCell<HashMap<String, tokio::time::Instant>>,
+}
+
+/// The API method requested by `url`, like `questions/{ids}/answers`. Backoffs apply to a
+/// method, whatever its ids and parameters.
+fn api_method(url: &Url) -> String {
+    let Some(segments) = url.path_segments() else {
+        return String::new();
+    };
+
+    segments
+        // The version of the API.
+        .skip(1)
+        .map(|segment| {
+            if segment.starts_with(|c: char| c.is_ascii_digit()) {
+                "{ids}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Percent-encode a value to be used in a path, like tags which can contain `#`.
+fn encode_path_segment(segment: &str) -> String {
+    let mut url = Url::parse("http://localhost/").unwrap();
+    url.path_segments_mut().unwrap().push(segment);
+
+    url.path()[1..].to_owned()
+}
+
+// Sites with their own domain, their meta sites are `meta.{site}` instead of `{site}.meta`.
+const DOMAIN_SITES: &[&str] = &[
+    "askubuntu",
+    "mathoverflow",
+    "serverfault",
+    "stackoverflow",
+    "superuser",
+];
+
+// Subdomains of stackexchange.com that aren't sites.
+const NON_SITE_SUBDOMAINS: &[&str] = &["api", "area51", "chat", "data", "openid"];
+
+// Short names of sites, for example stackexchange://so/123456 or `so:123456` in the search entry.
+// Sites whose name is already short are listed to be accepted as prefixes in the search entry.
+const SITE_ALIASES: &[(&str, &str)] = &[
+    ("au", "askubuntu"),
+    ("math", "math"),
+    ("mo", "mathoverflow"),
+    ("mse", "meta"),
+    ("mso", "meta.stackoverflow"),
+    ("sf", "serverfault"),
+    ("so", "stackoverflow"),
+    ("su", "superuser"),
+    ("ul", "unix"),
+];
+
+/// The API site parameter of a short name or a site with its own domain, `None` for anything
+/// else so it's not confused with search operators like `user:12345`.
+pub fn site_from_alias(alias: &str) -> Option<String> {
+    let alias = alias.to_lowercase();
+
+    SITE_ALIASES
+        .iter()
+        .find(|(other, _)| *other == alias)
+        .map(|(_, site)| site.to_string())
+        .or_else(|| DOMAIN_SITES.contains(&alias.as_str()).then_some(alias))
+}
+
+/// The API site parameter of a site written in a uri. Meta sites can be written like their
+/// domains, for example `meta.stackoverflow` or `meta.math` for `math.meta`, and sites can be
+/// written with their short names, like `so` or `meta.so`.
+pub fn site_parameter(site: &str) -> String {
+    let expand = |site: &str| site_from_alias(site).unwrap_or_else(|| site.to_owned());
+
+    match site.strip_prefix("meta.") {
+        Some(parent) => {
+            let parent = expand(parent);
+            if DOMAIN_SITES.contains(&parent.as_str()) {
+                format!("meta.{parent}")
+            } else {
+                format!("{parent}.meta")
+            }
+        }
+        None => expand(site),
+    }
+}
+
+/// The API site parameter of a site's web domain, like `math` for `math.stackexchange.com`.
+fn site_from_domain(domain: &str) -> Option<String> {
+    let domain = domain.strip_prefix("www.").unwrap_or(domain);
+
+    if let Some(site) = domain.strip_suffix(".stackexchange.com") {
+        return (!NON_SITE_SUBDOMAINS.contains(&site)).then(|| site_parameter(site));
+    }
+
+    // Other domains are only the sites having their own, along with their meta and localized
+    // sites like `meta.askubuntu.com` or `ru.stackoverflow.com`.
+    let site = domain
+        .strip_suffix(".com")
+        .or_else(|| domain.strip_suffix(".net"))?;
+    let parent = site.rsplit('.').next()?;
+    if !DOMAIN_SITES.contains(&parent) || !domain.ends_with(&site_domain(parent)) {
+        return None;
+    }
+
+    Some(site_parameter(site))
+}
+
+/// The web domain of a site, the reverse of `site_from_domain`.
+fn site_domain(site: &str) -> String {
+    if let Some(parent) = site
+        .strip_suffix(".meta")
+        .or_else(|| site.strip_prefix("meta."))
+    {
+        return format!("meta.{}", site_domain(parent));
+    }
+
+    match site {
+        "mathoverflow" => "mathoverflow.net".to_owned(),
+        // Including localized sites like `ru.stackoverflow`.
+        _ if DOMAIN_SITES.contains(&site) || site.ends_with(".stackoverflow") => {
+            format!("{site}.com")
+        }
+        _ => format!("{site}.stackexchange.com"),
+    }
+}
+
+/// The web link of a question, to be shared.
+pub fn question_url(site: &str, question_id: Id) -> String {
+    format!("https://{}/q/{question_id}", site_domain(site))
+}
+
+/// The canonical web page of a question, StackExchange redirects it to the one with the title.
+pub fn question_page_url(site: &str, question_id: Id) -> String {
+    format!("https://{}/questions/{question_id}", site_domain(site))
+}
+
+/// Parse a uri without any request.
+pub fn parse_uri(uri: &str) -> Result<UriLink, StackExchangeError> {
+    // Accept uris of form: stackexchange://{site}/{questions ids}
+    // For example: stackexchange://stackoverflow/123456;7891011;121314
+    //              stackexchange://stackoverflow/123456,7891011,121314
+    //              stackexchange://meta.stackoverflow/12345
+    //              stackexchange://stackoverflow/123456#7891011 (scrolled to the answer 7891011)
+    // And answer links: stackexchange://stackoverflow/a/123456
+    // And searches: stackexchange://askubuntu/search?q=wifi+driver
+    // And web links, like the ones copied from the browser or the share button:
+    //     https://stackoverflow.com/questions/123456/some-title
+    //     https://stackoverflow.com/questions/123456/some-title/7891011#7891011
+    //     https://math.stackexchange.com/q/123456/121314
+    //     https://stackoverflow.com/a/123456/121314
+    //     https://stackoverflow.com/search?q=%5Brust%5D+lifetimes
+    let invalid_uri = || StackExchangeError::InvalidUri(uri.to_owned());
+    let parse_id = |id: Option<&str>| id.and_then(|id| id.parse::<Id>().ok());
+
+    let parsed_uri = Url::parse(uri).map_err(|_| invalid_uri())?;
+
+    let domain = parsed_uri.domain().ok_or_else(invalid_uri)?;
+    let site = match parsed_uri.scheme() {
+        "stackexchange" => site_parameter(domain),
+        "http" | "https" => site_from_domain(domain).ok_or_else(invalid_uri)?,
+        _ => return Err(invalid_uri()),
+    };
+
+    let mut segments = parsed_uri
+        .path()
+        .split('/')
+        .filter(|segment| !segment.is_empty());
+
+    // Answer links of web pages are their fragment, comment links like #comment123_456 aren't.
+    let fragment_answer_id = parse_id(parsed_uri.fragment());
+
+    match segments.next() {
+        // The user id after the answer id is ignored, it's there for the share badges.
+        Some("a") => {
+            let answer_id = parse_id(segments.next()).ok_or_else(invalid_uri)?;
+            Ok(UriLink::Answer { site, answer_id })
+        }
+        Some("q") => Ok(UriLink::Questions {
+            site,
+            question_ids: vec![parse_id(segments.next()).ok_or_else(invalid_uri)?],
+            answer_id: fragment_answer_id,
+        }),
+        // The slug is ignored, the answer id can follow it.
+        Some("questions") => {
+            let question_id = parse_id(segments.next()).ok_or_else(invalid_uri)?;
+            let _slug = segments.next();
+
+            Ok(UriLink::Questions {
+                site,
+                question_ids: vec![question_id],
+                answer_id: parse_id(segments.next()).or(fragment_answer_id),
+            })
+        }
+        Some("search") if segments.next().is_none() => {
+            let query = parsed_uri
+                .query_pairs()
+                .find(|(name, _)| name == "q")
+                .map(|(_, query)| query.trim().to_owned())
+                .filter(|query| !query.is_empty())
+                .ok_or_else(invalid_uri)?;
+
+            Ok(UriLink::Search { site, query })
+        }
+        // The path has multiple ids separated by ; or ,
         // For example: /123456;78910;111213
-        let mut url = Url::parse(API_ENDPOINT)
-            .unwrap()
-            .join(&format!("questions{}", ids))
-            .unwrap();
+        Some(ids) if segments.next().is_none() => {
+            let question_ids = ids
+                .split([';', ','])
+                .filter(|id| !id.is_empty())
+                .map(|id| id.parse::<Id>())
+                .collect::<Result<Vec<Id>, _>>()
+                .map_err(|_| invalid_uri())?;
+
+            if question_ids.is_empty() {
+                return Err(invalid_uri());
+            }
+
+            // Scrolling to an answer only makes sense with one question.
+            let answer_id = fragment_answer_id.filter(|_| question_ids.len() == 1);
+
+            Ok(UriLink::Questions {
+                site,
+                question_ids,
+                answer_id,
+            })
+        }
+        _ => Err(invalid_uri()),
+    }
+}
+
+/// The title of a question guessed from the slug of its web link, for example
+/// https://stackoverflow.com/questions/123456/some-title gives "some title".
+pub fn title_from_slug(uri: &str) -> Option<String> {
+    let parsed_uri = Url::parse(uri).ok()?;
+    let mut segments = parsed_uri.path_segments()?;
+
+    if segments.next() != Some("questions") {
+        return None;
+    }
+    segments.next()?.parse::<Id>().ok()?;
+
+    segments
+        .next()
+        .map(|slug| slug.replace('-', " ").trim().to_owned())
+        .filter(|title| !title.is_empty())
+}
 
-        url.set_query(Some(&format!(
-            "site={site}&filter={API_QUESTIONS_FILTER}&pagesize={API_SITE_PAGESIZE}"
-        )));
+/// Ids in the form expected by vectorized requests, for example: 123456;78910;111213
+fn join_ids(ids: &[Id]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
+/// A client that decompresses responses by itself if `decompress` is true, requests go through
+/// `proxy` if it's set.
+fn build_client(
+    decompress: bool,
+    proxy: Option<&str>,
+    timeouts: Timeouts,
+) -> Result<reqwest::Client, reqwest::Error> {
+    let mut builder = reqwest::Client::builder()
+        .gzip(decompress)
+        .deflate(decompress)
+        .connect_timeout(timeouts.connect)
+        .timeout(timeouts.request);
+
+    let env = |name: &str| {
+        std::env::var(name.to_uppercase())
+            .or_else(|_| std::env::var(name))
+            .ok()
+            .filter(|value| !value.is_empty())
+    };
+
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    } else if let (None, Some(all_proxy)) = (env("https_proxy"), env("all_proxy")) {
+        // reqwest only reads `http_proxy` and `https_proxy` by itself.
+        match reqwest::Proxy::all(&all_proxy) {
+            Ok(all_proxy) => builder = builder.proxy(all_proxy),
+            Err(error) => eprintln!("Ignoring invalid all_proxy: {error}"),
+        }
+    }
+
+    builder.build()
+}
 
-        let res = self.reqwest_client.get(url).send().await.unwrap();
+/// Url of an API method, `path` is relative to the API version.
+fn api_url(path: &str) -> Url {
+    Url::parse(&format!("{API_ENDPOINT}/"))
+        .unwrap()
+        .join(path)
+        .unwrap()
+}
 
-        let value: json::Value = res.json().await.unwrap();
+impl StackExchange {
+    pub fn new() -> Self {
+        Self {
+            reqwest_client: RefCell::new(build_client(true, None, Timeouts::default()).unwrap()),
+            measuring_reqwest_client: RefCell::new(
+                build_client(false, None, Timeouts::default()).unwrap(),
+            ),
+            proxy: RefCell::new(None),
+            timeouts: Cell::new(Timeouts::default()),
+            measure_compression: Cell::new(false),
+            transfer_stats: Cell::new(TransferStats::default()),
+            quota: Cell::new(None),
+            retry_policy: RetryPolicy::default(),
+            auth: RefCell::new(None),
+            api_key: RefCell::new(None),
+            filters: RefCell::new(HashMap::new()),
+            cached_responses: RefCell::new(HashMap::new()),
+            cache: Cache::new(),
+            sites: RefCell::new(None),
+            offline: Cell::new(false),
+            private: Cell::new(false),
+            request_slots: RefCell::new(Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS))),
+            backoff_handler: RefCell::new(None),
+            backoff_deadlines: RefCell::new(HashMap::new()),
+        }
+    }
 
-        // TODO: Handle backend errors
-        if let Some(error_id) = value.get("error_id") {
-            // value.get("error_name");
-            // value.get("error_message");
-            return Err(error_id.to_string());
+    /// A client with the network settings of `settings`, invalid ones are ignored.
+    pub fn from_settings(settings: &Settings) -> Self {
+        let client = Self::new();
+        if let Err(error) = client.set_proxy(settings.proxy.as_deref()) {
+            eprintln!("Ignoring the proxy setting: {error}");
         }
+        if let Err(error) = client.set_timeouts(settings.timeouts()) {
+            eprintln!("Ignoring the timeout settings: {error}");
+        }
+        client.set_max_concurrent_requests(settings.max_concurrent_requests);
+        client.set_api_key(settings.api_key.clone());
 
-        Ok(json::from_value::<Vec<Question>>(value.get("items").unwrap().to_owned()).unwrap())
+        client
     }
 
-    // TODO: Search function
-    // fn search_questions() {
-    //     // Docs: https://api.stackexchange.com/docs/search
-    //     todo!();
-    // }
+    /// The client of the app, built from the saved settings on first use. The window and the
+    /// search provider share it, so they share its backoffs, quota and request limit, and the
+    /// settings changed in the window apply to both.
+    pub fn shared() -> Rc<Self> {
+        thread_local! {
+            static SHARED: Rc<StackExchange> =
+                Rc::new(StackExchange::from_settings(&Settings::load()));
+        }
+
+        SHARED.with(Rc::clone)
+    }
+
+    /// Call `handler` whenever a response asks to back off, so it can be shown to the user.
+    pub fn connect_backoff(&self, handler: impl Fn(Duration) + 'static) {
+        *self.backoff_handler.borrow_mut() = Some(Box::new(handler));
+    }
+
+    /// Send requests through `proxy`, like `socks5://127.0.0.1:9050`, or through the proxy set
+    /// in the environment if it's `None`.
+    pub fn set_proxy(&self, proxy: Option<&str>) -> Result<(), StackExchangeError> {
+        self.rebuild_clients(proxy.map(|proxy| proxy.to_owned()), self.timeouts.get())
+            .map_err(|error| StackExchangeError::Network(format!("Invalid proxy: {error}")))
+    }
+
+    pub fn set_timeouts(&self, timeouts: Timeouts) -> Result<(), StackExchangeError> {
+        self.rebuild_clients(self.proxy.borrow().clone(), timeouts)
+            .map_err(StackExchangeError::from)
+    }
+
+    fn rebuild_clients(
+        &self,
+        proxy: Option<String>,
+        timeouts: Timeouts,
+    ) -> Result<(), reqwest::Error> {
+        // Build both before replacing any, so an error leaves the clients unchanged.
+        let reqwest_client = build_client(true, proxy.as_deref(), timeouts)?;
+        let measuring_reqwest_client = build_client(false, proxy.as_deref(), timeouts)?;
+
+        *self.reqwest_client.borrow_mut() = reqwest_client;
+        *self.measuring_reqwest_client.borrow_mut() = measuring_reqwest_client;
+        *self.proxy.borrow_mut() = proxy;
+        self.timeouts.set(timeouts);
+
+        Ok(())
+    }
+
+    pub fn set_api_key(&self, api_key: Option<String>) {
+        *self.api_key.borrow_mut() = api_key;
+    }
+
+    /// Limit how many requests are sent at once, like when a session with many tabs is restored.
+    pub fn set_max_concurrent_requests(&self, limit: usize) {
+        *self.request_slots.borrow_mut() = Arc::new(Semaphore::new(limit.max(1)));
+    }
+
+    /// Wait until a request can be sent without exceeding the limit, the request is sent while
+    /// the permit is held.
+    async fn request_slot(&self) -> Result<OwnedSemaphorePermit, AcquireError> {
+        let request_slots = self.request_slots.borrow().clone();
+        request_slots.acquire_owned().await
+    }
+
+    /// In offline mode questions are only loaded from the cache, other requests fail.
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.set(offline);
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline.get()
+    }
+
+    /// In private mode fetched questions aren't kept in the disk cache, the cached ones are still
+    /// used.
+    pub fn set_private(&self, private: bool) {
+        self.private.set(private);
+    }
+
+    /// Measure the size of responses before and after decompression, it's slower since
+    /// responses are decompressed manually.
+    pub fn set_measure_compression(&self, measure_compression: bool) {
+        self.measure_compression.set(measure_compression);
+    }
+
+    pub fn measure_compression(&self) -> bool {
+        self.measure_compression.get()
+    }
+
+    pub fn transfer_stats(&self) -> TransferStats {
+        self.transfer_stats.get()
+    }
+
+    /// Quota reported by the last response, `None` before the first request.
+    pub fn quota(&self) -> Option<Quota> {
+        self.quota.get()
+    }
+
+    /// Every response includes the quota, even error responses.
+    fn update_quota(&self, value: &json::Value) {
+        let field = |name| {
+            value
+                .get(name)
+                .and_then(|field: &json::Value| field.as_u64())
+                .map(|field| field as u32)
+        };
+
+        if let (Some(remaining), Some(max)) = (field("quota_remaining"), field("quota_max")) {
+            self.quota.set(Some(Quota { remaining, max }));
+        }
+    }
+
+    fn notify_backoff(&self, backoff: Duration) {
+        if let Some(handler) = &*self.backoff_handler.borrow() {
+            handler(backoff);
+        }
+    }
+
+    /// Send a GET request and parse the response, retrying on transient failures according to
+    /// the retry policy.
+    async fn get_json(&self, url: Url) -> Result<json::Value, StackExchangeError> {
+        if self.is_offline() {
+            return Err(StackExchangeError::Offline);
+        }
+
+        let mut attempt = 1;
+
+        loop {
+            match self.get_json_once(url.clone()).await {
+                Ok(value) => return Ok(value),
+                Err(AttemptError::Permanent(error)) => return Err(error),
+                Err(AttemptError::Transient { error, backoff }) => {
+                    if attempt >= self.retry_policy.max_attempts {
+                        return Err(error);
+                    }
+
+                    // The API asks to wait `backoff` seconds before hitting the same method again.
+                    let delay = self
+                        .retry_policy
+                        .delay(attempt)
+                        .max(backoff.unwrap_or_default());
+                    tokio::time::sleep(delay).await;
+
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Send a GET request and parse the response, which is always compressed by StackExchange.
+    async fn get_json_once(&self, url: Url) -> Result<json::Value, AttemptError> {
+        // The API asked to wait before hitting this method again.
+        let method = api_method(&url);
+        let deadline = self.backoff_deadlines.borrow().get(&method).copied();
+        if let Some(deadline) = deadline {
+            // The app only sends requests from spawned tasks, so waiting doesn't block the window.
+            // Told again, since the toast of the response that asked for it might be gone.
+            let now = tokio::time::Instant::now();
+            if deadline > now {
+                self.notify_backoff(deadline - now);
+                tokio::time::sleep_until(deadline).await;
+            }
+            self.backoff_deadlines
+                .borrow_mut()
+                .retain(|_, deadline| *deadline > tokio::time::Instant::now());
+        }
+
+        // Wait for a slot for every attempt, so retries don't hold one while sleeping.
+        let _slot = self.request_slot().await;
+
+        // Changing it while the request is sent only applies to the next ones.
+        let measure_compression = self.measure_compression();
+        let client = if measure_compression {
+            self.measuring_reqwest_client.borrow().clone()
+        } else {
+            self.reqwest_client.borrow().clone()
+        };
+
+        let cache_key = response_cache_key(&url);
+
+        let send = |etag: Option<String>| {
+            let mut request = client.get(url.clone());
+            if measure_compression {
+                request = request.header(reqwest::header::ACCEPT_ENCODING, "gzip, deflate");
+            }
+            // Unchanged responses are answered with an empty 304 response.
+            if let Some(etag) = etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            request.send()
+        };
+
+        let cached_etag = self
+            .cached_responses
+            .borrow()
+            .get(&cache_key)
+            .map(|cached| cached.etag.clone());
+        let mut res = send(cached_etag)
+            .await
+            .map_err(AttemptError::from_reqwest)?;
+
+        if res.status() == reqwest::StatusCode::NOT_MODIFIED {
+            if let Some(cached) = self.cached_responses.borrow_mut().get_mut(&cache_key) {
+                cached.last_used = Instant::now();
+                return Ok(cached.value.clone());
+            }
+
+            // Evicted while the request was sent, so it's sent again for the whole response.
+            res = send(None).await.map_err(AttemptError::from_reqwest)?;
+        }
+
+        let etag = res
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_owned());
+
+        if res.status().is_server_error() {
+            return Err(AttemptError::Transient {
+                error: StackExchangeError::Network(format!("Server error: {}", res.status())),
+                backoff: None,
+            });
+        }
+
+        let value: json::Value = if measure_compression {
+            let content_encoding = res
+                .headers()
+                .get(reqwest::header::CONTENT_ENCODING)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_owned();
+
+            // The size sent by the server, the body itself when it doesn't tell.
+            let content_length = res.content_length();
+            let compressed = res.bytes().await.map_err(AttemptError::from_reqwest)?;
+            let decompressed = decompress(&compressed, &content_encoding).map_err(|error| {
+                AttemptError::Permanent(StackExchangeError::Json(error.to_string()))
+            })?;
+
+            let mut stats = self.transfer_stats.get();
+            stats.requests += 1;
+            stats.compressed_bytes += content_length.unwrap_or(compressed.len() as u64);
+            stats.decompressed_bytes += decompressed.len() as u64;
+            self.transfer_stats.set(stats);
+
+            json::from_slice(&decompressed)
+                .map_err(|error| AttemptError::Permanent(error.into()))?
+        } else {
+            res.json().await.map_err(AttemptError::from_reqwest)?
+        };
+
+        self.update_quota(&value);
+
+        // Successful responses can ask to back off too.
+        let backoff = value
+            .get("backoff")
+            .and_then(|backoff| backoff.as_u64())
+            .map(Duration::from_secs);
+        if let Some(backoff) = backoff {
+            self.backoff_deadlines
+                .borrow_mut()
+                .insert(method, tokio::time::Instant::now() + backoff);
+            self.notify_backoff(backoff);
+        }
+
+        // Docs: https://api.stackexchange.com/docs/error-handling
+        if let Some(error) = api_error(&value) {
+            let is_transient = matches!(
+                &error,
+                StackExchangeError::Api { kind, .. } if kind.is_transient()
+            );
+
+            return Err(match error {
+                error if is_transient => AttemptError::Transient { error, backoff },
+                _ => AttemptError::Permanent(error),
+            });
+        }
+
+        if let Some(etag) = etag {
+            let mut cached_responses = self.cached_responses.borrow_mut();
+            if cached_responses.len() >= MAX_CACHED_RESPONSES
+                && !cached_responses.contains_key(&cache_key)
+            {
+                let least_recently_used = cached_responses
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.last_used)
+                    .map(|(key, _)| key.clone());
+                if let Some(key) = least_recently_used {
+                    cached_responses.remove(&key);
+                }
+            }
+
+            cached_responses.insert(
+                cache_key,
+                CachedResponse {
+                    etag,
+                    value: value.clone(),
+                    last_used: Instant::now(),
+                },
+            );
+        }
+
+        Ok(value)
+    }
+
+    /// The custom filter to be passed to endpoints, it's created on first use and kept in the disk
+    /// cache for the next launches.
+    async fn filter(&self, filter: Filter) -> Result<String, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/create-filter
+        if let Some(created_filter) = self.filters.borrow().get(&filter) {
+            return Ok(created_filter.to_owned());
+        }
+
+        let include = filter.include();
+        if let Some(created_filter) = self.cache.get_filter(&include) {
+            self.filters
+                .borrow_mut()
+                .insert(filter, created_filter.clone());
+            return Ok(created_filter);
+        }
+
+        let mut url = api_url("filters/create");
+        url.query_pairs_mut()
+            .append_pair("include", &include)
+            .append_pair("base", "none")
+            .append_pair("unsafe", "false");
+
+        let value = self.get_json(url).await?;
+
+        let created_filter = value
+            .pointer("/items/0/filter")
+            .and_then(|created_filter| created_filter.as_str())
+            .ok_or_else(|| StackExchangeError::Json("Creating API filter failed".to_owned()))?
+            .to_owned();
+
+        self.cache.insert_filter(&include, &created_filter);
+        self.filters
+            .borrow_mut()
+            .insert(filter, created_filter.clone());
+
+        Ok(created_filter)
+    }
+
+    /// Url of the page where the user can authorize the app, `None` if the app was built without
+    /// API credentials.
+    pub fn login_url() -> Option<Url> {
+        let client_id = API_CLIENT_ID?;
+        // API key is required for every authenticated request.
+        API_KEY?;
+
+        let mut url = Url::parse(OAUTH_DIALOG).unwrap();
+        url.query_pairs_mut()
+            .append_pair("client_id", client_id)
+            .append_pair("scope", OAUTH_SCOPE)
+            .append_pair("redirect_uri", OAUTH_REDIRECT_URI);
+
+        Some(url)
+    }
+
+    /// Finish logging in using the url the user was redirected to after authorizing the app.
+    ///
+    /// The access token is in the fragment, for example:
+    /// https://stackoverflow.com/oauth/login_success#access_token=abc123&expires=86400
+    pub fn login(&self, redirect_url: &str) -> Result<(), StackExchangeError> {
+        let url = Url::parse(redirect_url.trim())
+            .map_err(|error| StackExchangeError::Authentication(error.to_string()))?;
+
+        let fragment = url.fragment().unwrap_or_default();
+
+        let access_token = Url::parse(&format!("http://localhost/?{fragment}"))
+            .unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == "access_token")
+            .map(|(_, value)| value.into_owned())
+            .ok_or_else(|| {
+                StackExchangeError::Authentication(
+                    "The link doesn't contain an access token".to_owned(),
+                )
+            })?;
+
+        *self.auth.borrow_mut() = Some(Auth { access_token });
+        self.cache.clear();
+
+        Ok(())
+    }
+
+    pub fn logout(&self) {
+        *self.auth.borrow_mut() = None;
+        self.cache.clear();
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.auth.borrow().is_some()
+    }
+
+    /// Add the credentials to the query of a request when the user is logged in, so the response
+    /// includes user specific fields like `favorited`. Otherwise add the key of the user if any.
+    fn authenticate_url(&self, url: &mut Url) {
+        if let Some(auth) = &*self.auth.borrow() {
+            url.query_pairs_mut()
+                .append_pair("key", API_KEY.unwrap_or_default())
+                .append_pair("access_token", &auth.access_token);
+        } else if let Some(api_key) = &*self.api_key.borrow() {
+            url.query_pairs_mut().append_pair("key", api_key);
+        }
+    }
+
+    /// Send an authenticated write request to `path`, like voting or favoriting a post, with
+    /// the `fields` it needs. Returns the whole response.
+    async fn post_action(
+        &self,
+        site: &str,
+        path: &str,
+        fields: &[(&str, &str)],
+    ) -> Result<json::Value, StackExchangeError> {
+        if self.is_offline() {
+            return Err(StackExchangeError::Offline);
+        }
+
+        let auth = self.auth.borrow().clone().ok_or_else(|| {
+            StackExchangeError::Authentication("You need to log in first".to_owned())
+        })?;
+
+        let url = api_url(path);
+
+        let _slot = self.request_slot().await;
+        let mut form = vec![
+            ("site", site),
+            ("key", API_KEY.unwrap_or_default()),
+            ("access_token", &auth.access_token),
+            ("preview", "false"),
+        ];
+        form.extend_from_slice(fields);
+
+        let client = self.reqwest_client.borrow().clone();
+        let res = client.post(url).form(&form).send().await?;
+
+        let value: json::Value = res.json().await?;
+
+        self.update_quota(&value);
+
+        if let Some(error) = api_error(&value) {
+            return Err(error);
+        }
+
+        Ok(value)
+    }
+
+    /// Id of the question `answer_id` answers.
+    pub async fn get_answer_question_id(
+        &self,
+        site: &str,
+        answer_id: Id,
+    ) -> Result<Id, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/answers-by-ids
+        let answer = self
+            .get_items::<AnswerSummary>(
+                site,
+                &format!("answers/{answer_id}"),
+                Filter::AnswerSummaries,
+                &[],
+            )
+            .await?
+            .pop()
+            .ok_or(StackExchangeError::NotFound)?;
+
+        Ok(answer.question_id)
+    }
+
+    pub async fn get_questions(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<Question>, StackExchangeError> {
+        self.fetch_questions(site, ids, true).await
+    }
+
+    /// Like `get_questions`, but recently cached copies are fetched again. They're only replaced
+    /// once the new ones are fetched, and still used if the network is down.
+    pub async fn refresh_questions(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<Question>, StackExchangeError> {
+        self.fetch_questions(site, ids, false).await
+    }
+
+    async fn fetch_questions(
+        &self,
+        site: &str,
+        ids: &[Id],
+        use_cache: bool,
+    ) -> Result<Vec<Question>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/questions-by-ids
+        let mut questions: Vec<Question> = Vec::with_capacity(ids.len());
+
+        // Serve recently fetched questions from the cache, or any cached copy when offline.
+        let mut missing_ids = Vec::new();
+        for &id in ids {
+            let cached = (use_cache || self.is_offline())
+                .then(|| self.cached_question(site, id, self.is_offline()))
+                .flatten();
+            match cached {
+                Some(question) => questions.push(question),
+                None => missing_ids.push(id),
+            }
+        }
+
+        if self.is_offline() {
+            if questions.is_empty() {
+                return Err(StackExchangeError::Offline);
+            }
+            missing_ids.clear();
+        }
+
+        // Batch the ids to use as few requests as possible, the batches are sent at once.
+        let batches =
+            futures::future::join_all(missing_ids.chunks(API_MAX_IDS).map(|ids| async move {
+                let items: Result<Vec<json::Value>, _> = self
+                    .get_items(
+                        site,
+                        &format!("questions/{}", join_ids(ids)),
+                        Filter::Questions,
+                        &[("pagesize", API_SITE_PAGESIZE)],
+                    )
+                    .await;
+                (ids, items)
+            }))
+            .await;
+
+        for (ids, items) in batches {
+            let items = match items {
+                Ok(items) => items,
+                // Fall back to old copies when the network is down.
+                Err(StackExchangeError::Network(error)) => {
+                    let cached = ids
+                        .iter()
+                        .filter_map(|&id| self.cached_question(site, id, true))
+                        .collect::<Vec<Question>>();
+
+                    if cached.is_empty() {
+                        return Err(StackExchangeError::Network(error));
+                    }
+
+                    questions.extend(cached);
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+
+            for item in items {
+                let question = Question::deserialize(&item)?;
+                if !self.private.get() {
+                    self.cache.insert(site, question.question_id, &item);
+                }
+                questions.push(question);
+            }
+        }
+
+        if questions.is_empty() {
+            return Err(StackExchangeError::NotFound);
+        }
+
+        for question in &mut questions {
+            question.site = site.to_owned();
+        }
+
+        // Keep the order of `ids`, the API sorts them by activity and cached ones come first.
+        questions.sort_by_key(|question| ids.iter().position(|&id| id == question.question_id));
+
+        Ok(questions)
+    }
+
+    /// A question from the cache, old copies are only used, and marked as `cached`, if `stale`
+    /// is true.
+    fn cached_question(&self, site: &str, id: Id, stale: bool) -> Option<Question> {
+        let entry = self.cache.get(site, id)?;
+        if !stale && !entry.is_fresh() {
+            return None;
+        }
+
+        let mut question = json::from_value::<Question>(entry.item).ok()?;
+        if stale {
+            question.cached = OffsetDateTime::from_unix_timestamp(entry.fetched).ok();
+        }
+
+        Some(question)
+    }
+
+    /// Answer count and last activity of the questions, always fetched since it's used to tell if
+    /// they changed.
+    pub async fn get_question_activity(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<QuestionActivity>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/questions-by-ids
+        let batches = futures::future::join_all(ids.chunks(API_MAX_IDS).map(|ids| async move {
+            self.get_items::<QuestionActivity>(
+                site,
+                &format!("questions/{}", join_ids(ids)),
+                Filter::QuestionActivity,
+                &[("pagesize", API_SITE_PAGESIZE)],
+            )
+            .await
+        }))
+        .await;
+
+        let mut activity: Vec<QuestionActivity> = Vec::with_capacity(ids.len());
+        for batch in batches {
+            activity.extend(batch?);
+        }
+
+        Ok(activity)
+    }
+
+    /// Answer count and last activity of the cached copy of the question.
+    pub fn cached_activity(&self, site: &str, id: Id) -> Option<QuestionActivity> {
+        self.cached_question(site, id, true)
+            .map(|question| QuestionActivity {
+                answer_count: question.answer_count,
+                last_activity_date: question.last_activity_date,
+                question_id: question.question_id,
+            })
+    }
+
+    /// Title of a cached copy of the question, no matter how old it is.
+    pub fn cached_title(&self, site: &str, id: Id) -> Option<String> {
+        self.cached_question(site, id, true)
+            .map(|question| question.title)
+    }
+
+    /// Questions related or linked to the question `id`, most relevant first.
+    pub async fn get_related_questions(
+        &self,
+        site: &str,
+        id: Id,
+        relation: Relation,
+    ) -> Result<Vec<QuestionSummary>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/related-questions
+        //       https://api.stackexchange.com/docs/linked-questions
+        let mut questions: Vec<QuestionSummary> = self
+            .get_items(
+                site,
+                &format!("questions/{id}/{}", relation.endpoint()),
+                Filter::QuestionSummaries,
+                &[("pagesize", API_RELATED_PAGESIZE), ("sort", "rank")],
+            )
+            .await?;
+
+        for question in &mut questions {
+            question.site = site.to_owned();
+        }
+
+        Ok(questions)
+    }
+
+    /// Send a GET request to `path` with `filter`, and parse the returned items.
+    async fn get_items<T: serde::de::DeserializeOwned>(
+        &self,
+        site: &str,
+        path: &str,
+        filter: Filter,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>, StackExchangeError> {
+        let filter = self.filter(filter).await?;
+
+        let mut url = api_url(path);
+        url.query_pairs_mut()
+            .append_pair("site", site)
+            .append_pair("filter", &filter)
+            .extend_pairs(query);
+        self.authenticate_url(&mut url);
+
+        let value = self.get_json(url).await?;
+
+        Ok(json::from_value(
+            value.get("items").cloned().unwrap_or_default(),
+        )?)
+    }
+
+    pub async fn get_users(
+        &self,
+        site: &str,
+        ids: &[Id],
+    ) -> Result<Vec<UserProfile>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/users-by-ids
+        let batches = futures::future::join_all(ids.chunks(API_MAX_IDS).map(|ids| async move {
+            self.get_items::<UserProfile>(
+                site,
+                &format!("users/{}", join_ids(ids)),
+                Filter::Users,
+                &[("pagesize", API_SITE_PAGESIZE)],
+            )
+            .await
+        }))
+        .await;
+
+        let mut users: Vec<UserProfile> = Vec::with_capacity(ids.len());
+        for batch in batches {
+            users.extend(batch?);
+        }
+
+        if users.is_empty() {
+            return Err(StackExchangeError::NotFound);
+        }
+
+        for user in &mut users {
+            user.site = site.to_owned();
+        }
+
+        Ok(users)
+    }
+
+    /// Tags the user `id` is most active in.
+    pub async fn get_user_top_tags(
+        &self,
+        site: &str,
+        id: Id,
+    ) -> Result<Vec<TopTag>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/top-tags-on-users
+        self.get_items(
+            site,
+            &format!("users/{id}/top-tags"),
+            Filter::TopTags,
+            &[("pagesize", API_PROFILE_PAGESIZE)],
+        )
+        .await
+    }
+
+    /// Answers recently posted by the user `id`.
+    pub async fn get_user_answers(
+        &self,
+        site: &str,
+        id: Id,
+    ) -> Result<Vec<AnswerSummary>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/answers-on-users
+        self.get_items(
+            site,
+            &format!("users/{id}/answers"),
+            Filter::AnswerSummaries,
+            &[
+                ("pagesize", API_PROFILE_PAGESIZE),
+                ("sort", "creation"),
+                ("order", "desc"),
+            ],
+        )
+        .await
+    }
+
+    pub async fn get_tags_info(
+        &self,
+        site: &str,
+        tags: &[String],
+    ) -> Result<Vec<TagInfo>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/tags-by-name
+        let tags = tags
+            .iter()
+            .map(|tag| encode_path_segment(tag))
+            .collect::<Vec<String>>()
+            .join(";");
+
+        self.get_items(site, &format!("tags/{tags}/info"), Filter::Tags, &[])
+            .await
+    }
+
+    pub async fn get_tags_wikis(
+        &self,
+        site: &str,
+        tags: &[String],
+    ) -> Result<Vec<TagWiki>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/wikis-by-tags
+        let tags = tags
+            .iter()
+            .map(|tag| encode_path_segment(tag))
+            .collect::<Vec<String>>()
+            .join(";");
+
+        self.get_items(site, &format!("tags/{tags}/wikis"), Filter::TagWikis, &[])
+            .await
+    }
+
+    /// Questions having all of `tags`, or any question of the site if `tags` is empty.
+    pub async fn get_tagged_questions(
+        &self,
+        site: &str,
+        tags: &[String],
+        sort: QuestionSort,
+    ) -> Result<Vec<QuestionSummary>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/questions
+        let tagged = tags.join(";");
+        let mut query = vec![
+            ("sort", sort.as_str()),
+            ("order", "desc"),
+            ("pagesize", API_LIST_PAGESIZE),
+        ];
+        if !tags.is_empty() {
+            query.push(("tagged", &tagged));
+        }
+
+        let mut questions: Vec<QuestionSummary> = self
+            .get_items(site, "questions", Filter::QuestionSummaries, &query)
+            .await?;
+
+        for question in &mut questions {
+            question.site = site.to_owned();
+        }
+
+        Ok(questions)
+    }
+
+    /// Questions whose title contains `text`, used as suggestions while typing a search.
+    pub async fn get_search_suggestions(
+        &self,
+        site: &str,
+        text: &str,
+    ) -> Result<Vec<QuestionSummary>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/search
+        let mut questions: Vec<QuestionSummary> = self
+            .get_items(
+                site,
+                "search",
+                Filter::QuestionSummaries,
+                &[
+                    ("intitle", text),
+                    ("sort", "relevance"),
+                    ("order", "desc"),
+                    ("pagesize", API_SUGGESTION_PAGESIZE),
+                ],
+            )
+            .await?;
+
+        for question in &mut questions {
+            question.site = site.to_owned();
+        }
+
+        Ok(questions)
+    }
+
+    /// Existing questions with a title similar to `title`, used as suggestions while searching.
+    pub async fn get_similar_questions(
+        &self,
+        site: &str,
+        title: &str,
+    ) -> Result<Vec<QuestionSummary>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/similar
+        let mut questions: Vec<QuestionSummary> = self
+            .get_items(
+                site,
+                "similar",
+                Filter::QuestionSummaries,
+                &[
+                    ("title", title),
+                    ("sort", "relevance"),
+                    ("order", "desc"),
+                    ("pagesize", API_SUGGESTION_PAGESIZE),
+                ],
+            )
+            .await?;
+
+        for question in &mut questions {
+            question.site = site.to_owned();
+        }
+
+        Ok(questions)
+    }
+
+    /// Every StackExchange site, including meta sites.
+    pub async fn get_sites(&self) -> Result<Vec<Site>, StackExchangeError> {
+        if let Some(sites) = self.sites.borrow().as_ref() {
+            return Ok(sites.clone());
+        }
+
+        let value = match self.cache.get_sites(self.is_offline()) {
+            Some(value) => value,
+            None => {
+                // Docs: https://api.stackexchange.com/docs/sites
+                let filter = self.filter(Filter::Sites).await?;
+                let mut items = Vec::new();
+
+                for page in 1.. {
+                    let mut url = api_url("sites");
+                    url.query_pairs_mut()
+                        .append_pair("filter", &filter)
+                        .append_pair("page", &page.to_string())
+                        .append_pair("pagesize", API_SITE_PAGESIZE);
+                    self.authenticate_url(&mut url);
+
+                    let value = self.get_json(url).await?;
+
+                    if let Some(json::Value::Array(page_items)) = value.get("items") {
+                        items.extend(page_items.iter().cloned());
+                    }
+                    if value.get("has_more") != Some(&json::Value::Bool(true)) {
+                        break;
+                    }
+                }
+
+                let value = json::Value::Array(items);
+                self.cache.insert_sites(&value);
+                value
+            }
+        };
+
+        let sites = json::from_value::<Vec<Site>>(value)?;
+        *self.sites.borrow_mut() = Some(sites.clone());
+
+        Ok(sites)
+    }
+
+    /// Path of the icon of `site`, it's downloaded the first time.
+    pub async fn get_site_icon(&self, site: &str) -> Result<PathBuf, StackExchangeError> {
+        if let Some(path) = self.cached_site_icon(site) {
+            return Ok(path);
+        }
+        let path = self.cache.icon_path(site);
+
+        if self.is_offline() {
+            return Err(StackExchangeError::Offline);
+        }
+
+        let icon_url = self
+            .get_sites()
+            .await?
+            .into_iter()
+            .find(|other| other.api_site_parameter == site)
+            .ok_or(StackExchangeError::NotFound)?
+            .icon_url;
+
+        let _slot = self.request_slot().await;
+        let client = self.reqwest_client.borrow().clone();
+        let icon = client
+            .get(icon_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+
+        self.cache.insert_icon(site, &icon);
+
+        // Saving it might have failed.
+        if path.exists() {
+            Ok(path)
+        } else {
+            Err(StackExchangeError::NotFound)
+        }
+    }
+
+    /// The icon of `site` if it was downloaded before, without any request.
+    pub fn cached_site_icon(&self, site: &str) -> Option<PathBuf> {
+        let path = self.cache.icon_path(site);
+
+        path.exists().then_some(path)
+    }
+
+    /// Newest questions having `tag`, from the site's feed instead of the API.
+    pub async fn get_tag_feed(
+        &self,
+        site: &str,
+        tag: &str,
+    ) -> Result<Vec<FeedEntry>, StackExchangeError> {
+        if self.is_offline() {
+            return Err(StackExchangeError::Offline);
+        }
+
+        let url = format!(
+            "https://{}/feeds/tag/{}",
+            site_domain(site),
+            encode_path_segment(tag)
+        );
+
+        let _slot = self.request_slot().await;
+        let client = self.reqwest_client.borrow().clone();
+        let xml = client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+
+        feeds::parse_feed(&xml)
+    }
+
+    /// Edit history of the post `id`, newest first.
+    pub async fn get_revisions(
+        &self,
+        site: &str,
+        id: Id,
+    ) -> Result<Vec<Revision>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/revisions-by-ids
+        self.get_items(
+            site,
+            &format!("posts/{id}/revisions"),
+            Filter::Revisions,
+            &[("pagesize", API_SITE_PAGESIZE)],
+        )
+        .await
+    }
+
+    /// Cast a vote on a post of the question `question_id`, or undo it when `undo` is true.
+    pub async fn vote(
+        &self,
+        site: &str,
+        question_id: Id,
+        post_kind: PostKind,
+        id: Id,
+        vote: Vote,
+        undo: bool,
+    ) -> Result<(), StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/upvote-question
+        //       https://api.stackexchange.com/docs/downvote-answer
+        //       https://api.stackexchange.com/docs/undo-upvote-question
+        let mut path = format!("{}/{id}/{}", post_kind.endpoint(), vote.action());
+        if undo {
+            path.push_str("/undo");
+        }
+
+        self.post_action(site, &path, &[]).await?;
+
+        // The cached question has the previous vote, along with its answers.
+        self.cache.remove(site, question_id);
+
+        Ok(())
+    }
+
+    /// Add a question to the user's favorites, or remove it when `favorite` is false.
+    pub async fn set_favorite(
+        &self,
+        site: &str,
+        id: Id,
+        favorite: bool,
+    ) -> Result<(), StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/favorite-question
+        //       https://api.stackexchange.com/docs/undo-favorite-question
+        let path = if favorite {
+            format!("questions/{id}/favorite")
+        } else {
+            format!("questions/{id}/favorite/undo")
+        };
+
+        self.post_action(site, &path, &[]).await?;
+        self.cache.remove(site, id);
+
+        Ok(())
+    }
+
+    /// Comment on the question `question_id` or one of its answers `post_id`, returns the new
+    /// comment.
+    pub async fn add_comment(
+        &self,
+        site: &str,
+        question_id: Id,
+        post_id: Id,
+        body: &str,
+    ) -> Result<Comment, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/create-comment
+        let filter = self.filter(Filter::Questions).await?;
+
+        let value = self
+            .post_action(
+                site,
+                &format!("posts/{post_id}/comments/add"),
+                &[("body", body), ("filter", &filter)],
+            )
+            .await?;
+
+        // The cached question doesn't have the comment, along with its answers.
+        self.cache.remove(site, question_id);
+
+        value
+            .get("items")
+            .and_then(|items| items.get(0))
+            .map(Comment::deserialize)
+            .ok_or(StackExchangeError::NotFound)?
+            .map_err(StackExchangeError::from)
+    }
+
+    /// Questions favorited by the logged in user on `site`.
+    pub async fn get_favorites(&self, site: &str) -> Result<Vec<Question>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/me-favorites
+        if !self.is_authenticated() {
+            return Err(StackExchangeError::Authentication(
+                "You need to log in first".to_owned(),
+            ));
+        }
+
+        let mut questions = self
+            .get_items::<Question>(
+                site,
+                "me/favorites",
+                Filter::Questions,
+                &[("pagesize", API_SITE_PAGESIZE)],
+            )
+            .await?;
+
+        for question in &mut questions {
+            question.site = site.to_owned();
+            // Every question in the list is a favorite even if the filter doesn't include it.
+            question.favorited = true;
+        }
+
+        Ok(questions)
+    }
+
+    /// Profile of the logged in user on `site`.
+    pub async fn get_me(&self, site: &str) -> Result<UserProfile, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/me
+        if !self.is_authenticated() {
+            return Err(StackExchangeError::Authentication(
+                "You need to log in first".to_owned(),
+            ));
+        }
+
+        let mut user = self
+            .get_items::<UserProfile>(site, "me", Filter::Users, &[])
+            .await?
+            .pop()
+            .ok_or(StackExchangeError::NotFound)?;
+        user.site = site.to_owned();
+
+        Ok(user)
+    }
+
+    /// Recent reputation changes of the logged in user on `site`, newest first.
+    pub async fn get_reputation_history(
+        &self,
+        site: &str,
+    ) -> Result<Vec<ReputationChange>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/me-full-reputation-history
+        if !self.is_authenticated() {
+            return Err(StackExchangeError::Authentication(
+                "You need to log in first".to_owned(),
+            ));
+        }
+
+        self.get_items(
+            site,
+            "me/reputation-history",
+            Filter::ReputationHistory,
+            &[("pagesize", API_LIST_PAGESIZE)],
+        )
+        .await
+    }
+
+    /// Unread items of the inbox of the logged in user, from every site.
+    pub async fn get_unread_inbox(&self) -> Result<Vec<InboxItem>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/me-unread-inbox
+        if !self.is_authenticated() {
+            return Err(StackExchangeError::Authentication(
+                "You need to log in first".to_owned(),
+            ));
+        }
+
+        let filter = self.filter(Filter::Inbox).await?;
+
+        let mut url = api_url("me/inbox/unread");
+        url.query_pairs_mut()
+            .append_pair("filter", &filter)
+            .append_pair("pagesize", API_LIST_PAGESIZE);
+        self.authenticate_url(&mut url);
+
+        let value = self.get_json(url).await?;
+
+        Ok(json::from_value(
+            value.get("items").cloned().unwrap_or_default(),
+        )?)
+    }
+
+    /// Questions and answers matching `query`, with excerpts of the matched text.
+    pub async fn search_excerpts(
+        &self,
+        site: &str,
+        query: &SearchQuery,
+    ) -> Result<Vec<SearchExcerpt>, StackExchangeError> {
+        // Docs: https://api.stackexchange.com/docs/excerpt-search
+        // It takes the same parameters as https://api.stackexchange.com/docs/advanced-search
+        let parameters = query.parameters();
+        let mut parameters = parameters
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .collect::<Vec<_>>();
+        parameters.extend([
+            ("sort", "relevance"),
+            ("order", "desc"),
+            ("pagesize", API_LIST_PAGESIZE),
+        ]);
+
+        let mut results: Vec<SearchExcerpt> = self
+            .get_items(site, "search/excerpts", Filter::SearchExcerpts, &parameters)
+            .await?;
+
+        for result in &mut results {
+            result.site = site.to_owned();
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn response_cache_keys_skip_access_tokens() {
+        let url = Url::parse(
+            "https://api.stackexchange.com/2.3/questions/1?site=superuser&key=abc&access_token=xyz",
+        )
+        .unwrap();
+
+        assert_eq!(
+            response_cache_key(&url),
+            "https://api.stackexchange.com/2.3/questions/1?site=superuser&key=abc"
+        );
+    }
+
+    #[test]
+    fn api_methods() {
+        let method = |url| api_method(&Url::parse(url).unwrap());
+
+        assert_eq!(
+            method("https://api.stackexchange.com/2.3/questions/1;2/answers?site=stackoverflow"),
+            "questions/{ids}/answers"
+        );
+        assert_eq!(
+            method("https://api.stackexchange.com/2.3/questions/3?site=superuser"),
+            "questions/{ids}"
+        );
+        assert_eq!(method("https://api.stackexchange.com/2.3/sites"), "sites");
+    }
+
+    fn questions(site: &str, question_ids: &[Id], answer_id: Option<Id>) -> UriLink {
+        UriLink::Questions {
+            site: site.to_owned(),
+            question_ids: question_ids.to_vec(),
+            answer_id,
+        }
+    }
+
+    #[test]
+    fn parse_question_uris() {
+        assert_eq!(
+            parse_uri("stackexchange://stackoverflow/123456").unwrap(),
+            questions("stackoverflow", &[123456], None)
+        );
+        assert_eq!(
+            parse_uri("https://stackoverflow.com/questions/123456/some-title").unwrap(),
+            questions("stackoverflow", &[123456], None)
+        );
+        assert_eq!(
+            parse_uri("https://math.stackexchange.com/q/123456/121314").unwrap(),
+            questions("math", &[123456], None)
+        );
+        assert_eq!(
+            parse_uri("https://meta.stackoverflow.com/questions/12345").unwrap(),
+            questions("meta.stackoverflow", &[12345], None)
+        );
+    }
+
+    #[test]
+    fn parse_answer_uris() {
+        assert_eq!(
+            parse_uri("stackexchange://stackoverflow/a/123456").unwrap(),
+            UriLink::Answer {
+                site: "stackoverflow".to_owned(),
+                answer_id: 123456,
+            }
+        );
+        assert_eq!(
+            parse_uri("https://stackoverflow.com/a/123456/121314").unwrap(),
+            UriLink::Answer {
+                site: "stackoverflow".to_owned(),
+                answer_id: 123456,
+            }
+        );
+        assert_eq!(
+            parse_uri("https://stackoverflow.com/questions/123456/some-title/7891011#7891011")
+                .unwrap(),
+            questions("stackoverflow", &[123456], Some(7891011))
+        );
+        assert_eq!(
+            parse_uri("stackexchange://stackoverflow/123456#7891011").unwrap(),
+            questions("stackoverflow", &[123456], Some(7891011))
+        );
+    }
+
+    #[test]
+    fn parse_multi_id_uris() {
+        assert_eq!(
+            parse_uri("stackexchange://stackoverflow/123456;7891011;121314").unwrap(),
+            questions("stackoverflow", &[123456, 7891011, 121314], None)
+        );
+        assert_eq!(
+            parse_uri("stackexchange://stackoverflow/123456,7891011").unwrap(),
+            questions("stackoverflow", &[123456, 7891011], None)
+        );
+        // Answers can't be told apart between several questions.
+        assert_eq!(
+            parse_uri("stackexchange://stackoverflow/123456;7891011#121314").unwrap(),
+            questions("stackoverflow", &[123456, 7891011], None)
+        );
+    }
+
+    #[test]
+    fn parse_short_and_alias_uris() {
+        assert_eq!(
+            parse_uri("stackexchange://so/123456").unwrap(),
+            questions("stackoverflow", &[123456], None)
+        );
+        assert_eq!(
+            parse_uri("stackexchange://au/123456").unwrap(),
+            questions("askubuntu", &[123456], None)
+        );
+        assert_eq!(
+            parse_uri("stackexchange://meta.so/12345").unwrap(),
+            questions("meta.stackoverflow", &[12345], None)
+        );
+        assert_eq!(
+            parse_uri("https://askubuntu.com/q/123456").unwrap(),
+            questions("askubuntu", &[123456], None)
+        );
+        assert_eq!(
+            parse_uri("https://stackoverflow.com/search?q=%5Brust%5D+lifetimes").unwrap(),
+            UriLink::Search {
+                site: "stackoverflow".to_owned(),
+                query: "[rust] lifetimes".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_invalid_uris() {
+        for uri in [
+            "",
+            "not a uri",
+            "ftp://stackoverflow.com/q/123456",
+            "stackexchange://stackoverflow/",
+            "stackexchange://stackoverflow/abc",
+            "stackexchange://stackoverflow/123;abc",
+            "stackexchange://stackoverflow/a/",
+            "https://stackoverflow.com/questions/",
+            "https://stackoverflow.com/search?q=",
+            "https://example.com/questions/123456",
+            "https://chat.stackexchange.com/rooms/123456",
+        ] {
+            assert!(
+                matches!(parse_uri(uri), Err(StackExchangeError::InvalidUri(_))),
+                "{uri} was parsed"
+            );
+        }
+    }
 }
@@ -0,0 +1,96 @@
+// Questions bookmarked locally, independent of any StackExchange account, saved as JSON in:
+// {user data dir}/stackbloatless/bookmarks.json
+
+use std::path::PathBuf;
+
+use relm4::gtk::glib;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::api::stackexchange::Id;
+use crate::storage;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub site: String,
+    pub question_id: Id,
+    pub title: String,
+    /// Bookmarks without a folder are listed first.
+    pub folder: Option<String>,
+    #[serde(with = "time::serde::timestamp")]
+    pub added: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bookmarks {
+    pub items: Vec<Bookmark>,
+}
+
+pub fn bookmarks_path() -> PathBuf {
+    glib::user_data_dir()
+        .join("stackbloatless")
+        .join("bookmarks.json")
+}
+
+impl Bookmarks {
+    /// Load the saved bookmarks, or none if there are none.
+    pub fn load() -> Self {
+        storage::load_json(&bookmarks_path(), "bookmarks").unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save_json(&bookmarks_path(), self, "bookmarks");
+    }
+
+    pub fn contains(&self, site: &str, question_id: Id) -> bool {
+        self.items
+            .iter()
+            .any(|bookmark| bookmark.site == site && bookmark.question_id == question_id)
+    }
+
+    /// Bookmark the question, or remove its bookmark. Returns whether it's bookmarked now.
+    pub fn toggle(&mut self, site: &str, question_id: Id, title: &str) -> bool {
+        if self.contains(site, question_id) {
+            self.remove(site, question_id);
+            return false;
+        }
+
+        self.items.push(Bookmark {
+            site: site.to_owned(),
+            question_id,
+            title: title.to_owned(),
+            folder: None,
+            added: OffsetDateTime::now_utc(),
+        });
+        true
+    }
+
+    pub fn remove(&mut self, site: &str, question_id: Id) {
+        self.items
+            .retain(|bookmark| !(bookmark.site == site && bookmark.question_id == question_id));
+    }
+
+    pub fn set_folder(&mut self, site: &str, question_id: Id, folder: Option<String>) {
+        if let Some(bookmark) = self
+            .items
+            .iter_mut()
+            .find(|bookmark| bookmark.site == site && bookmark.question_id == question_id)
+        {
+            bookmark.folder = folder;
+        }
+    }
+
+    /// Names of the folders in use, sorted.
+    pub fn folders(&self) -> Vec<String> {
+        let mut folders: Vec<String> = self
+            .items
+            .iter()
+            .filter_map(|bookmark| bookmark.folder.clone())
+            .collect();
+        folders.sort();
+        folders.dedup();
+
+        folders
+    }
+}
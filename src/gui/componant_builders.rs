@@ -1,11 +1,2009 @@
-use relm4::prelude::gtk::{self, prelude::*};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
+use adw::prelude::*;
+use relm4::prelude::gtk::{self, glib};
+
+use super::fuzzy;
+use super::main_window::AppInput;
 use super::markdown2gtk::md2gtk;
-use crate::api::stackexchange::{Answer, Comment, Question, User};
+use crate::api::dates::{format_absolute, format_relative};
+use crate::api::feeds::FeedEntry;
+use crate::api::stackexchange::{
+    decode_html_entities, parse_uri, Answer, AnswerSummary, ApiErrorKind, Comment, Id, InboxItem,
+    PostKind, Question, QuestionSort, QuestionSummary, ReputationChange, Revision, SearchExcerpt,
+    StackExchangeError, TagInfo, TagWiki, TopTag, UriLink, User, UserProfile, Vote,
+    COMMENT_MAX_LENGTH, COMMENT_MIN_LENGTH,
+};
+use crate::bookmarks::Bookmarks;
+use crate::history::HistoryEntry;
+use crate::settings::AnswerSort;
+use crate::settings::Subscription;
+
+/// Widgets showing the vote state of a post.
+///
+/// Weak references are used since messages are sent between threads, and the tab might be closed
+/// before the vote request finishes.
+#[derive(Debug, Clone)]
+pub struct VoteControls {
+    upvote_button: glib::SendWeakRef<gtk::ToggleButton>,
+    downvote_button: glib::SendWeakRef<gtk::ToggleButton>,
+    score_label: glib::SendWeakRef<gtk::Label>,
+}
+
+/// A snapshot of the vote state, used to roll back the optimistic update when voting fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoteState {
+    pub upvoted: bool,
+    pub downvoted: bool,
+    pub score: i32,
+}
+
+impl VoteControls {
+    /// Show the given state, does nothing if the widgets were destroyed.
+    pub fn set_state(&self, state: VoteState) {
+        if let (Some(upvote_button), Some(downvote_button), Some(score_label)) = (
+            self.upvote_button.upgrade(),
+            self.downvote_button.upgrade(),
+            self.score_label.upgrade(),
+        ) {
+            upvote_button.set_active(state.upvoted);
+            downvote_button.set_active(state.downvoted);
+            set_score_label(&score_label, state.score);
+        }
+    }
+}
+
+fn set_score_label(label: &gtk::Label, score: i32) {
+    label.set_label(&score.to_string());
+    label.set_css_classes(if score >= 0 { &["success"] } else { &["error"] });
+}
+
+/// Score of a post with upvote and downvote buttons when the user is logged in.
+fn st_vote_controls(
+    site: &str,
+    question_id: Id,
+    post_kind: PostKind,
+    id: Id,
+    state: VoteState,
+    authenticated: bool,
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
+    let layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let score_label = gtk::Label::builder().margin_top(5).margin_bottom(5).build();
+    set_score_label(&score_label, state.score);
+
+    if !authenticated {
+        layout.append(&score_label);
+        return layout;
+    }
+
+    let upvote_button = gtk::ToggleButton::builder()
+        .active(state.upvoted)
+        .icon_name("go-up-symbolic")
+        .tooltip_text("Upvote")
+        .css_classes(["flat"])
+        .build();
+    let downvote_button = gtk::ToggleButton::builder()
+        .active(state.downvoted)
+        .icon_name("go-down-symbolic")
+        .tooltip_text("Downvote")
+        .css_classes(["flat"])
+        .build();
+
+    layout.append(&upvote_button);
+    layout.append(&score_label);
+    layout.append(&downvote_button);
+
+    let controls = VoteControls {
+        upvote_button: upvote_button.downgrade().into(),
+        downvote_button: downvote_button.downgrade().into(),
+        score_label: score_label.downgrade().into(),
+    };
+
+    for (vote, button) in [(Vote::Up, &upvote_button), (Vote::Down, &downvote_button)] {
+        button.connect_clicked(glib::clone!(
+            @strong controls,
+            @weak upvote_button,
+            @weak downvote_button,
+            @weak score_label,
+            @strong sender,
+            @to-owned site => move |button| {
+                // The button is already toggled when the signal is emitted.
+                let previous = VoteState {
+                    upvoted: if vote == Vote::Up { !button.is_active() } else { upvote_button.is_active() },
+                    downvoted: if vote == Vote::Down { !button.is_active() } else { downvote_button.is_active() },
+                    score: score_label.label().parse().unwrap_or_default(),
+                };
+
+                // Remove the old vote from the score, then add the new one.
+                let mut next = VoteState {
+                    upvoted: vote == Vote::Up && button.is_active(),
+                    downvoted: vote == Vote::Down && button.is_active(),
+                    score: previous.score,
+                };
+                next.score -= previous.upvoted as i32 - previous.downvoted as i32;
+                next.score += next.upvoted as i32 - next.downvoted as i32;
+
+                // Update the UI optimistically, it's rolled back if the request fails.
+                controls.set_state(next);
+
+                sender.emit(AppInput::Vote {
+                    site: site.clone(),
+                    question_id,
+                    post_kind,
+                    id,
+                    controls: controls.clone(),
+                    previous,
+                    next,
+                });
+            }
+        ));
+    }
+
+    layout
+}
+
+/// The error followed by what can be done about it, if anything.
+pub fn error_description(error: &StackExchangeError) -> String {
+    match error {
+        StackExchangeError::Api { kind, .. } => format!("{error}\n\n{}", kind.hint()),
+        _ => error.to_string(),
+    }
+}
+
+// Characters of the excerpt in tab tooltips, the rest of the body is cut.
+const TOOLTIP_EXCERPT_LENGTH: usize = 200;
+
+/// Pango markup of the tooltip of a question tab: the full title, the site, stats and the start
+/// of its body, since titles are truncated in tabs.
+pub fn question_tooltip(question: &Question) -> String {
+    // Code blocks and markdown syntax only get in the way of a glance at the question.
+    let text: Vec<&str> = question
+        .body_markdown
+        .lines()
+        .filter(|line| !line.starts_with("    ") && !line.trim_start().starts_with("```"))
+        .flat_map(|line| line.split_whitespace())
+        .map(|word| word.trim_matches(|c| matches!(c, '*' | '_' | '`' | '#' | '>')))
+        .filter(|word| !word.is_empty())
+        .collect();
+    let text = text.join(" ");
+
+    let mut excerpt: String = text.chars().take(TOOLTIP_EXCERPT_LENGTH).collect();
+    if excerpt.len() < text.len() {
+        excerpt.push('…');
+    }
+
+    let answers = match question.answer_count {
+        1 => "1 answer".to_owned(),
+        count => format!("{count} answers"),
+    };
+
+    let mut tooltip = format!(
+        "<b>{}</b>\n<small>{} · {} score · {answers} · {} views</small>",
+        glib::markup_escape_text(&question.title),
+        glib::markup_escape_text(&question.site),
+        question.score,
+        question.view_count,
+    );
+    if let Some(cached) = question.cached {
+        tooltip.push_str(&format!(
+            "\n<small>Cached copy from {}</small>",
+            format_absolute(cached)
+        ));
+    }
+    if !excerpt.is_empty() {
+        tooltip.push_str("\n\n");
+        tooltip.push_str(&glib::markup_escape_text(&excerpt));
+    }
+
+    tooltip
+}
+
+/// A page for a question that wasn't returned by the API, it offers to search for its title when
+/// it's known.
+pub fn st_missing_question(
+    site: &str,
+    id: Id,
+    title: Option<&str>,
+    sender: &relm4::Sender<AppInput>,
+) -> adw::StatusPage {
+    let status_page = adw::StatusPage::builder()
+        .icon_name("edit-find-symbolic")
+        .title("Question Not Found or Deleted")
+        .description(format!(
+            "The question {id} on {site} doesn't exist, was deleted or isn't visible to you."
+        ))
+        .vexpand(true)
+        .hexpand(true)
+        .build();
+
+    if let Some(title) = title {
+        let search_button = gtk::Button::builder()
+            .label(format!("Search for “{title}”"))
+            .css_classes(["pill", "suggested-action"])
+            .halign(gtk::Align::Center)
+            .build();
+
+        let site = site.to_owned();
+        let query = title.to_owned();
+        search_button.connect_clicked(gtk::glib::clone!(@strong sender => move |_| {
+            sender.emit(AppInput::Search {
+                site: site.clone(),
+                query: query.clone(),
+            });
+        }));
+
+        status_page.set_child(Some(&search_button));
+    }
+
+    status_page
+}
+
+/// A page describing why a tab couldn't be loaded.
+pub fn st_error_page(error: &StackExchangeError) -> adw::StatusPage {
+    let (icon_name, title) = match error {
+        StackExchangeError::Network(_) => ("network-offline-symbolic", "Connection Failed"),
+        StackExchangeError::NotFound => ("edit-find-symbolic", "Not Found"),
+        StackExchangeError::InvalidUri(_) => ("dialog-error-symbolic", "Invalid Link"),
+        StackExchangeError::Authentication(_) => ("dialog-password-symbolic", "Log In Required"),
+        StackExchangeError::Offline => ("network-offline-symbolic", "Offline"),
+        StackExchangeError::Api { kind, .. } if kind.needs_login() => {
+            ("dialog-password-symbolic", "Log In Required")
+        }
+        StackExchangeError::Api {
+            kind: ApiErrorKind::ThrottleViolation,
+            ..
+        } => ("network-error-symbolic", "Too Many Requests"),
+        StackExchangeError::Json(_) | StackExchangeError::Api { .. } => {
+            ("dialog-error-symbolic", "Loading Failed")
+        }
+    };
+
+    adw::StatusPage::builder()
+        .icon_name(icon_name)
+        .title(title)
+        .description(error_description(error))
+        .vexpand(true)
+        .hexpand(true)
+        .build()
+}
+
+/// Shown when no tab is open, with the ways to open one.
+pub fn st_empty_page(sender: &relm4::Sender<AppInput>) -> adw::StatusPage {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(20)
+        .halign(gtk::Align::Center)
+        .build();
+
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text("Enter a search term, question id or link")
+        .width_request(300)
+        .build();
+    search_entry.connect_activate(glib::clone!(@strong sender => move |entry| {
+        let search_term = entry.text();
+        if !search_term.trim().is_empty() {
+            sender.emit(AppInput::SubmitSearchTerm(search_term.trim().to_owned()));
+            entry.set_text("");
+        }
+    }));
+    main_layout.append(&search_entry);
+
+    let buttons_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(10)
+        .halign(gtk::Align::Center)
+        .build();
+
+    let paste_button = gtk::Button::builder()
+        .label("Paste a Link")
+        .css_classes(["pill"])
+        .build();
+    paste_button.connect_clicked(glib::clone!(@strong sender => move |_| {
+        sender.emit(AppInput::PasteLink);
+    }));
+    buttons_box.append(&paste_button);
+
+    let hot_button = gtk::Button::builder()
+        .label("Browse Hot Questions")
+        .css_classes(["pill", "suggested-action"])
+        .build();
+    hot_button.connect_clicked(glib::clone!(@strong sender => move |_| {
+        sender.emit(AppInput::OpenHome);
+    }));
+    buttons_box.append(&hot_button);
+
+    main_layout.append(&buttons_box);
+
+    adw::StatusPage::builder()
+        .icon_name("io.github.zer0_x.stackbloatless")
+        .title("No Open Tabs")
+        .description("Search for a question, or open a link to one")
+        .child(&main_layout)
+        .vexpand(true)
+        .hexpand(true)
+        .build()
+}
+
+/// The error of a tab that failed to load, with a button to load `uri` again in the same tab.
+pub fn st_request_error_page(
+    error: &StackExchangeError,
+    uri: &str,
+    tab_page: &adw::TabPage,
+    sender: &relm4::Sender<AppInput>,
+) -> adw::StatusPage {
+    let status_page = st_error_page(error);
+
+    let retry_button = gtk::Button::builder()
+        .label("Retry")
+        .halign(gtk::Align::Center)
+        .css_classes(["pill", "suggested-action"])
+        .build();
+    let uri = uri.to_owned();
+    // The tab owns the button, so it's referenced weakly.
+    let tab_page: glib::SendWeakRef<adw::TabPage> = tab_page.downgrade().into();
+    retry_button.connect_clicked(glib::clone!(@strong sender => move |_| {
+        sender.emit(AppInput::RetryRequest {
+            uri: uri.clone(),
+            tab_page: tab_page.clone(),
+        });
+    }));
+    status_page.set_child(Some(&retry_button));
+
+    status_page
+}
+
+/// A hidden banner of a question tab, revealed when the question changed, with a button to load
+/// it again.
+pub fn st_activity_banner(
+    tab_page: &adw::TabPage,
+    sender: &relm4::Sender<AppInput>,
+) -> adw::Banner {
+    let banner = adw::Banner::builder()
+        .button_label("Reload")
+        .revealed(false)
+        .build();
+
+    // The tab owns the banner, so it's referenced weakly.
+    let tab_page: glib::SendWeakRef<adw::TabPage> = tab_page.downgrade().into();
+    banner.connect_button_clicked(glib::clone!(@strong sender => move |banner| {
+        banner.set_revealed(false);
+        sender.emit(AppInput::RefreshTab(tab_page.clone()));
+    }));
+
+    banner
+}
+
+/// The right side of the split view, showing `question_box` beside the tabs.
+pub fn st_split_pane(
+    question_box: &gtk::Box,
+    title: &str,
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
+    let title_label = gtk::Label::builder()
+        .label(title)
+        .ellipsize(gtk::pango::EllipsizeMode::End)
+        .hexpand(true)
+        .xalign(0.0)
+        .css_classes(["heading"])
+        .build();
+    let close_button = gtk::Button::builder()
+        .icon_name("window-close-symbolic")
+        .tooltip_text("Close Split View")
+        .css_classes(["flat"])
+        .build();
+    close_button.connect_clicked(glib::clone!(@strong sender => move |_| {
+        sender.emit(AppInput::CloseSplitView);
+    }));
+
+    let title_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(5)
+        .margin_top(5)
+        .margin_bottom(5)
+        .margin_start(10)
+        .margin_end(5)
+        .build();
+    title_layout.append(&title_label);
+    title_layout.append(&close_button);
+
+    let pane = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    pane.append(&title_layout);
+    pane.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+    pane.append(
+        &gtk::ScrolledWindow::builder()
+            .child(question_box)
+            .vexpand(true)
+            .hexpand(true)
+            .build(),
+    );
+
+    pane
+}
+
+/// A page shown in a tab while its question is loaded, `title` is shown when it's already known.
+pub fn st_loading_page(title: Option<&str>) -> adw::StatusPage {
+    let spinner = gtk::Spinner::builder()
+        .width_request(32)
+        .height_request(32)
+        .halign(gtk::Align::Center)
+        .build();
+    spinner.start();
+
+    let status_page = adw::StatusPage::builder()
+        .title("Loading Question…")
+        .child(&spinner)
+        .vexpand(true)
+        .hexpand(true)
+        .build();
+    if let Some(title) = title {
+        status_page.set_description(Some(title));
+    }
+
+    status_page
+}
+
+/// A titled section at the bottom of a question page, it shows a spinner until it's filled
+/// with `fill_questions_section`.
+pub fn st_questions_section(title: &str) -> gtk::Box {
+    let section = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(15)
+        .margin_bottom(15)
+        .margin_start(5)
+        .margin_end(15)
+        .build();
+
+    section.append(
+        &gtk::Label::builder()
+            .label(title)
+            .css_classes(["title-2"])
+            .halign(gtk::Align::Start)
+            .build(),
+    );
+
+    let spinner = gtk::Spinner::builder().halign(gtk::Align::Start).build();
+    spinner.start();
+    section.append(&spinner);
+
+    section
+}
+
+pub fn fill_questions_section(
+    section: &gtk::Box,
+    questions: Result<Vec<QuestionSummary>, StackExchangeError>,
+    sender: &relm4::Sender<AppInput>,
+) {
+    // Remove the spinner, it's always the last child.
+    if let Some(spinner) = section.last_child() {
+        section.remove(&spinner);
+    }
+
+    match questions {
+        Ok(questions) if questions.is_empty() => {
+            section.append(
+                &gtk::Label::builder()
+                    .label("Nothing found")
+                    .css_classes(["dim-label"])
+                    .halign(gtk::Align::Start)
+                    .build(),
+            );
+        }
+        Ok(questions) => section.append(&st_question_list(&questions, sender)),
+        Err(error) => {
+            section.append(
+                &gtk::Label::builder()
+                    .label(error.to_string())
+                    .css_classes(["error"])
+                    .wrap(true)
+                    .halign(gtk::Align::Start)
+                    .build(),
+            );
+        }
+    }
+}
+
+/// Put the spinner back in a section to load it again.
+fn reset_questions_section(section: &gtk::Box) {
+    // Keep the title, it's always the first child.
+    while let Some(child) = section.last_child() {
+        if section.first_child().as_ref() == Some(&child) {
+            break;
+        }
+        section.remove(&child);
+    }
+
+    let spinner = gtk::Spinner::builder().halign(gtk::Align::Start).build();
+    spinner.start();
+    section.append(&spinner);
+}
+
+/// Hot questions of a site, the site can be changed from the page itself.
+pub fn st_home_page(site: &str, sender: &relm4::Sender<AppInput>) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let site_entry = gtk::Entry::builder()
+        .text(site)
+        .placeholder_text("Site, e.g. superuser")
+        .primary_icon_name("network-server-symbolic")
+        .halign(gtk::Align::Start)
+        .build();
+    main_layout.append(&site_entry);
+
+    let section = st_questions_section("Hot Questions");
+    main_layout.append(&section);
+
+    sender.emit(AppInput::LoadTaggedQuestions {
+        site: site.to_owned(),
+        tags: Vec::new(),
+        sort: QuestionSort::Hot,
+        section: section.downgrade().into(),
+    });
+
+    site_entry.connect_activate(glib::clone!(@strong sender, @weak section => move |entry| {
+        let site = entry.text().trim().to_owned();
+        if site.is_empty() {
+            return;
+        }
+
+        reset_questions_section(&section);
+        sender.emit(AppInput::LoadTaggedQuestions {
+            site,
+            tags: Vec::new(),
+            sort: QuestionSort::Hot,
+            section: section.downgrade().into(),
+        });
+    }));
+
+    main_layout
+}
+
+/// Questions having all of `tags`, their order can be changed from the page.
+pub fn st_tagged_questions(
+    site: &str,
+    tags: &[String],
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
+    let layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .build();
+
+    let section = st_questions_section("Questions");
+    layout.append(&section);
+
+    let sort_buttons = gtk::Box::builder()
+        .css_classes(["linked"])
+        .halign(gtk::Align::Start)
+        .margin_start(5)
+        .build();
+    layout.prepend(&sort_buttons);
+
+    let mut first_button: Option<gtk::ToggleButton> = None;
+    for (label, sort) in [
+        ("Votes", QuestionSort::Votes),
+        ("Newest", QuestionSort::Newest),
+        ("Active", QuestionSort::Active),
+    ] {
+        let button = gtk::ToggleButton::builder()
+            .label(label)
+            .active(first_button.is_none())
+            .build();
+        button.set_group(first_button.as_ref());
+
+        button.connect_toggled(glib::clone!(
+            @strong sender, @weak section, @to-owned site, @to-owned tags => move |button| {
+                // The previously active button is toggled too.
+                if !button.is_active() {
+                    return;
+                }
+
+                reset_questions_section(&section);
+                sender.emit(AppInput::LoadTaggedQuestions {
+                    site: site.clone(),
+                    tags: tags.clone(),
+                    sort,
+                    section: section.downgrade().into(),
+                });
+            }
+        ));
+
+        sort_buttons.append(&button);
+        first_button.get_or_insert(button);
+    }
+
+    sender.emit(AppInput::LoadTaggedQuestions {
+        site: site.to_owned(),
+        tags: tags.to_owned(),
+        sort: QuestionSort::Votes,
+        section: section.downgrade().into(),
+    });
+
+    layout
+}
+
+/// Questions having several tags at once, like [rust] [gtk].
+pub fn st_tag_browser(site: &str, tags: &[String], sender: &relm4::Sender<AppInput>) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    main_layout.append(
+        &gtk::Label::builder()
+            .label(
+                tags.iter()
+                    .map(|tag| format!("[{tag}]"))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+            .css_classes(["title-1"])
+            .halign(gtk::Align::Start)
+            .wrap(true)
+            .build(),
+    );
+
+    main_layout.append(&st_tags(tags, site, sender));
+    main_layout.append(&st_tagged_questions(site, tags, sender));
+
+    main_layout
+}
+
+/// Questions as rows, activating one opens it in a new tab.
+pub fn st_question_list(
+    questions: &[QuestionSummary],
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::ListBox {
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+
+    for question in questions {
+        let title = match &question.closed_reason {
+            Some(closed_reason) => format!("{} [{closed_reason}]", question.title),
+            None => question.title.clone(),
+        };
+
+        let row = adw::ActionRow::builder()
+            .title(title)
+            .use_markup(false)
+            .subtitle(format!(
+                "{} votes · {} answers · {}",
+                question.score,
+                question.answer_count,
+                question.tags.join(", ")
+            ))
+            .activatable(true)
+            .build();
+
+        if let Some(bounty_amount) = question.bounty_amount {
+            row.add_suffix(
+                &gtk::Label::builder()
+                    .label(format!("+{bounty_amount}"))
+                    .tooltip_text("Open bounty")
+                    .css_classes(["accent", "caption-heading"])
+                    .build(),
+            );
+        }
+
+        if question.is_answered {
+            row.add_suffix(
+                &gtk::Image::builder()
+                    .icon_name("emblem-ok-symbolic")
+                    .tooltip_text("Answered")
+                    .css_classes(["success"])
+                    .build(),
+            );
+        }
+
+        let uri = format!("stackexchange://{}/{}", question.site, question.question_id);
+        row.connect_activated(glib::clone!(@strong sender => move |_| {
+            sender.emit(AppInput::RequestPagesByUri(uri.clone()));
+        }));
+
+        list.append(&row);
+    }
+
+    list
+}
+
+/// Tags as small buttons, clicking one opens its tag page.
+fn st_tags(tags: &[String], site: &str, sender: &relm4::Sender<AppInput>) -> gtk::FlowBox {
+    let flow_box = gtk::FlowBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .column_spacing(5)
+        .row_spacing(5)
+        .max_children_per_line(20)
+        .margin_start(10)
+        .margin_end(10)
+        .margin_bottom(10)
+        .build();
+
+    for tag in tags {
+        let button = gtk::Button::builder()
+            .label(tag)
+            .css_classes(["pill", "tag"])
+            .build();
+
+        button.connect_clicked(
+            glib::clone!(@strong sender, @to-owned site, @to-owned tag => move |_| {
+                sender.emit(AppInput::OpenTag {
+                    site: site.clone(),
+                    tag: tag.clone(),
+                });
+            }),
+        );
+
+        flow_box.insert(&button, -1);
+    }
+
+    flow_box
+}
+
+/// Page of a tag with its wiki excerpt and its top questions.
+pub fn st_tag_page(
+    site: &str,
+    tag: &str,
+    info: Option<&TagInfo>,
+    wiki: Option<&TagWiki>,
+    subscribed: bool,
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    main_layout.append(
+        &gtk::Label::builder()
+            .selectable(true)
+            .can_focus(false)
+            .label(tag)
+            .css_classes(["title-1"])
+            .halign(gtk::Align::Start)
+            .build(),
+    );
+
+    // Follow new questions through the tag's feed
+    let subscribe_button = gtk::ToggleButton::builder()
+        .label(if subscribed {
+            "Subscribed"
+        } else {
+            "Subscribe"
+        })
+        .active(subscribed)
+        .halign(gtk::Align::Start)
+        .build();
+
+    subscribe_button.connect_toggled(glib::clone!(
+        @strong sender, @to-owned site, @to-owned tag => move |button| {
+            button.set_label(if button.is_active() { "Subscribed" } else { "Subscribe" });
+            sender.emit(AppInput::SetSubscribed {
+                site: site.clone(),
+                tag: tag.clone(),
+                subscribed: button.is_active(),
+            });
+        }
+    ));
+
+    main_layout.append(&subscribe_button);
+
+    if let Some(info) = info {
+        main_layout.append(
+            &gtk::Label::builder()
+                .label(format!("{} questions", info.count))
+                .css_classes(["dim-label"])
+                .halign(gtk::Align::Start)
+                .build(),
+        );
+    }
+
+    main_layout.append(
+        &gtk::Label::builder()
+            .selectable(true)
+            .can_focus(false)
+            .label(
+                wiki.and_then(|wiki| wiki.excerpt.as_deref())
+                    .unwrap_or("This tag doesn't have a wiki yet."),
+            )
+            .wrap(true)
+            .xalign(0.0)
+            .css_classes(["body_buffer"])
+            .build(),
+    );
+
+    // Browse questions having this tag and others
+    let combine_entry = gtk::Entry::builder()
+        .placeholder_text("Combine with tags, e.g. gtk windows")
+        .primary_icon_name("list-add-symbolic")
+        .halign(gtk::Align::Start)
+        .width_chars(30)
+        .build();
+
+    combine_entry.connect_activate(
+        glib::clone!(@strong sender, @to-owned site, @to-owned tag => move |entry| {
+            let mut tags = vec![tag.clone()];
+            tags.extend(entry.text().split_whitespace().map(|tag| tag.to_lowercase()));
+            if tags.len() == 1 {
+                return;
+            }
+
+            entry.set_text("");
+            sender.emit(AppInput::BrowseTags {
+                site: site.clone(),
+                tags,
+            });
+        }),
+    );
+
+    main_layout.append(&combine_entry);
+    main_layout.append(&st_tagged_questions(site, &[tag.to_owned()], sender));
+
+    main_layout
+}
+
+/// Newest questions of the subscribed tags.
+pub fn st_subscriptions(
+    entries: &[(Subscription, FeedEntry)],
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    main_layout.append(
+        &gtk::Label::builder()
+            .label("Subscriptions")
+            .css_classes(["title-1"])
+            .halign(gtk::Align::Start)
+            .build(),
+    );
+
+    if entries.is_empty() {
+        main_layout.append(
+            &gtk::Label::builder()
+                .label("Nothing yet, subscribe to tags from their pages.")
+                .css_classes(["dim-label"])
+                .halign(gtk::Align::Start)
+                .build(),
+        );
+        return main_layout;
+    }
+
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    main_layout.append(&list);
+
+    for (subscription, entry) in entries {
+        let published = glib::DateTime::from_iso8601(&entry.published, None)
+            .and_then(|date_time| date_time.to_local())
+            .and_then(|date_time| date_time.format("%Y-%m-%d %H:%M"))
+            .map(|formatted| formatted.to_string())
+            .unwrap_or_default();
+
+        let row = adw::ActionRow::builder()
+            .title(&entry.title)
+            .use_markup(false)
+            .subtitle(format!(
+                "[{}] on {} · {published}",
+                subscription.tag, subscription.site
+            ))
+            .activatable(true)
+            .build();
+
+        let uri = format!(
+            "stackexchange://{}/{}",
+            subscription.site, entry.question_id
+        );
+        row.connect_activated(glib::clone!(@strong sender => move |_| {
+            sender.emit(AppInput::RequestPagesByUri(uri.clone()));
+        }));
+
+        list.append(&row);
+    }
+
+    main_layout
+}
+
+/// Local bookmarks with their folders, the dropdown above them shows a single folder.
+pub fn st_bookmarks(bookmarks: &Bookmarks, sender: &relm4::Sender<AppInput>) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let header = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(10)
+        .build();
+    main_layout.append(&header);
+
+    header.append(
+        &gtk::Label::builder()
+            .label("Bookmarks")
+            .css_classes(["title-1"])
+            .halign(gtk::Align::Start)
+            .hexpand(true)
+            .build(),
+    );
+
+    if bookmarks.items.is_empty() {
+        main_layout.append(
+            &gtk::Label::builder()
+                .label("Nothing yet, bookmark questions with the star in the header.")
+                .css_classes(["dim-label"])
+                .halign(gtk::Align::Start)
+                .build(),
+        );
+        return main_layout;
+    }
+
+    // The first two choices show every bookmark and the ones without a folder.
+    let folders = bookmarks.folders();
+    let mut choices = vec!["All Folders", "Unsorted"];
+    choices.extend(folders.iter().map(|folder| folder.as_str()));
+    let folder_dropdown = gtk::DropDown::from_strings(&choices);
+    header.append(&folder_dropdown);
+
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    main_layout.append(&list);
+
+    // Unsorted first, then by folder, newest first in every folder.
+    let mut items: Vec<_> = bookmarks.items.iter().collect();
+    items.sort_by(|a, b| a.folder.cmp(&b.folder).then(b.added.cmp(&a.added)));
+
+    // Folder of every row, updated when bookmarks are moved.
+    let row_folders: Rc<RefCell<HashMap<gtk::ListBoxRow, Option<String>>>> = Rc::default();
+
+    for bookmark in items {
+        let subtitle = |folder: Option<&str>| {
+            format!(
+                "{} · {} · added {}",
+                bookmark.site,
+                folder.unwrap_or("Unsorted"),
+                format_relative(bookmark.added)
+            )
+        };
+
+        let row = adw::ActionRow::builder()
+            .title(&bookmark.title)
+            .use_markup(false)
+            .subtitle(subtitle(bookmark.folder.as_deref()))
+            .activatable(true)
+            .build();
+        row_folders
+            .borrow_mut()
+            .insert(row.clone().upcast(), bookmark.folder.clone());
+
+        let uri = format!("stackexchange://{}/{}", bookmark.site, bookmark.question_id);
+        row.connect_activated(glib::clone!(@strong sender => move |_| {
+            sender.emit(AppInput::RequestPagesByUri(uri.clone()));
+        }));
+
+        // Move to another folder, an empty name removes it from its folder
+        let folder_entry = gtk::Entry::builder()
+            .placeholder_text("Folder")
+            .text(bookmark.folder.as_deref().unwrap_or_default())
+            .build();
+        let folder_popover = gtk::Popover::builder().child(&folder_entry).build();
+        let folder_button = gtk::MenuButton::builder()
+            .icon_name("folder-symbolic")
+            .tooltip_text("Move to Folder")
+            .popover(&folder_popover)
+            .css_classes(["flat"])
+            .valign(gtk::Align::Center)
+            .build();
+        row.add_suffix(&folder_button);
+
+        let (site, question_id) = (bookmark.site.clone(), bookmark.question_id);
+        let added = format_relative(bookmark.added);
+        folder_entry.connect_activate(glib::clone!(
+            @strong sender,
+            @strong site,
+            @strong row_folders,
+            @weak row,
+            @weak list,
+            @weak folder_popover => move |entry| {
+                let folder = entry.text().trim().to_owned();
+                let folder = (!folder.is_empty()).then_some(folder);
+
+                row.set_subtitle(&format!(
+                    "{site} · {} · added {added}",
+                    folder.as_deref().unwrap_or("Unsorted")
+                ));
+                row_folders
+                    .borrow_mut()
+                    .insert(row.clone().upcast(), folder.clone());
+                list.invalidate_filter();
+                folder_popover.popdown();
+
+                sender.emit(AppInput::SetBookmarkFolder {
+                    site: site.clone(),
+                    question_id,
+                    folder,
+                });
+            }
+        ));
+
+        let remove_button = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Remove Bookmark")
+            .css_classes(["flat"])
+            .valign(gtk::Align::Center)
+            .build();
+        row.add_suffix(&remove_button);
+
+        remove_button.connect_clicked(glib::clone!(
+            @strong sender, @strong row_folders, @weak row, @weak list => move |_| {
+                row_folders.borrow_mut().remove(row.upcast_ref::<gtk::ListBoxRow>());
+                list.remove(&row);
+
+                sender.emit(AppInput::RemoveBookmark {
+                    site: site.clone(),
+                    question_id,
+                });
+            }
+        ));
+
+        list.append(&row);
+    }
+
+    list.set_filter_func(glib::clone!(
+        @weak folder_dropdown, @strong row_folders => @default-return true, move |row| {
+            let row_folders = row_folders.borrow();
+            let Some(folder) = row_folders.get(row) else {
+                return true;
+            };
+
+            match folder_dropdown.selected() {
+                0 => true,
+                1 => folder.is_none(),
+                selected => folder.as_ref() == folders.get(selected as usize - 2),
+            }
+        }
+    ));
+
+    folder_dropdown.connect_selected_notify(glib::clone!(@weak list => move |_| {
+        list.invalidate_filter();
+    }));
+
+    main_layout
+}
+
+/// Opened questions, newest first, filtered by the search entry above them.
+pub fn st_history(entries: &[HistoryEntry], sender: &relm4::Sender<AppInput>) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let header = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(10)
+        .build();
+    main_layout.append(&header);
+
+    header.append(
+        &gtk::Label::builder()
+            .label("History")
+            .css_classes(["title-1"])
+            .halign(gtk::Align::Start)
+            .hexpand(true)
+            .build(),
+    );
+
+    let empty_label = gtk::Label::builder()
+        .label("No questions were opened yet.")
+        .css_classes(["dim-label"])
+        .halign(gtk::Align::Start)
+        .visible(entries.is_empty())
+        .build();
+
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text("Search the history")
+        .visible(!entries.is_empty())
+        .build();
+
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .visible(!entries.is_empty())
+        .build();
+
+    let clear_button = gtk::Button::builder()
+        .label("Clear History")
+        .css_classes(["destructive-action"])
+        .sensitive(!entries.is_empty())
+        .build();
+
+    clear_button.connect_clicked(glib::clone!(
+        @strong sender, @weak list, @weak search_entry, @weak empty_label => move |button| {
+            sender.emit(AppInput::ClearHistory);
+
+            list.set_visible(false);
+            search_entry.set_visible(false);
+            empty_label.set_visible(true);
+            button.set_sensitive(false);
+        }
+    ));
+    header.append(&clear_button);
+
+    main_layout.append(&empty_label);
+    main_layout.append(&search_entry);
+    main_layout.append(&list);
+
+    for entry in entries {
+        let row = adw::ActionRow::builder()
+            .title(&entry.title)
+            .use_markup(false)
+            .subtitle(format!(
+                "{} · {}",
+                entry.site,
+                format_relative(entry.visited)
+            ))
+            .activatable(true)
+            .build();
+
+        let uri = format!("stackexchange://{}/{}", entry.site, entry.question_id);
+        row.connect_activated(glib::clone!(@strong sender => move |_| {
+            sender.emit(AppInput::RequestPagesByUri(uri.clone()));
+        }));
+
+        list.append(&row);
+    }
+
+    // Searched case insensitively in the title and the site, rows are in the order of entries.
+    let keys: Vec<String> = entries
+        .iter()
+        .map(|entry| format!("{} {}", entry.title, entry.site).to_lowercase())
+        .collect();
+
+    list.set_filter_func(
+        glib::clone!(@weak search_entry => @default-return true, move |row| {
+            let Some(key) = keys.get(row.index() as usize) else {
+                return true;
+            };
+
+            search_entry
+                .text()
+                .to_lowercase()
+                .split_whitespace()
+                .all(|word| key.contains(word))
+        }),
+    );
+
+    search_entry.connect_search_changed(glib::clone!(@weak list => move |_| {
+        list.invalidate_filter();
+    }));
+
+    main_layout
+}
+
+/// Pango markup of a search excerpt, with the matches in bold.
+fn excerpt_markup(excerpt: &str) -> String {
+    let mut markup = String::with_capacity(excerpt.len());
+
+    for (index, part) in excerpt.split("<span class=\"highlight\">").enumerate() {
+        // Every part but the first starts with a match.
+        let (highlight, rest) = match index {
+            0 => ("", part),
+            _ => part.split_once("</span>").unwrap_or((part, "")),
+        };
+
+        if !highlight.is_empty() {
+            markup.push_str("<b>");
+            markup.push_str(&glib::markup_escape_text(&decode_html_entities(highlight)));
+            markup.push_str("</b>");
+        }
+        markup.push_str(&glib::markup_escape_text(&decode_html_entities(rest)));
+    }
+
+    markup
+}
+
+/// Past search terms, picking one searches it again.
+pub fn st_search_history(terms: &[String], sender: &relm4::Sender<AppInput>) -> gtk::ListBox {
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::Single)
+        .css_classes(["boxed-list"])
+        .build();
+
+    for term in terms {
+        let row = adw::ActionRow::builder()
+            .title(term)
+            .use_markup(false)
+            .activatable(true)
+            .build();
+        row.add_prefix(&gtk::Image::from_icon_name("document-open-recent-symbolic"));
+
+        // Keeps the focus in the search entry.
+        let remove_button = gtk::Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text("Remove From History")
+            .css_classes(["flat"])
+            .valign(gtk::Align::Center)
+            .focus_on_click(false)
+            .build();
+        remove_button.connect_clicked(glib::clone!(@strong sender, @strong term => move |_| {
+            sender.emit(AppInput::RemoveSearchTerm(term.clone()));
+        }));
+        row.add_suffix(&remove_button);
+
+        row.connect_activated(glib::clone!(@strong sender, @strong term => move |_| {
+            sender.emit(AppInput::SubmitSearchTerm(term.clone()));
+        }));
+
+        list.append(&row);
+    }
+
+    list
+}
+
+/// An entry of `st_palette` or `st_sidebar_list`, `message` is sent when it's picked.
+pub struct PaletteEntry {
+    pub title: String,
+    pub subtitle: String,
+    pub message: AppInput,
+}
+
+/// The tabs gone through with Ctrl+Tab, most recently used first, with the `selected` one
+/// highlighted.
+pub fn st_tab_cycle_list(titles: &[String], selected: usize) -> gtk::ListBox {
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::Single)
+        .css_classes(["navigation-sidebar"])
+        .width_request(300)
+        .build();
+
+    for title in titles {
+        list.append(
+            &gtk::Label::builder()
+                .label(title)
+                .xalign(0.0)
+                .ellipsize(gtk::pango::EllipsizeMode::End)
+                .build(),
+        );
+    }
+    list.select_row(list.row_at_index(selected as i32).as_ref());
+
+    list
+}
+
+/// A dialog to pick one of `entries` by typing parts of its title or subtitle, best matches first.
+/// Enter picks the selected one, the arrow keys select another.
+pub fn st_palette(
+    parent: &gtk::Window,
+    placeholder: &str,
+    entries: Vec<PaletteEntry>,
+    sender: &relm4::Sender<AppInput>,
+) -> adw::Window {
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text(placeholder)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::Single)
+        .css_classes(["navigation-sidebar"])
+        .build();
+
+    let main_layout = gtk::Box::new(gtk::Orientation::Vertical, 0);
+    main_layout.append(&search_entry);
+    main_layout.append(
+        &gtk::ScrolledWindow::builder()
+            .child(&list)
+            .vexpand(true)
+            .build(),
+    );
+
+    let window = adw::Window::builder()
+        .transient_for(parent)
+        .modal(true)
+        .destroy_with_parent(true)
+        .default_width(500)
+        .default_height(400)
+        .content(&main_layout)
+        .build();
+    search_entry.set_key_capture_widget(Some(&window));
+
+    // Indices of the entries shown by the rows, in order.
+    let shown: Rc<RefCell<Vec<usize>>> = Rc::new(RefCell::new(Vec::new()));
+    let entries = Rc::new(entries);
+
+    let filter = glib::clone!(@strong entries, @strong shown, @weak list => move |pattern: &str| {
+        while let Some(row) = list.first_child() {
+            list.remove(&row);
+        }
+
+        let mut matches: Vec<(u32, usize)> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                let text = format!("{} {}", entry.title, entry.subtitle);
+                Some((fuzzy::score(pattern, &text)?, index))
+            })
+            .collect();
+        // Stable, so equal matches keep their order.
+        matches.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+
+        for &(_, index) in &matches {
+            let row = adw::ActionRow::builder()
+                .title(&entries[index].title)
+                .subtitle(&entries[index].subtitle)
+                .use_markup(false)
+                .activatable(true)
+                .build();
+            list.append(&row);
+        }
+        list.select_row(list.row_at_index(0).as_ref());
+
+        *shown.borrow_mut() = matches.into_iter().map(|(_, index)| index).collect();
+    });
+    filter("");
+
+    let pick = glib::clone!(
+        @strong entries,
+        @strong shown,
+        @strong sender,
+        @weak window => move |row: &gtk::ListBoxRow| {
+        let Some(&index) = shown.borrow().get(row.index() as usize) else {
+            return;
+        };
+        sender.emit(entries[index].message.clone());
+        window.close();
+    });
+
+    search_entry.connect_search_changed(glib::clone!(@strong filter => move |entry| {
+        filter(&entry.text());
+    }));
+    search_entry.connect_activate(glib::clone!(@strong pick, @weak list => move |_| {
+        if let Some(row) = list.selected_row() {
+            pick(&row);
+        }
+    }));
+    search_entry.connect_stop_search(glib::clone!(@weak window => move |_| {
+        window.close();
+    }));
+    list.connect_row_activated(move |_, row| pick(row));
+
+    // The focus stays in the search entry while selecting.
+    let key_controller = gtk::EventControllerKey::new();
+    key_controller.connect_key_pressed(glib::clone!(
+        @weak list,
+        @weak search_entry => @default-return gtk::Inhibit(false), move |_, key, _, _| {
+        let step = match key {
+            gtk::gdk::Key::Down => 1,
+            gtk::gdk::Key::Up => -1,
+            _ => return gtk::Inhibit(false),
+        };
+
+        let index = list.selected_row().map_or(0, |row| row.index() + step);
+        if let Some(row) = list.row_at_index(index) {
+            list.select_row(Some(&row));
+            // Scrolls to the row.
+            row.grab_focus();
+            search_entry.grab_focus();
+            search_entry.set_position(-1);
+        }
+        gtk::Inhibit(true)
+    }));
+    search_entry.add_controller(key_controller);
+
+    window
+}
+
+/// A list of the sidebar, `placeholder` is shown when there are no `entries`.
+pub fn st_sidebar_list(
+    entries: Vec<PaletteEntry>,
+    placeholder: &str,
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::ListBox {
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::Single)
+        .css_classes(["navigation-sidebar"])
+        .build();
+    list.set_placeholder(Some(
+        &gtk::Label::builder()
+            .label(placeholder)
+            .wrap(true)
+            .margin_top(20)
+            .margin_start(10)
+            .margin_end(10)
+            .css_classes(["dim-label"])
+            .build(),
+    ));
+
+    for entry in entries {
+        let row = adw::ActionRow::builder()
+            .title(&entry.title)
+            .subtitle(&entry.subtitle)
+            .use_markup(false)
+            .activatable(true)
+            .build();
+        row.connect_activated(glib::clone!(@strong sender => move |_| {
+            sender.emit(entry.message.clone());
+        }));
+        list.append(&row);
+    }
+
+    list
+}
+
+/// Results of a search, picking one opens its question.
+pub fn st_search_results(
+    query: &str,
+    results: &[SearchExcerpt],
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    main_layout.append(
+        &gtk::Label::builder()
+            .label(format!("Search: {query}"))
+            .css_classes(["title-1"])
+            .halign(gtk::Align::Start)
+            .wrap(true)
+            .build(),
+    );
+
+    if results.is_empty() {
+        main_layout.append(
+            &gtk::Label::builder()
+                .label("No results.")
+                .css_classes(["dim-label"])
+                .halign(gtk::Align::Start)
+                .build(),
+        );
+        return main_layout;
+    }
+
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    main_layout.append(&list);
+
+    for result in results {
+        let title = decode_html_entities(&result.title);
+        let title = match result.answer_id {
+            Some(_) => format!("Answer: {title}"),
+            None => title,
+        };
+
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&title))
+            .subtitle(excerpt_markup(&result.excerpt))
+            .subtitle_lines(3)
+            .activatable(true)
+            .build();
+
+        let details = match result.answer_id {
+            Some(_) => format!("{} votes", result.score),
+            None => format!(
+                "{} votes · {} answers\n{}",
+                result.score,
+                result.answer_count,
+                result.tags.join(", ")
+            ),
+        };
+        row.add_suffix(
+            &gtk::Label::builder()
+                .label(details)
+                .css_classes(["dim-label", "caption"])
+                .justify(gtk::Justification::Right)
+                .build(),
+        );
+
+        if result.is_answered {
+            row.add_suffix(
+                &gtk::Image::builder()
+                    .icon_name("emblem-ok-symbolic")
+                    .tooltip_text("Answered")
+                    .css_classes(["success"])
+                    .build(),
+            );
+        }
+
+        // Answers open on their question, scrolled to them.
+        let uri = match result.answer_id {
+            Some(answer_id) => format!("stackexchange://{}/a/{answer_id}", result.site),
+            None => format!("stackexchange://{}/{}", result.site, result.question_id),
+        };
+        row.connect_activated(glib::clone!(@strong sender => move |_| {
+            sender.emit(AppInput::RequestPagesByUri(uri.clone()));
+        }));
+
+        list.append(&row);
+    }
+
+    main_layout
+}
+
+/// Reputation of the logged in user with its recent changes.
+pub fn st_reputation(user: &UserProfile, changes: &[ReputationChange]) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(5)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    main_layout.append(
+        &gtk::Label::builder()
+            .label(decode_html_entities(&user.display_name))
+            .css_classes(["title-4"])
+            .halign(gtk::Align::Start)
+            .build(),
+    );
+    main_layout.append(
+        &gtk::Label::builder()
+            .label(format!("{} reputation on {}", user.reputation, user.site))
+            .css_classes(["dim-label"])
+            .halign(gtk::Align::Start)
+            .build(),
+    );
+
+    if changes.is_empty() {
+        return main_layout;
+    }
+
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .margin_top(5)
+        .build();
+
+    for change in changes {
+        // For example: post_upvoted -> Post upvoted
+        let mut reason = change.reputation_history_type.replace('_', " ");
+        if let Some(first) = reason.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+
+        let row = adw::ActionRow::builder()
+            .title(reason)
+            .subtitle(format_relative(change.creation_date))
+            .build();
+
+        let amount = gtk::Label::builder()
+            .label(format!("{:+}", change.reputation_change))
+            .css_classes(["numeric"])
+            .build();
+        amount.add_css_class(if change.reputation_change >= 0 {
+            "success"
+        } else {
+            "error"
+        });
+        row.add_suffix(&amount);
+
+        list.append(&row);
+    }
+
+    main_layout.append(
+        &gtk::ScrolledWindow::builder()
+            .child(&list)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .propagate_natural_height(true)
+            .max_content_height(400)
+            .min_content_width(300)
+            .build(),
+    );
+
+    main_layout
+}
+
+/// Unread inbox items, activating one opens its question.
+pub fn st_inbox(items: &[InboxItem], sender: &relm4::Sender<AppInput>) -> gtk::Widget {
+    if items.is_empty() {
+        return gtk::Label::builder()
+            .label("No unread messages")
+            .css_classes(["dim-label"])
+            .margin_top(10)
+            .margin_bottom(10)
+            .margin_start(10)
+            .margin_end(10)
+            .build()
+            .upcast();
+    }
+
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .margin_top(5)
+        .margin_bottom(5)
+        .margin_start(5)
+        .margin_end(5)
+        .build();
+
+    for item in items {
+        let site = item
+            .site
+            .as_ref()
+            .map(|site| format!(" on {}", site.api_site_parameter))
+            .unwrap_or_default();
+
+        let row = adw::ActionRow::builder()
+            .title(decode_html_entities(&item.title))
+            .use_markup(false)
+            .subtitle(format!(
+                "{}{site} · {}",
+                item.item_type.label(),
+                format_relative(item.creation_date)
+            ))
+            .build();
+
+        if let Some(uri) = item.uri() {
+            row.set_activatable(true);
+            row.connect_activated(glib::clone!(@strong sender => move |row| {
+                // Close the popover the inbox is shown in.
+                if let Some(popover) = row
+                    .ancestor(gtk::Popover::static_type())
+                    .and_downcast::<gtk::Popover>()
+                {
+                    popover.popdown();
+                }
+                sender.emit(AppInput::RequestPagesByUri(uri.clone()));
+            }));
+        }
+
+        list.append(&row);
+    }
+
+    gtk::ScrolledWindow::builder()
+        .child(&list)
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .propagate_natural_height(true)
+        .max_content_height(400)
+        .min_content_width(350)
+        .build()
+        .upcast()
+}
+
+/// Timeline of the edits of a question, newest first.
+pub fn st_revisions(title: &str, revisions: &[Revision]) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    main_layout.append(
+        &gtk::Label::builder()
+            .label(format!("History of “{title}”"))
+            .css_classes(["title-1"])
+            .wrap(true)
+            .halign(gtk::Align::Start)
+            .build(),
+    );
+
+    let timeline = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    main_layout.append(&timeline);
+
+    for revision in revisions {
+        let title = match (revision.is_rollback, revision.revision_number) {
+            (true, _) => "Rollback".to_owned(),
+            (false, Some(revision_number)) => format!("Revision {revision_number}"),
+            (false, None) => "Tags edit".to_owned(),
+        };
+
+        let author = revision
+            .user
+            .as_ref()
+            .map(|user| user.display_name.as_str())
+            .unwrap_or("Community");
+
+        let row = adw::ActionRow::builder()
+            .title(title)
+            .use_markup(false)
+            .subtitle(format!(
+                "by {author} on {}",
+                format_absolute(revision.creation_date)
+            ))
+            .build();
+
+        if let Some(comment) = revision.comment.as_deref().filter(|c| !c.is_empty()) {
+            row.add_suffix(
+                &gtk::Label::builder()
+                    .label(comment)
+                    .wrap(true)
+                    .max_width_chars(60)
+                    .xalign(1.0)
+                    .css_classes(["dim-label"])
+                    .build(),
+            );
+        }
+
+        timeline.append(&row);
+    }
+
+    main_layout
+}
+
+/// Name of the author of a post, clicking it opens their profile.
+fn st_author(prefix: &str, owner: &User, site: &str, sender: &relm4::Sender<AppInput>) -> gtk::Box {
+    let layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(5)
+        .margin_start(10)
+        .build();
+
+    layout.append(
+        &gtk::Label::builder()
+            .label(prefix)
+            .css_classes(["dim-label"])
+            .build(),
+    );
+
+    match owner.user_id {
+        Some(user_id) => {
+            let tooltip = match owner.reputation {
+                Some(reputation) => format!("Open profile ({reputation} reputation)"),
+                None => "Open profile".to_owned(),
+            };
+
+            let button = gtk::Button::builder()
+                .label(&owner.display_name)
+                .css_classes(["link"])
+                .tooltip_text(tooltip)
+                .build();
+
+            button.connect_clicked(glib::clone!(@strong sender, @to-owned site => move |_| {
+                sender.emit(AppInput::OpenUserProfile {
+                    site: site.clone(),
+                    id: user_id,
+                });
+            }));
+
+            layout.append(&button);
+        }
+        // Anonymous and deleted users don't have profiles.
+        None => layout.append(&gtk::Label::new(Some(&owner.display_name))),
+    }
+
+    layout
+}
+
+/// Profile of a user with their reputation, badges, top tags and recent answers.
+pub fn st_user_profile(
+    user: &UserProfile,
+    top_tags: &[TopTag],
+    answers: &[AnswerSummary],
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
+    let main_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .spacing(10)
+        .margin_top(10)
+        .margin_bottom(10)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    // User name
+    main_layout.append(
+        &gtk::Label::builder()
+            .selectable(true)
+            .can_focus(false)
+            .label(&user.display_name)
+            .css_classes(["title-1"])
+            .wrap(true)
+            .halign(gtk::Align::Start)
+            .build(),
+    );
+
+    if let Some(location) = &user.location {
+        main_layout.append(
+            &gtk::Label::builder()
+                .label(location)
+                .css_classes(["dim-label"])
+                .halign(gtk::Align::Start)
+                .build(),
+        );
+    }
+
+    // Reputation and badges
+    let stats_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(20)
+        .build();
+    main_layout.append(&stats_layout);
+
+    stats_layout.append(
+        &gtk::Label::builder()
+            .label(format!("{} reputation", user.reputation))
+            .css_classes(["heading"])
+            .build(),
+    );
 
-pub fn st_question(question: &Question) -> gtk::Box {
+    for (count, name, css_class) in [
+        (user.badge_counts.gold, "gold", "warning"),
+        (user.badge_counts.silver, "silver", "dim-label"),
+        (user.badge_counts.bronze, "bronze", "accent"),
+    ] {
+        stats_layout.append(
+            &gtk::Label::builder()
+                .label(format!("● {count} {name}"))
+                .css_classes([css_class])
+                .build(),
+        );
+    }
+
+    // Top tags
+    main_layout.append(
+        &gtk::Label::builder()
+            .label("Top Tags")
+            .css_classes(["title-2"])
+            .halign(gtk::Align::Start)
+            .margin_top(10)
+            .build(),
+    );
+
+    let tags_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    main_layout.append(&tags_list);
+
+    for tag in top_tags {
+        tags_list.append(
+            &adw::ActionRow::builder()
+                .title(&tag.tag_name)
+                .use_markup(false)
+                .subtitle(format!(
+                    "{} answers with {} score · {} questions",
+                    tag.answer_count, tag.answer_score, tag.question_count
+                ))
+                .build(),
+        );
+    }
+
+    // Recent answers
+    main_layout.append(
+        &gtk::Label::builder()
+            .label("Recent Answers")
+            .css_classes(["title-2"])
+            .halign(gtk::Align::Start)
+            .margin_top(10)
+            .build(),
+    );
+
+    let answers_list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .css_classes(["boxed-list"])
+        .build();
+    main_layout.append(&answers_list);
+
+    for answer in answers {
+        let row = adw::ActionRow::builder()
+            .title(&answer.title)
+            .use_markup(false)
+            .subtitle(format!("{} votes", answer.score))
+            .activatable(true)
+            .build();
+
+        if answer.is_accepted {
+            row.add_suffix(
+                &gtk::Image::builder()
+                    .icon_name("emblem-ok-symbolic")
+                    .tooltip_text("Accepted")
+                    .css_classes(["success"])
+                    .build(),
+            );
+        }
+
+        let uri = format!("stackexchange://{}/{}", user.site, answer.question_id);
+        row.connect_activated(glib::clone!(@strong sender => move |_| {
+            sender.emit(AppInput::RequestPagesByUri(uri.clone()));
+        }));
+
+        answers_list.append(&row);
+    }
+
+    // Link to the full profile on the site
+    main_layout.append(
+        &gtk::LinkButton::builder()
+            .label("Full profile on the website")
+            .uri(&user.link)
+            .halign(gtk::Align::Start)
+            .build(),
+    );
+
+    main_layout
+}
+
+pub fn st_question(
+    question: &Question,
+    focused_answer: Option<Id>,
+    answer_sort: AnswerSort,
+    authenticated: bool,
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
     let main_layout = gtk::Box::new(gtk::Orientation::Vertical, 0);
 
+    // Old copy loaded from the cache while offline
+    if let Some(fetched) = question.cached {
+        main_layout.append(
+            &adw::Banner::builder()
+                .title(format!("Cached copy from {}", format_absolute(fetched)))
+                .revealed(true)
+                .build(),
+        );
+    }
+
+    if let Some(closed_reason) = &question.closed_reason {
+        let closed = match question.closed_date {
+            Some(closed_date) => {
+                format!(
+                    "Closed as {closed_reason} on {}",
+                    format_absolute(closed_date)
+                )
+            }
+            None => format!("Closed as {closed_reason}"),
+        };
+
+        main_layout.append(&adw::Banner::builder().title(closed).revealed(true).build());
+    }
+
     // Question title
     main_layout.append(
         &gtk::Label::builder()
@@ -17,29 +2015,64 @@ pub fn st_question(question: &Question) -> gtk::Box {
             .wrap_mode(gtk::pango::WrapMode::Char)
             .margin_start(5)
             .margin_end(5)
+            .margin_bottom(5)
+            .build(),
+    );
+
+    // Dates and views
+    let mut stats = vec![
+        format!("Asked {}", format_relative(question.creation_date)),
+        format!("Active {}", format_relative(question.last_activity_date)),
+    ];
+    if let Some(last_edit_date) = question.last_edit_date {
+        stats.push(format!("Edited {}", format_relative(last_edit_date)));
+    }
+    stats.push(format!("Viewed {} times", question.view_count));
+
+    main_layout.append(
+        &gtk::Label::builder()
+            .label(stats.join(" · "))
+            .css_classes(["dim-label", "caption"])
+            .wrap(true)
+            .margin_start(5)
+            .margin_end(5)
             .margin_bottom(10)
             .build(),
     );
 
-    // Question info bar
-    let question_header = gtk::Box::builder()
-        .orientation(gtk::Orientation::Horizontal)
+    // Question info bar, its actions go below the info in narrow windows
+    let question_header = gtk::FlowBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .min_children_per_line(1)
+        .max_children_per_line(2)
         .margin_bottom(20)
         .margin_start(10)
         .margin_end(10)
         .build();
     main_layout.append(&question_header);
 
+    let question_info = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .build();
+    question_header.insert(&question_info, -1);
+
+    let question_actions = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .hexpand(true)
+        .halign(gtk::Align::End)
+        .build();
+    question_header.insert(&question_actions, -1);
+
     // Answered or not indecator
     if question.is_answered {
-        question_header.append(
+        question_info.append(
             &gtk::Label::builder()
                 .label("Answered")
                 .css_classes(["success", "heading"])
                 .build(),
         );
     } else {
-        question_header.append(
+        question_info.append(
             &gtk::Label::builder()
                 .label("Not Answered")
                 .css_classes(["warning", "heading"])
@@ -47,6 +2080,97 @@ pub fn st_question(question: &Question) -> gtk::Box {
         )
     }
 
+    question_info.append(&st_author(
+        "asked by",
+        &question.owner,
+        &question.site,
+        sender,
+    ));
+
+    if let Some(bounty_amount) = question.bounty_amount {
+        let bounty = gtk::Label::builder()
+            .label(format!("+{bounty_amount} bounty"))
+            .css_classes(["accent", "heading"])
+            .margin_start(10)
+            .build();
+        if let Some(bounty_closes_date) = question.bounty_closes_date {
+            bounty.set_tooltip_text(Some(&format!(
+                "Ends {}",
+                format_relative(bounty_closes_date)
+            )));
+        }
+        question_info.append(&bounty);
+    }
+
+    // Edit history
+    let history_button = gtk::Button::builder()
+        .icon_name("document-open-recent-symbolic")
+        .tooltip_text("History")
+        .css_classes(["flat"])
+        .build();
+
+    history_button.connect_clicked(glib::clone!(
+        @strong sender,
+        @strong question.site as site,
+        @strong question.question_id as id,
+        @strong question.title as title => move |_| {
+            sender.emit(AppInput::OpenRevisions {
+                site: site.clone(),
+                id,
+                title: title.clone(),
+            });
+        }
+    ));
+
+    question_actions.append(&history_button);
+
+    // Jump to the accepted answer, connected once the answers are built
+    let accepted_button = gtk::Button::builder()
+        .icon_name("emblem-ok-symbolic")
+        .tooltip_text("Jump to Accepted Answer")
+        .css_classes(["flat", "success"])
+        .visible(question.accepted_answer_id.is_some())
+        .build();
+    question_actions.append(&accepted_button);
+
+    // Favorite toggle, kept in sync with the user's favorites on the site
+    if authenticated {
+        let favorite_button = gtk::ToggleButton::builder()
+            .icon_name(if question.favorited {
+                "starred-symbolic"
+            } else {
+                "non-starred-symbolic"
+            })
+            .tooltip_text("Favorite")
+            .active(question.favorited)
+            .css_classes(["flat"])
+            .build();
+
+        favorite_button.connect_toggled(|button| {
+            button.set_icon_name(if button.is_active() {
+                "starred-symbolic"
+            } else {
+                "non-starred-symbolic"
+            });
+        });
+
+        favorite_button.connect_clicked(glib::clone!(
+            @strong sender, @strong question.site as site, @strong question.question_id as id => move |button| {
+                // The button is already toggled optimistically, it's rolled back if the request fails.
+                sender.emit(AppInput::Favorite {
+                    site: site.clone(),
+                    id,
+                    favorite: button.is_active(),
+                    button: button.downgrade().into(),
+                });
+            }
+        ));
+
+        question_actions.append(&favorite_button);
+    }
+
+    main_layout.append(&st_tags(&question.tags, &question.site, sender));
+
     // Separator between header and question body
     main_layout.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
 
@@ -62,24 +2186,23 @@ pub fn st_question(question: &Question) -> gtk::Box {
         .build();
     question_layout.append(&question_sidebar_layout);
 
-    question_sidebar_layout.append(
-        &gtk::Label::builder()
-            .label(question.score.to_string())
-            .margin_top(10)
-            .margin_bottom(10)
-            .margin_start(10)
-            .margin_end(10)
-            .css_classes(if question.score >= 0 {
-                ["success"]
-            } else {
-                ["error"]
-            })
-            .build(),
-    );
+    question_sidebar_layout.append(&st_vote_controls(
+        &question.site,
+        question.question_id,
+        PostKind::Question,
+        question.question_id,
+        VoteState {
+            upvoted: question.upvoted,
+            downvoted: question.downvoted,
+            score: question.score,
+        },
+        authenticated,
+        sender,
+    ));
 
     question_layout.append(&gtk::Separator::new(gtk::Orientation::Vertical));
 
-    question_layout.append(&md2gtk(&question.body_markdown));
+    question_layout.append(&st_post_body(&question.body_markdown, sender));
 
     match &question.comments {
         Some(comments) => {
@@ -98,6 +2221,15 @@ pub fn st_question(question: &Question) -> gtk::Box {
         None => {}
     }
 
+    if authenticated {
+        main_layout.append(&st_comment_composer(
+            &question.site,
+            question.question_id,
+            question.question_id,
+            sender,
+        ));
+    }
+
     main_layout.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
 
     match &question.answers {
@@ -115,8 +2247,49 @@ pub fn st_question(question: &Question) -> gtk::Box {
                     .build(),
             );
 
+            let mut answers: Vec<&Answer> = answers.iter().collect();
+            match answer_sort {
+                AnswerSort::Votes => answers.sort_by_key(|answer| std::cmp::Reverse(answer.score)),
+                AnswerSort::Newest => {
+                    answers.sort_by_key(|answer| std::cmp::Reverse(answer.creation_date))
+                }
+                AnswerSort::Oldest => answers.sort_by_key(|answer| answer.creation_date),
+                AnswerSort::Active => {
+                    answers.sort_by_key(|answer| std::cmp::Reverse(answer.last_activity_date))
+                }
+            }
+
             for answer in answers {
-                main_layout.append(&st_answer(answer));
+                let answer_frame = st_answer(
+                    answer,
+                    &question.site,
+                    question.question_id,
+                    authenticated,
+                    sender,
+                );
+                // Tells which answer is focused, see `focused_answer`.
+                answer_frame.set_widget_name(&format!("answer-{}", answer.answer_id));
+                main_layout.append(&answer_frame);
+
+                if Some(answer.answer_id) == focused_answer {
+                    answer_frame.add_css_class("focused-answer");
+
+                    // Coordinates are only known once it's laid out, and only scroll the first time.
+                    let scrolled = std::cell::Cell::new(false);
+                    answer_frame.connect_map(move |answer_frame| {
+                        if !scrolled.replace(true) {
+                            glib::idle_add_local_once(
+                                glib::clone!(@weak answer_frame => move || scroll_to(&answer_frame)),
+                            );
+                        }
+                    });
+                }
+
+                if Some(answer.answer_id) == question.accepted_answer_id {
+                    accepted_button.connect_clicked(glib::clone!(@weak answer_frame => move |_| {
+                        scroll_to(&answer_frame);
+                    }));
+                }
             }
         }
         None => {}
@@ -125,7 +2298,13 @@ pub fn st_question(question: &Question) -> gtk::Box {
     main_layout
 }
 
-fn st_answer(answer: &Answer) -> gtk::Frame {
+fn st_answer(
+    answer: &Answer,
+    site: &str,
+    question_id: Id,
+    authenticated: bool,
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Frame {
     // Answer main area
     let answer_area_layout = gtk::Box::builder()
         .orientation(gtk::Orientation::Vertical)
@@ -143,24 +2322,49 @@ fn st_answer(answer: &Answer) -> gtk::Frame {
         .build();
     answer_layout.append(&answer_sidebar_layout);
 
-    answer_sidebar_layout.append(
-        &gtk::Label::builder()
-            .label(answer.score.to_string())
-            .margin_top(10)
-            .margin_bottom(10)
-            .margin_start(10)
-            .margin_end(10)
-            .css_classes(if answer.score >= 0 {
-                ["success"]
-            } else {
-                ["error"]
-            })
-            .build(),
-    );
+    answer_sidebar_layout.append(&st_vote_controls(
+        site,
+        question_id,
+        PostKind::Answer,
+        answer.answer_id,
+        VoteState {
+            upvoted: answer.upvoted,
+            downvoted: answer.downvoted,
+            score: answer.score,
+        },
+        authenticated,
+        sender,
+    ));
+
+    if answer.is_accepted {
+        answer_sidebar_layout.append(
+            &gtk::Image::builder()
+                .icon_name("emblem-ok-symbolic")
+                .tooltip_text("Accepted")
+                .pixel_size(24)
+                .css_classes(["success"])
+                .margin_bottom(10)
+                .build(),
+        );
+    }
 
     answer_layout.append(&gtk::Separator::new(gtk::Orientation::Vertical));
 
-    answer_layout.append(&md2gtk(&answer.body_markdown));
+    answer_layout.append(&st_post_body(&answer.body_markdown, sender));
+
+    let answered = match answer.last_edit_date {
+        Some(last_edit_date) => format!(
+            "answered {}, edited {}, by",
+            format_relative(answer.creation_date),
+            format_relative(last_edit_date)
+        ),
+        None => format!("answered {} by", format_relative(answer.creation_date)),
+    };
+    let author = st_author(&answered, &answer.owner, site, sender);
+    author.set_halign(gtk::Align::End);
+    author.set_margin_end(10);
+    author.set_margin_bottom(5);
+    answer_area_layout.append(&author);
 
     match &answer.comments {
         Some(comments) => {
@@ -171,6 +2375,15 @@ fn st_answer(answer: &Answer) -> gtk::Frame {
         None => {}
     }
 
+    if authenticated {
+        answer_area_layout.append(&st_comment_composer(
+            site,
+            question_id,
+            answer.answer_id,
+            sender,
+        ));
+    }
+
     gtk::Frame::builder()
         .child(&answer_area_layout)
         .margin_top(15)
@@ -180,6 +2393,428 @@ fn st_answer(answer: &Answer) -> gtk::Frame {
         .build()
 }
 
+/// Text searched by the find bar, post bodies are text views and everything else is labels.
+#[derive(Clone)]
+enum FindTarget {
+    Label(gtk::Label),
+    TextView(gtk::TextView),
+}
+
+impl FindTarget {
+    /// Its text, one character for each embedded widget so offsets match.
+    fn text(&self) -> String {
+        match self {
+            FindTarget::Label(label) => label.text().to_string(),
+            FindTarget::TextView(text_view) => {
+                let buffer = text_view.buffer();
+                let (start, end) = buffer.bounds();
+                buffer.slice(&start, &end, true).to_string()
+            }
+        }
+    }
+
+    /// Select the characters from `start` to `end` and scroll to them.
+    fn select(&self, start: i32, end: i32) {
+        match self {
+            FindTarget::Label(label) => {
+                label.set_selectable(true);
+                label.select_region(start, end);
+                scroll_to(label);
+            }
+            FindTarget::TextView(text_view) => {
+                let buffer = text_view.buffer();
+                let start = buffer.iter_at_offset(start);
+                buffer.select_range(&start, &buffer.iter_at_offset(end));
+
+                let location = text_view.iter_location(&start);
+                let (_, y) =
+                    text_view.buffer_to_window_coords(gtk::TextWindowType::Widget, 0, location.y());
+                scroll_to_offset(text_view, y as f64);
+            }
+        }
+    }
+}
+
+/// The rendered body of a question or answer.
+fn st_post_body(body_markdown: &str, sender: &relm4::Sender<AppInput>) -> gtk::TextView {
+    let body = md2gtk(body_markdown);
+    connect_question_links(body.upcast_ref(), sender);
+
+    body
+}
+
+/// Open links to questions inside `widget` in new tabs on a middle click, in the background, or
+/// on a Ctrl+click. Plain clicks show them in the tab of the link instead.
+fn connect_question_links(widget: &gtk::Widget, sender: &relm4::Sender<AppInput>) {
+    let mut child = widget.first_child();
+    while let Some(current) = child {
+        match current.downcast_ref::<gtk::LinkButton>() {
+            Some(link_button) => connect_question_link(link_button, sender),
+            None => connect_question_links(&current, sender),
+        }
+        child = current.next_sibling();
+    }
+}
+
+fn connect_question_link(link_button: &gtk::LinkButton, sender: &relm4::Sender<AppInput>) {
+    let uri = match parse_uri(&link_button.uri()) {
+        Ok(link @ (UriLink::Questions { .. } | UriLink::Answer { .. })) => link.to_uri(),
+        _ => return,
+    };
+
+    // Captured before the button handles the click itself.
+    let gesture = gtk::GestureClick::builder()
+        .button(0)
+        .propagation_phase(gtk::PropagationPhase::Capture)
+        .build();
+    gesture.connect_pressed(glib::clone!(@strong sender => move |gesture, _, _, _| {
+        let message = match gesture.current_button() {
+            gtk::gdk::BUTTON_MIDDLE => AppInput::OpenLinkInNewTab {
+                uri: uri.clone(),
+                background: true,
+            },
+            gtk::gdk::BUTTON_PRIMARY
+                if gesture
+                    .current_event_state()
+                    .contains(gtk::gdk::ModifierType::CONTROL_MASK) =>
+            {
+                AppInput::OpenLinkInNewTab {
+                    uri: uri.clone(),
+                    background: false,
+                }
+            }
+            gtk::gdk::BUTTON_PRIMARY => AppInput::FollowLink {
+                uri: uri.clone(),
+                link: gesture.widget().downgrade().into(),
+            },
+            _ => return,
+        };
+
+        gesture.set_state(gtk::EventSequenceState::Claimed);
+        sender.emit(message);
+    }));
+    link_button.add_controller(gesture);
+}
+
+/// The highlighted answer of a box built by `st_question`.
+pub fn focused_answer(question_box: &gtk::Widget) -> Option<Id> {
+    let mut child = question_box.first_child();
+    while let Some(current) = child {
+        if current.has_css_class("focused-answer") {
+            return current.widget_name().strip_prefix("answer-")?.parse().ok();
+        }
+        child = current.next_sibling();
+    }
+
+    None
+}
+
+/// Highlight an answer of a box built by `st_question` instead of the highlighted one, and
+/// scroll to it when `scroll` is true.
+pub fn highlight_answer(question_box: &gtk::Widget, answer_id: Id, scroll: bool) {
+    let name = format!("answer-{answer_id}");
+
+    let mut child = question_box.first_child();
+    while let Some(current) = child {
+        if current.widget_name() == name {
+            current.add_css_class("focused-answer");
+            if scroll {
+                scroll_to(&current);
+            }
+        } else {
+            current.remove_css_class("focused-answer");
+        }
+        child = current.next_sibling();
+    }
+}
+
+/// Every visible label and text view inside `widget`, in reading order.
+fn find_targets(widget: &gtk::Widget, targets: &mut Vec<FindTarget>) {
+    if !widget.is_drawable() {
+        return;
+    }
+    if let Some(label) = widget.downcast_ref::<gtk::Label>() {
+        targets.push(FindTarget::Label(label.clone()));
+    } else if let Some(text_view) = widget.downcast_ref::<gtk::TextView>() {
+        targets.push(FindTarget::TextView(text_view.clone()));
+    }
+
+    let mut child = widget.first_child();
+    while let Some(current) = child {
+        find_targets(&current, targets);
+        child = current.next_sibling();
+    }
+}
+
+/// Where `query` occurs in `text`, case insensitively, in characters. Characters are compared one
+/// by one instead of lowercasing `text`, which would shift the offsets after characters whose
+/// lowercase is longer, like 'İ'.
+fn text_matches(text: &str, query: &str) -> Vec<(i32, i32)> {
+    let same = |a: &char, b: &char| a == b || a.to_lowercase().eq(b.to_lowercase());
+    let text: Vec<char> = text.chars().collect();
+    let query: Vec<char> = query.chars().collect();
+
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+
+    let mut start = 0;
+    while start + query.len() <= text.len() {
+        let end = start + query.len();
+        if text[start..end].iter().zip(&query).all(|(a, b)| same(a, b)) {
+            matches.push((start as i32, end as i32));
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+
+    matches
+}
+
+/// Matches of the find bar, the text they are in with their start and end.
+#[derive(Default)]
+struct FindMatches {
+    matches: Vec<(FindTarget, i32, i32)>,
+    selected: usize,
+    // Labels made selectable to select their match, they're reset once the find bar is closed.
+    selectable_labels: Vec<gtk::Label>,
+}
+
+/// A find bar for the text of `content`, shown with `set_search_mode`. Matches are selected one
+/// at a time, Enter and Shift+Enter step through them.
+pub fn st_find_bar(content: &gtk::Widget) -> gtk::SearchBar {
+    let search_entry = gtk::SearchEntry::builder()
+        .placeholder_text("Find in page")
+        .hexpand(true)
+        .build();
+    let matches_label = gtk::Label::builder().css_classes(["dim-label"]).build();
+    let previous_button = gtk::Button::builder()
+        .icon_name("go-up-symbolic")
+        .tooltip_text("Previous Match")
+        .build();
+    let next_button = gtk::Button::builder()
+        .icon_name("go-down-symbolic")
+        .tooltip_text("Next Match")
+        .build();
+
+    let find_box = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(5)
+        .build();
+    find_box.append(&search_entry);
+    find_box.append(&matches_label);
+    find_box.append(&previous_button);
+    find_box.append(&next_button);
+
+    let search_bar = gtk::SearchBar::builder()
+        .child(&find_box)
+        .show_close_button(true)
+        .build();
+    search_bar.connect_entry(&search_entry);
+
+    let find_matches = Rc::new(RefCell::new(FindMatches::default()));
+
+    let select_match = glib::clone!(
+        @strong find_matches,
+        @weak matches_label => move |step: isize| {
+        let FindMatches { matches, selected, selectable_labels } = &mut *find_matches.borrow_mut();
+        if matches.is_empty() {
+            return;
+        }
+
+        *selected = (*selected as isize + step).rem_euclid(matches.len() as isize) as usize;
+        let (target, start, end) = &matches[*selected];
+        if let FindTarget::Label(label) = target {
+            if !label.is_selectable() {
+                selectable_labels.push(label.clone());
+            }
+        }
+        target.select(*start, *end);
+
+        matches_label.set_label(&format!("{} of {}", *selected + 1, matches.len()));
+    });
+
+    search_entry.connect_search_changed(glib::clone!(
+        @strong find_matches,
+        @strong select_match,
+        @weak content,
+        @weak matches_label => move |entry| {
+        let query = entry.text();
+
+        let mut targets = Vec::new();
+        find_targets(&content, &mut targets);
+
+        let matches = if query.is_empty() {
+            Vec::new()
+        } else {
+            targets
+                .into_iter()
+                .flat_map(|target| {
+                    text_matches(&target.text(), &query)
+                        .into_iter()
+                        .map(move |(start, end)| (target.clone(), start, end))
+                })
+                .collect()
+        };
+        let no_matches = matches.is_empty();
+        {
+            let mut find_matches = find_matches.borrow_mut();
+            find_matches.matches = matches;
+            find_matches.selected = 0;
+        }
+
+        if query.is_empty() {
+            matches_label.set_label("");
+        } else if no_matches {
+            matches_label.set_label("No matches");
+        } else {
+            select_match(0);
+        }
+    }));
+
+    search_entry.connect_activate(glib::clone!(@strong select_match => move |_| select_match(1)));
+    search_entry.connect_next_match(glib::clone!(@strong select_match => move |_| select_match(1)));
+    search_entry
+        .connect_previous_match(glib::clone!(@strong select_match => move |_| select_match(-1)));
+    next_button.connect_clicked(glib::clone!(@strong select_match => move |_| select_match(1)));
+    previous_button.connect_clicked(move |_| select_match(-1));
+
+    search_bar.connect_search_mode_enabled_notify(move |search_bar| {
+        if search_bar.is_search_mode() {
+            return;
+        }
+        for label in find_matches.borrow_mut().selectable_labels.drain(..) {
+            label.set_selectable(false);
+        }
+    });
+
+    search_bar
+}
+
+/// Scroll the closest scrolled window up to `widget`.
+fn scroll_to(widget: &impl IsA<gtk::Widget>) {
+    scroll_to_offset(widget, 0.0);
+}
+
+/// Scroll the closest scrolled window up to `offset` pixels below the top of `widget`.
+fn scroll_to_offset(widget: &impl IsA<gtk::Widget>, offset: f64) {
+    let Some(scrolled_window) = widget
+        .ancestor(gtk::ScrolledWindow::static_type())
+        .and_downcast::<gtk::ScrolledWindow>()
+    else {
+        return;
+    };
+
+    // Coordinates inside the scrolled content, the scrolled window's child is a viewport.
+    let Some(content) = scrolled_window
+        .child()
+        .and_then(|viewport| viewport.first_child())
+    else {
+        return;
+    };
+
+    if let Some((_, y)) = widget.translate_coordinates(&content, 0.0, offset) {
+        scrolled_window.vadjustment().set_value(y);
+    }
+}
+
+/// Entry to comment on a post, posted comments are added right before it.
+/// `post_id` is the question `question_id` or one of its answers.
+fn st_comment_composer(
+    site: &str,
+    question_id: Id,
+    post_id: Id,
+    sender: &relm4::Sender<AppInput>,
+) -> gtk::Box {
+    let composer = gtk::Box::builder()
+        .orientation(gtk::Orientation::Horizontal)
+        .spacing(5)
+        .margin_top(5)
+        .margin_bottom(5)
+        .margin_start(10)
+        .margin_end(10)
+        .build();
+
+    let entry = gtk::Entry::builder()
+        .placeholder_text("Add a comment")
+        .max_length(COMMENT_MAX_LENGTH as i32)
+        .hexpand(true)
+        .build();
+    composer.append(&entry);
+
+    let length_label = gtk::Label::builder()
+        .css_classes(["dim-label", "caption", "numeric"])
+        .build();
+    composer.append(&length_label);
+
+    let comment_button = gtk::Button::builder()
+        .label("Comment")
+        .sensitive(false)
+        .build();
+    composer.append(&comment_button);
+
+    // Comments shorter than the minimum are rejected by the API.
+    entry.connect_changed(
+        glib::clone!(@weak length_label, @weak comment_button => move |entry| {
+            let length = entry.text().trim().chars().count();
+            comment_button.set_sensitive(length >= COMMENT_MIN_LENGTH);
+
+            if length == 0 {
+                length_label.set_label("");
+            } else if length < COMMENT_MIN_LENGTH {
+                length_label.set_label(&format!("{} more", COMMENT_MIN_LENGTH - length));
+            } else {
+                length_label.set_label(&format!("{length}/{COMMENT_MAX_LENGTH}"));
+            }
+        }),
+    );
+
+    let submit = glib::clone!(
+        @strong sender, @weak entry, @weak comment_button, @weak composer, @to-owned site =>
+        move || {
+            let body = entry.text().trim().to_owned();
+            if body.chars().count() < COMMENT_MIN_LENGTH {
+                return;
+            }
+
+            // Until the comment is posted, the text is kept if it fails. It's only sent once.
+            entry.set_sensitive(false);
+            comment_button.set_sensitive(false);
+            sender.emit(AppInput::AddComment {
+                site: site.clone(),
+                question_id,
+                post_id,
+                body,
+                composer: composer.downgrade().into(),
+            });
+        }
+    );
+
+    entry.connect_activate(glib::clone!(@strong submit => move |_| submit()));
+    comment_button.connect_clicked(move |_| submit());
+
+    composer
+}
+
+/// Show a posted comment before its composer and make the composer usable again.
+pub fn finish_comment(composer: &gtk::Box, comment: Option<&Comment>) {
+    let Some(entry) = composer.first_child().and_downcast::<gtk::Entry>() else {
+        return;
+    };
+    entry.set_sensitive(true);
+    // Clearing the entry disables it again.
+    if let Some(comment_button) = composer.last_child().and_downcast::<gtk::Button>() {
+        comment_button.set_sensitive(true);
+    }
+
+    if let (Some(comment), Some(parent)) = (comment, composer.parent()) {
+        st_comment(comment).insert_before(&parent, Some(composer));
+        entry.set_text("");
+    }
+}
+
 fn st_comment(comment: &Comment) -> gtk::Frame {
     // Comment Body
     let comment_layout = gtk::Box::builder()
@@ -209,9 +2844,16 @@ fn st_comment(comment: &Comment) -> gtk::Frame {
 
     comment_layout.append(&gtk::Separator::new(gtk::Orientation::Vertical));
 
+    // Comment content with its author
+    let comment_content_layout = gtk::Box::builder()
+        .orientation(gtk::Orientation::Vertical)
+        .hexpand(true)
+        .build();
+    comment_layout.append(&comment_content_layout);
+
     match &comment.body_markdown {
-        Some(body_markdown) => comment_layout.append(&md2gtk(body_markdown)),
-        None => comment_layout.append(
+        Some(body_markdown) => comment_content_layout.append(&md2gtk(body_markdown)),
+        None => comment_content_layout.append(
             &gtk::Label::builder()
                 .label("No content")
                 .css_classes(["dim-label"])
@@ -219,6 +2861,21 @@ fn st_comment(comment: &Comment) -> gtk::Frame {
         ),
     };
 
+    comment_content_layout.append(
+        &gtk::Label::builder()
+            .label(format!(
+                "{} · {}{}",
+                comment.owner.display_name,
+                format_relative(comment.creation_date),
+                if comment.edited { " (edited)" } else { "" }
+            ))
+            .css_classes(["dim-label", "caption"])
+            .halign(gtk::Align::End)
+            .margin_end(10)
+            .margin_bottom(5)
+            .build(),
+    );
+
     gtk::Frame::builder()
         .child(&comment_layout)
         .margin_top(5)
@@ -0,0 +1,80 @@
+// Fuzzy matching for the quick switchers, the characters of the pattern have to appear in order in
+// the text, but not next to each other.
+
+// Added for every matched character.
+const MATCH_SCORE: u32 = 1;
+// Added when the previous character matched too.
+const CONSECUTIVE_BONUS: u32 = 2;
+// Added when the character starts a word, so "ob" ranks "Open in Browser" first.
+const WORD_START_BONUS: u32 = 3;
+
+/// How well `text` matches `pattern`, higher is better, `None` if it doesn't. Case and the spaces
+/// of `pattern` are ignored, so an empty pattern matches everything.
+pub fn score(pattern: &str, text: &str) -> Option<u32> {
+    let mut pattern = pattern
+        .chars()
+        .filter(|character| !character.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+
+    for character in text.chars().flat_map(char::to_lowercase) {
+        let Some(&wanted) = pattern.peek() else {
+            break;
+        };
+
+        let matched = character == wanted;
+        if matched {
+            pattern.next();
+            score += MATCH_SCORE;
+            if previous_matched {
+                score += CONSECUTIVE_BONUS;
+            }
+            if !previous.is_some_and(char::is_alphanumeric) {
+                score += WORD_START_BONUS;
+            }
+        }
+
+        previous = Some(character);
+        previous_matched = matched;
+    }
+
+    pattern.peek().is_none().then_some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_word_starts_and_consecutive_matches_first() {
+        let mut titles = ["Close Tabs to the Right", "Open in Browser", "Copy Link"];
+        titles.sort_by_key(|title| std::cmp::Reverse(score("ob", title)));
+        assert_eq!(titles[0], "Open in Browser");
+
+        assert!(score("op", "open") > score("op", "oxp"));
+    }
+
+    #[test]
+    fn no_match() {
+        assert_eq!(score("po", "Open"), None);
+        assert_eq!(score("xyz", "Open in Browser"), None);
+        assert_eq!(score("open", "Op"), None);
+    }
+
+    #[test]
+    fn ignores_case_and_spaces() {
+        assert_eq!(
+            score("OIB", "open in browser"),
+            score("oib", "Open In Browser")
+        );
+        assert_eq!(
+            score("o b", "Open in Browser"),
+            score("ob", "Open in Browser")
+        );
+        assert_eq!(score("", "Open in Browser"), Some(0));
+    }
+}
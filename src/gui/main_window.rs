@@ -1,4 +1,9 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
 use adw::prelude::*;
+use futures::future::{AbortHandle, Abortable};
 use relm4::{
     actions::AccelsPlus,
     adw::traits::AdwWindowExt,
@@ -13,22 +18,400 @@ use relm4::{
 use relm4_icons::icon_name;
 
 use super::componant_builders;
+use super::preferences;
+use crate::api::feeds::FeedEntry;
+use crate::api::provider::{FixtureProvider, QuestionProvider};
+use crate::api::search::SearchQuery;
 use crate::api::stackexchange;
+use crate::api::stackexchange::Vote;
+use crate::bookmarks::Bookmarks;
+use crate::history::History;
+use crate::session::{Session, SessionTab};
+use crate::settings::{AnswerSort, Settings, Subscription, Theme, MAX_ZOOM, MIN_ZOOM, ZOOM_STEP};
 
 const APP_NAME: &str = "StackBloatLess";
+const APP_SUBTITLE: &str = "Your 1000 tabs are in safe hands";
+// Narrower windows, like on phones, hide the tab bar and condense the header, in pixels.
+const NARROW_WIDTH: i32 = 600;
+// Widest the content of question tabs gets in reading mode.
+const READING_WIDTH: i32 = 900;
+// How often the progress bar pulses while questions are loading, in milliseconds.
+const PROGRESS_PULSE_INTERVAL: u64 = 100;
+// Quitting with more unpinned tabs asks first, when they won't be restored.
+const QUIT_CONFIRMATION_TABS: u32 = 10;
+// Older closed tabs can't be reopened.
+const MAX_CLOSED_TABS: usize = 20;
+// Closed tabs listed in the "Recently Closed" menu, older ones are reopened with Ctrl+Shift+T.
+const CLOSED_TABS_MENU_ENTRIES: usize = 10;
+// Tabs listed while going through the recently used ones with Ctrl+Tab.
+const TAB_CYCLE_ENTRIES: usize = 10;
+// How often subscribed feeds are checked, in seconds.
+const SUBSCRIPTIONS_INTERVAL: u32 = 15 * 60;
+// How often the inbox is checked while logged in, in seconds.
+const INBOX_INTERVAL: u32 = 5 * 60;
+// How often the reputation is refreshed while logged in, in seconds.
+const REPUTATION_INTERVAL: u32 = 30 * 60;
+// How often the open tabs are saved to recover them after a crash, in seconds.
+const RECOVERY_INTERVAL: u32 = 60;
+// How often watched questions are checked for new answers, in seconds.
+const WATCH_INTERVAL: u32 = 5 * 60;
+// How often tabs that weren't looked at for a while are suspended, in seconds.
+const SUSPEND_INTERVAL: u32 = 60;
+
+// Width of the sidebar, it covers the tabs when the window is narrower than twice that.
+const SIDEBAR_WIDTH: i32 = 300;
+// Only the most recent ones are listed in the sidebar, the history tab has them all.
+const SIDEBAR_HISTORY_ENTRIES: usize = 100;
+// Name, title and icon of the pages of the sidebar.
+const SIDEBAR_PAGES: &[(&str, &str, &str)] = &[
+    ("history", "History", "document-open-recent-symbolic"),
+    ("bookmarks", "Bookmarks", "user-bookmarks-symbolic"),
+    (
+        "watching",
+        "Watching",
+        "preferences-system-notifications-symbolic",
+    ),
+];
+// Actions without a menu entry, the command palette lists them after the ones of the menus.
+const PALETTE_ACTIONS: &[(&str, &str)] = &[
+    ("New Tab", "tab.new"),
+    ("Show All Tabs", "tab.overview"),
+    ("Switch to Tab", "tab.switcher"),
+    ("Next Tab", "tab.next"),
+    ("Previous Tab", "tab.previous"),
+    ("Move Tab Right", "tab.move_right"),
+    ("Move Tab Left", "tab.move_left"),
+    ("Reopen Closed Tab", "tab.reopen_last"),
+    ("Back", "tab.back"),
+    ("Forward", "tab.forward"),
+    ("Search", "tab.search"),
+    ("Find in Page", "tab.find"),
+    ("Zoom In", "tab.zoom_in"),
+    ("Zoom Out", "tab.zoom_out"),
+    ("Reset Zoom", "tab.reset_zoom"),
+];
 
 // Save build-time informations
 shadow_rs::shadow!(build);
 
+/// A closed question tab, it can be reopened at the same scroll position.
+#[derive(Debug, Clone)]
+pub struct ClosedTab {
+    site: String,
+    question_id: stackexchange::Id,
+    title: String,
+    scroll_position: f64,
+}
+
+/// Where a question tab continues once it's loaded, it's only loaded when it's selected.
+#[derive(Debug, Clone, Default)]
+struct UnloadedTab {
+    scroll_position: f64,
+    answer_id: Option<stackexchange::Id>,
+}
+
+/// What a question tab continues with once `load_questions` loaded it.
+#[derive(Debug, Clone, Default)]
+pub struct AfterLoad {
+    scroll_position: Option<f64>,
+    // Highlighted without scrolling to it, the scroll position is restored instead.
+    answer_id: Option<stackexchange::Id>,
+    // The tab selected before a tab was opened in the background, it's selected again if the
+    // loaded question took the selection to show an answer.
+    reselect: Option<gtk::glib::SendWeakRef<adw::TabPage>>,
+    // The tab followed a link or went through its history, its buttons change.
+    navigated: bool,
+}
+
+/// A question a tab showed before following a link, it's shown again at the same scroll position.
+struct TabLocation {
+    site: String,
+    question_id: stackexchange::Id,
+    scroll_position: f64,
+}
+
+/// Questions of a tab to go back and forward to, the most recent last.
+#[derive(Default)]
+struct TabHistory {
+    back: Vec<TabLocation>,
+    forward: Vec<TabLocation>,
+}
+
+/// What the windows tabs are moved to share with the main one, their tabs are handled by it.
+#[derive(Clone)]
+struct TabWindowContext {
+    pending_requests: Rc<RefCell<HashMap<adw::TabPage, AbortHandle>>>,
+    question_tabs: Rc<RefCell<HashMap<adw::TabPage, (String, stackexchange::Id)>>>,
+    unloaded_tabs: Rc<RefCell<HashMap<adw::TabPage, UnloadedTab>>>,
+    watched_tabs: Rc<RefCell<HashMap<adw::TabPage, stackexchange::QuestionActivity>>>,
+    auto_refresh_tabs: Rc<RefCell<HashMap<adw::TabPage, gtk::glib::SourceId>>>,
+    tab_windows: Rc<RefCell<Vec<adw::TabView>>>,
+    active_tab_view: Rc<RefCell<Option<adw::TabView>>>,
+    tab_menu: gtk::gio::Menu,
+    tab_actions: gtk::gio::SimpleActionGroup,
+    sender: relm4::Sender<AppInput>,
+}
+
 #[derive(Debug, Clone)]
 pub enum AppInput {
     RequestPagesByUri(stackexchange::Uri),
     ToggleSearchEntry,
+    SuggestQuestions(String),
+    Search {
+        site: String,
+        query: String,
+    },
     ShowAboutWindow,
-    Quit,
+    ShowPreferences,
+    // Restore the tabs on the next launch, even if it's disabled.
+    Quit {
+        restore_session: bool,
+    },
     ToggleSelectedTabPin,
+    // Show the find bar of the selected tab, if it's a question.
+    ShowFindBar,
+    // Open the question of the selected tab again next to it, at the same scroll position.
+    DuplicateTab,
+    MoveTabToNewWindow,
+    RememberClosedTab(ClosedTab),
+    // Reopen the nth most recently closed tab, starting at 0.
+    ReopenClosedTab(u32),
+    ToggleTabOverview,
+    // Pick a tab by typing parts of its title.
+    ShowTabSwitcher,
+    FocusTab(gtk::glib::SendWeakRef<adw::TabPage>),
+    NewTab,
+    // Select the next tab if true, or the previous one, wrapping around.
+    CycleTabs(bool),
+    // Select the next most recently used tab if true, or the previous one, while Ctrl is held.
+    // It's `CycleTabs` unless `recent_tab_cycling` is set.
+    CycleRecentTabs(bool),
+    // Ctrl was released, the tab it ended on becomes the most recently used.
+    EndTabCycle,
+    UpdateTabRecency,
+    // Select the nth tab, starting at 1.
+    SelectTab(u32),
+    // Move the selected tab right if true, or left, pinned tabs stay before the others.
+    MoveTab(bool),
+    FocusSearch,
     CloseTab,
-    ClosePinnedTab,
+    // Ask before closing a pinned tab, the tab view doesn't close them by itself.
+    ConfirmCloseTab(gtk::glib::SendWeakRef<adw::TabPage>),
+    ClosePinnedTab(gtk::glib::SendWeakRef<adw::TabPage>),
+    // Pinned tabs are kept by both.
+    CloseOtherTabs,
+    CloseTabsToRight,
+    Login,
+    FinishLogin(String),
+    Logout,
+    Vote {
+        site: String,
+        // The question of voted answers, its cached copy is outdated.
+        question_id: stackexchange::Id,
+        post_kind: stackexchange::PostKind,
+        id: stackexchange::Id,
+        controls: componant_builders::VoteControls,
+        previous: componant_builders::VoteState,
+        next: componant_builders::VoteState,
+    },
+    Favorite {
+        site: String,
+        id: stackexchange::Id,
+        favorite: bool,
+        button: gtk::glib::SendWeakRef<gtk::ToggleButton>,
+    },
+    OpenFavorites,
+    FavoritesLoaded(Result<Vec<stackexchange::Question>, stackexchange::StackExchangeError>),
+    OpenHistory,
+    ClearHistory,
+    // Bookmark the question of the selected tab, or remove its bookmark.
+    ToggleBookmark,
+    ToggleQuestionBookmark {
+        site: String,
+        id: stackexchange::Id,
+        // Saved with the bookmark.
+        title: String,
+    },
+    UpdateQuestionButtons,
+    OpenBookmarks,
+    SetBookmarkFolder {
+        site: String,
+        question_id: stackexchange::Id,
+        folder: Option<String>,
+    },
+    RemoveBookmark {
+        site: String,
+        question_id: stackexchange::Id,
+    },
+    // Copy the web link of the question in the selected tab.
+    CopyLink,
+    // Open the question of the selected tab in the web browser.
+    OpenInBrowser,
+    // Hot questions of the site of the selected tab.
+    OpenSiteHome,
+    // Check the question of the selected tab for new answers, or stop checking it.
+    ToggleWatch,
+    PollWatchedTabs,
+    // Load the question of a tab again, instead of its cached copy.
+    RefreshTab(gtk::glib::SendWeakRef<adw::TabPage>),
+    RefreshSelectedTab,
+    // Refresh the selected tab every so many minutes, never if 0.
+    SetAutoRefresh(u32),
+    // Refresh a tab if its question changed, sent by its auto refresh timer.
+    AutoRefreshTab(gtk::glib::SendWeakRef<adw::TabPage>),
+    // A short message that doesn't need an action, like recoverable errors.
+    ShowToast(String),
+    AddComment {
+        site: String,
+        // The question of commented answers, its cached copy is outdated.
+        question_id: stackexchange::Id,
+        post_id: stackexchange::Id,
+        body: String,
+        composer: gtk::glib::SendWeakRef<gtk::Box>,
+    },
+    OpenHome,
+    LoadTaggedQuestions {
+        site: String,
+        // Every question of the site if it's empty.
+        tags: Vec<String>,
+        sort: stackexchange::QuestionSort,
+        section: gtk::glib::SendWeakRef<gtk::Box>,
+    },
+    BrowseTags {
+        site: String,
+        tags: Vec<String>,
+    },
+    OpenUserProfile {
+        site: String,
+        id: stackexchange::Id,
+    },
+    OpenTag {
+        site: String,
+        tag: String,
+    },
+    SetSubscribed {
+        site: String,
+        tag: String,
+        subscribed: bool,
+    },
+    PollSubscriptions,
+    // The feeds fetched by `PollSubscriptions`, by subscription.
+    FeedsChecked(
+        Vec<(
+            Subscription,
+            Result<Vec<FeedEntry>, stackexchange::StackExchangeError>,
+        )>,
+    ),
+    PollInbox,
+    RefreshReputation,
+    OpenSubscriptions,
+    SetNotifySubscriptions(bool),
+    SetRestoreSession(bool),
+    // In minutes, 0 never suspends tabs.
+    SetSuspendTabsAfter(u32),
+    SetRecentTabCycling(bool),
+    SetDefaultSite(String),
+    SetTheme(Theme),
+    // The window became narrower than `NARROW_WIDTH` if true, or wider.
+    SetNarrow(bool),
+    // Fullscreen with only the content of the tabs, or back to the normal window.
+    ToggleReadingMode,
+    // Show the question of the selected tab beside the tabs, to compare it with the others.
+    SplitTab,
+    CloseSplitView,
+    // Reveal the sidebar, or hide it.
+    ToggleSidebar,
+    // Build the lists of the sidebar again, if it's revealed.
+    UpdateSidebar,
+    // Select the tab of the question, or open one if there isn't.
+    OpenQuestion {
+        site: String,
+        id: stackexchange::Id,
+    },
+    // Pick any action of the menus, or one of `PALETTE_ACTIONS`, by typing parts of its name.
+    ShowCommandPalette,
+    ActivateAction {
+        name: String,
+        target: Option<gtk::glib::Variant>,
+    },
+    SetContentFont(Option<String>),
+    SetCodeFont(Option<String>),
+    SetDefaultZoom(u32),
+    // Zoom the selected tab in or out by steps, 0 resets it to the default zoom.
+    ZoomTab(i32),
+    SetAnswerSort(AnswerSort),
+    SetApiKey(Option<String>),
+    // Text entered in the search entry.
+    SubmitSearchTerm(String),
+    // Past search terms, shown while the search entry is focused and empty.
+    ShowSearchHistory,
+    RemoveSearchTerm(String),
+    // Open the StackExchange links in the clipboard.
+    PasteLink,
+    RestoreSession(Vec<SessionTab>),
+    // Save the open tabs, in case the app crashes.
+    SaveRecovery,
+    OpenRevisions {
+        site: String,
+        id: stackexchange::Id,
+        title: String,
+    },
+    LoadRelatedQuestions {
+        site: String,
+        id: stackexchange::Id,
+        relation: stackexchange::Relation,
+        section: gtk::glib::SendWeakRef<gtk::Box>,
+    },
+    // Links to questions opened with a middle click, or a Ctrl+click if not `background`.
+    OpenLinkInNewTab {
+        uri: String,
+        background: bool,
+    },
+    // Links to questions opened with a plain click, in the tab of `link`.
+    FollowLink {
+        uri: String,
+        link: gtk::glib::SendWeakRef<gtk::Widget>,
+    },
+    // Drop the pages of question tabs that weren't selected for `suspend_tabs_after` minutes.
+    SuspendIdleTabs,
+    // Load the question of a tab restored, opened or suspended without its content, once it's
+    // selected.
+    LoadTab(gtk::glib::SendWeakRef<adw::TabPage>),
+    // Show the previous or next question of the selected tab.
+    NavigateBack,
+    NavigateForward,
+    // Load `uri` again in the tab that shows why it failed.
+    RetryRequest {
+        uri: String,
+        tab_page: gtk::glib::SendWeakRef<adw::TabPage>,
+    },
+    // The questions of `uri` were fetched for `placeholder`, or failed to.
+    QuestionsLoaded {
+        uri: String,
+        placeholder: gtk::glib::SendWeakRef<adw::TabPage>,
+        pages: Result<
+            (stackexchange::UriTarget, Vec<stackexchange::Question>),
+            stackexchange::StackExchangeError,
+        >,
+        refresh: bool,
+        after_load: AfterLoad,
+    },
+    // For tabs that are created without awaiting, like home tabs.
+    LoadSiteIcon {
+        site: String,
+        tab_page: gtk::glib::SendWeakRef<adw::TabPage>,
+    },
+    SetMeasureCompression(bool),
+    SetOffline(bool),
+    // Opened questions and searches aren't recorded while it's on.
+    SetPrivate(bool),
+    SetProxy(Option<String>),
+    SetMaxConcurrentRequests(usize),
+    // In seconds.
+    SetTimeouts {
+        connect_timeout: u64,
+        request_timeout: u64,
+    },
 }
 
 pub struct AppInit {
@@ -36,15 +419,90 @@ pub struct AppInit {
 }
 
 pub struct AppModel {
-    stackexchange_client: stackexchange::StackExchange,
+    // Shared with the requests sent outside of `update`, so waiting for them doesn't block the
+    // other messages.
+    stackexchange_client: Rc<stackexchange::StackExchange>,
+    // Replace the API for opening questions, set with `STACKBLOATLESS_FIXTURES`.
+    fixtures: Option<Rc<FixtureProvider>>,
+    settings: Settings,
+    // Applies the content font.
+    content_css: gtk::CssProvider,
+    // The default site, shared with the tabs overview which creates home tabs by itself.
+    home_site: Rc<RefCell<String>>,
+    quota: Option<stackexchange::Quota>,
+    // The window is narrower than `NARROW_WIDTH`.
+    narrow: bool,
+    reading_mode: bool,
+    // Nothing is added to the history, the disk cache or the session.
+    private: Rc<Cell<bool>>,
+    // `settings.restore_session`, shared with the close handler of the window.
+    restore_session: Rc<Cell<bool>>,
+    // Requests of tabs that are still loading, they're aborted when the tab is closed.
+    pending_requests: Rc<RefCell<HashMap<adw::TabPage, AbortHandle>>>,
+    // Site and id of the question shown by each question tab, saved with the session.
+    question_tabs: Rc<RefCell<HashMap<adw::TabPage, (String, stackexchange::Id)>>>,
+    // Question tabs that weren't selected since they were opened or suspended, they're in
+    // `question_tabs` too.
+    unloaded_tabs: Rc<RefCell<HashMap<adw::TabPage, UnloadedTab>>>,
+    // Most recently closed first.
+    closed_tabs: Vec<ClosedTab>,
+    // Tabs of the last recovery snapshot, it's only written again once they change.
+    recovery_tabs: Vec<SessionTab>,
+    // Tab views of the windows tabs were moved to, in the order they were opened.
+    tab_windows: Rc<RefCell<Vec<adw::TabView>>>,
+    // Tab view of the window tabs were moved to that was focused last, `None` while the main
+    // window is. The tab actions apply to its selected tab.
+    active_tab_view: Rc<RefCell<Option<adw::TabView>>>,
+    // Opened questions, recorded by `open_question_tabs`.
+    history: RefCell<History>,
+    bookmarks: Bookmarks,
+    // Question tabs checked for new answers, with how their question was when last checked.
+    watched_tabs: Rc<RefCell<HashMap<adw::TabPage, stackexchange::QuestionActivity>>>,
+    // Timers of the question tabs that are refreshed automatically.
+    auto_refresh_tabs: Rc<RefCell<HashMap<adw::TabPage, gtk::glib::SourceId>>>,
+    // Questions the tabs showed before following links in them.
+    tab_histories: HashMap<adw::TabPage, TabHistory>,
+    // Minutes since the loaded question tabs were last selected.
+    idle_tabs: HashMap<adw::TabPage, u32>,
+    // Tabs of the main window, most recently selected first.
+    tab_recency: Vec<adw::TabPage>,
+    // Position in `tab_recency` while going through it with Ctrl+Tab.
+    tab_cycle: Option<usize>,
+    // Newest questions of every subscription, from the last time it was checked.
+    feed_entries: Vec<(Subscription, FeedEntry)>,
 }
 
 pub struct AppWidgets {
     tab_view: adw::TabView,
+    tab_overview: adw::TabOverview,
+    tab_bar: adw::TabBar,
+    reading_mode_button: gtk::Button,
+    toast_overlay: adw::ToastOverlay,
     header: adw::HeaderBar,
+    progress_bar: gtk::ProgressBar,
     search_button: gtk::ToggleButton,
     search_entry: gtk::SearchEntry,
+    suggestions_popover: gtk::Popover,
+    inbox_button: gtk::MenuButton,
+    bookmark_button: gtk::ToggleButton,
+    browser_button: gtk::Button,
+    copy_link_button: gtk::Button,
+    back_button: gtk::Button,
+    forward_button: gtk::Button,
+    account_button: gtk::MenuButton,
+    account_avatar: adw::Avatar,
     title_widget: adw::WindowTitle,
+    quota_label: gtk::Label,
+    closed_tabs_menu: gtk::gio::Menu,
+    tab_cycle_popover: gtk::Popover,
+    split_view: gtk::Paned,
+    sidebar: adw::Flap,
+    sidebar_stack: adw::ViewStack,
+    // Listed by the command palette.
+    main_menu: gtk::gio::Menu,
+    tab_menu: gtk::gio::Menu,
+    // Installed in the windows tabs are moved to too.
+    tab_actions: gtk::gio::SimpleActionGroup,
 }
 
 #[relm4::async_trait::async_trait(?Send)]
@@ -76,8 +534,40 @@ impl AsyncComponent for AppModel {
         root: Self::Root,
         sender: AsyncComponentSender<Self>,
     ) -> AsyncComponentParts<Self> {
+        let settings = Settings::load();
+
+        root.set_default_size(settings.window_width, settings.window_height);
+        if settings.window_maximized {
+            root.maximize();
+        }
+
         let model = AppModel {
-            stackexchange_client: stackexchange::StackExchange::new(),
+            stackexchange_client: stackexchange::StackExchange::shared(),
+            fixtures: FixtureProvider::from_env().map(Rc::new),
+            home_site: Rc::new(RefCell::new(settings.default_site.clone())),
+            restore_session: Rc::new(Cell::new(settings.restore_session)),
+            settings,
+            content_css: gtk::CssProvider::new(),
+            quota: None,
+            narrow: false,
+            reading_mode: false,
+            private: Rc::new(Cell::new(false)),
+            pending_requests: Rc::new(RefCell::new(HashMap::new())),
+            question_tabs: Rc::new(RefCell::new(HashMap::new())),
+            unloaded_tabs: Rc::new(RefCell::new(HashMap::new())),
+            tab_windows: Rc::new(RefCell::new(Vec::new())),
+            active_tab_view: Rc::new(RefCell::new(None)),
+            closed_tabs: Vec::new(),
+            recovery_tabs: Vec::new(),
+            history: RefCell::new(History::load()),
+            bookmarks: Bookmarks::load(),
+            watched_tabs: Rc::new(RefCell::new(HashMap::new())),
+            auto_refresh_tabs: Rc::new(RefCell::new(HashMap::new())),
+            tab_histories: HashMap::new(),
+            idle_tabs: HashMap::new(),
+            tab_recency: Vec::new(),
+            tab_cycle: None,
+            feed_entries: Vec::new(),
         };
 
         // Load CSS
@@ -89,7 +579,14 @@ impl AsyncComponent for AppModel {
                 &provider,
                 gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
             );
+            gtk::style_context_add_provider_for_display(
+                &display,
+                &model.content_css,
+                gtk::STYLE_PROVIDER_PRIORITY_APPLICATION + 1,
+            );
         }
+        load_content_css(&model.content_css, &model.settings);
+        apply_theme(model.settings.theme);
 
         // Load icons
         relm4_icons::initialize_icons();
@@ -105,7 +602,7 @@ impl AsyncComponent for AppModel {
         // Create header bar
         let title_widget = adw::WindowTitle::builder()
             .title(APP_NAME)
-            .subtitle("Your 1000 tabs are in safe hands")
+            .subtitle(APP_SUBTITLE)
             .build();
 
         let header = adw::HeaderBar::builder()
@@ -113,16 +610,172 @@ impl AsyncComponent for AppModel {
             .show_end_title_buttons(true)
             .build();
 
-        main_layout.append(&header);
+        // Pulses at the bottom of the header while any question is loading.
+        let progress_bar = gtk::ProgressBar::builder()
+            .css_classes(["osd"])
+            .valign(gtk::Align::End)
+            .visible(false)
+            .build();
+        let header_overlay = gtk::Overlay::builder().child(&header).build();
+        header_overlay.add_overlay(&progress_bar);
+
+        main_layout.append(&header_overlay);
 
         // Create menu actions
         // TODO: Create action to show GtkShortcutsWindow.
         relm4::new_action_group!(MenuActionGroup, "menu");
+        relm4::new_stateless_action!(HomeAction, MenuActionGroup, "home");
+        relm4::new_stateless_action!(OpenClipboardAction, MenuActionGroup, "open_clipboard");
+        relm4::new_stateless_action!(LoginAction, MenuActionGroup, "login");
+        relm4::new_stateless_action!(LogoutAction, MenuActionGroup, "logout");
+        relm4::new_stateless_action!(FavoritesAction, MenuActionGroup, "favorites");
+        relm4::new_stateless_action!(HistoryAction, MenuActionGroup, "history");
+        relm4::new_stateless_action!(BookmarksAction, MenuActionGroup, "bookmarks");
+        relm4::new_stateless_action!(SubscriptionsAction, MenuActionGroup, "subscriptions");
+        relm4::new_stateless_action!(ReadingModeAction, MenuActionGroup, "reading_mode");
+        relm4::new_stateless_action!(CommandPaletteAction, MenuActionGroup, "command_palette");
+        relm4::new_stateless_action!(SidebarAction, MenuActionGroup, "sidebar");
+        relm4::new_stateful_action!(
+            MeasureCompressionAction,
+            MenuActionGroup,
+            "measure_compression",
+            (),
+            bool
+        );
+        relm4::new_stateful_action!(OfflineAction, MenuActionGroup, "offline", (), bool);
+        relm4::new_stateful_action!(PrivateAction, MenuActionGroup, "private", (), bool);
+        relm4::new_stateless_action!(PreferencesAction, MenuActionGroup, "preferences");
         relm4::new_stateless_action!(AboutAction, MenuActionGroup, "about");
         relm4::new_stateless_action!(QuitAction, MenuActionGroup, "quit");
         {
             let mut group = relm4::actions::RelmActionGroup::<MenuActionGroup>::new();
 
+            let home_action: relm4::actions::RelmAction<HomeAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::OpenHome);
+                    }),
+                );
+            group.add_action(home_action);
+
+            let open_clipboard_action: relm4::actions::RelmAction<OpenClipboardAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::PasteLink);
+                    }),
+                );
+            group.add_action(open_clipboard_action);
+
+            let login_action: relm4::actions::RelmAction<LoginAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::Login);
+                    }),
+                );
+            group.add_action(login_action);
+
+            let logout_action: relm4::actions::RelmAction<LogoutAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::Logout);
+                    }),
+                );
+            group.add_action(logout_action);
+
+            let favorites_action: relm4::actions::RelmAction<FavoritesAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::OpenFavorites);
+                    }),
+                );
+            group.add_action(favorites_action);
+
+            let history_action: relm4::actions::RelmAction<HistoryAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::OpenHistory);
+                    }),
+                );
+            group.add_action(history_action);
+
+            let bookmarks_action: relm4::actions::RelmAction<BookmarksAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::OpenBookmarks);
+                    }),
+                );
+            group.add_action(bookmarks_action);
+
+            let subscriptions_action: relm4::actions::RelmAction<SubscriptionsAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::OpenSubscriptions);
+                    }),
+                );
+            group.add_action(subscriptions_action);
+
+            let reading_mode_action: relm4::actions::RelmAction<ReadingModeAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ToggleReadingMode);
+                    }),
+                );
+            group.add_action(reading_mode_action);
+
+            let command_palette_action: relm4::actions::RelmAction<CommandPaletteAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ShowCommandPalette);
+                    }),
+                );
+            group.add_action(command_palette_action);
+
+            let sidebar_action: relm4::actions::RelmAction<SidebarAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ToggleSidebar);
+                    }),
+                );
+            group.add_action(sidebar_action);
+
+            let measure_compression_action: relm4::actions::RelmAction<MeasureCompressionAction> =
+                relm4::actions::RelmAction::new_stateful(
+                    &model.stackexchange_client.measure_compression(),
+                    gtk::glib::clone!(@strong sender => move |_, state: &mut bool| {
+                        *state = !*state;
+                        sender.input(AppInput::SetMeasureCompression(*state));
+                    }),
+                );
+            group.add_action(measure_compression_action);
+
+            let offline_action: relm4::actions::RelmAction<OfflineAction> =
+                relm4::actions::RelmAction::new_stateful(
+                    &model.stackexchange_client.is_offline(),
+                    gtk::glib::clone!(@strong sender => move |_, state: &mut bool| {
+                        *state = !*state;
+                        sender.input(AppInput::SetOffline(*state));
+                    }),
+                );
+            group.add_action(offline_action);
+
+            let private_action: relm4::actions::RelmAction<PrivateAction> =
+                relm4::actions::RelmAction::new_stateful(
+                    &model.private.get(),
+                    gtk::glib::clone!(@strong sender => move |_, state: &mut bool| {
+                        *state = !*state;
+                        sender.input(AppInput::SetPrivate(*state));
+                    }),
+                );
+            group.add_action(private_action);
+
+            let preferences_action: relm4::actions::RelmAction<PreferencesAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ShowPreferences);
+                    }),
+                );
+            group.add_action(preferences_action);
+
             let about_action: relm4::actions::RelmAction<AboutAction> =
                 relm4::actions::RelmAction::new_stateless(
                     gtk::glib::clone!(@strong sender => move |_| {
@@ -131,10 +784,11 @@ impl AsyncComponent for AppModel {
                 );
             group.add_action(about_action);
 
+            // Closing the window asks first if tabs would be lost.
             let quit_action: relm4::actions::RelmAction<QuitAction> =
                 relm4::actions::RelmAction::new_stateless(
-                    gtk::glib::clone!(@strong sender => move |_| {
-                        sender.input(AppInput::Quit);
+                    gtk::glib::clone!(@strong root => move |_| {
+                        root.close();
                     }),
                 );
             group.add_action(quit_action);
@@ -144,12 +798,44 @@ impl AsyncComponent for AppModel {
 
         relm4::menu! {
             main_menu: {
+                "Home" => HomeAction,
+                "Open from Clipboard" => OpenClipboardAction,
+                "Log In" => LoginAction,
+                "Log Out" => LogoutAction,
+                "Favorites" => FavoritesAction,
+                "History" => HistoryAction,
+                "Bookmarks" => BookmarksAction,
+                "Subscriptions" => SubscriptionsAction,
+                "Offline Mode" => OfflineAction,
+                "Private Mode" => PrivateAction,
+                "Measure Compression" => MeasureCompressionAction,
+                "Reading Mode" => ReadingModeAction,
+                "Preferences" => PreferencesAction,
                 "About" => AboutAction,
                 "Quit" => QuitAction
             }
         }
 
         relm4::main_application().set_accelerators_for_action::<QuitAction>(&["<Control>q"]);
+        relm4::main_application().set_accelerators_for_action::<HomeAction>(&["<Alt>Home"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<OpenClipboardAction>(&["<Control><Shift>v"]);
+        relm4::main_application().set_accelerators_for_action::<HistoryAction>(&["<Control>h"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<PreferencesAction>(&["<Control>comma"]);
+        relm4::main_application().set_accelerators_for_action::<ReadingModeAction>(&["F11"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<CommandPaletteAction>(&["<Control><Shift>p"]);
+        relm4::main_application().set_accelerators_for_action::<SidebarAction>(&["F9"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<PrivateAction>(&["<Control><Shift>n"]);
+
+        // Synced with the sidebar once it's created.
+        let sidebar_button = gtk::ToggleButton::builder()
+            .icon_name("sidebar-show-symbolic")
+            .tooltip_text("Sidebar")
+            .build();
+        header.pack_start(&sidebar_button);
 
         // Create hamburger menu
         let menu_button = gtk::MenuButton::builder()
@@ -172,24 +858,130 @@ impl AsyncComponent for AppModel {
 
         let search_entry = gtk::SearchEntry::builder()
             // TODO: Make icon clickable to select a stackexchange site to search in.
-            .placeholder_text("Enter a search term or question id")
+            .placeholder_text("Enter a search term, question id or link")
+            .tooltip_text("Narrow searches with [tag], user:id, isaccepted:yes, score:5 and \"exact phrases\", \
+                 and pick a site with a prefix like au:wifi driver or so:12345")
+            // Wait for the user to stop typing before asking for suggestions.
+            .search_delay(400)
+            .build();
+
+        // Suggested questions, shown while typing
+        let suggestions_popover = gtk::Popover::builder()
+            .autohide(false)
+            .has_arrow(false)
+            .halign(gtk::Align::Start)
             .build();
+        suggestions_popover.set_parent(&search_entry);
+
+        let search_focus = gtk::EventControllerFocus::new();
+        search_focus.connect_enter(gtk::glib::clone!(@strong sender => move |_| {
+            sender.input(AppInput::ShowSearchHistory);
+        }));
+        search_entry.add_controller(search_focus);
 
-        search_entry.connect_activate(gtk::glib::clone!(@strong sender => move |entry| {
-            let search_term = entry.text();
-            // TODO: Change how search_term is parsed to support urls and terms at the same time.
-            // TODO: Connect it to search api
-            // TODO: Don't accept uris.
-            // TODO: Support all stackexchange sites: https://api.stackexchange.com/docs/sites
-            sender.input(AppInput::RequestPagesByUri(format!("stackexchange://stackoverflow/{search_term}")));
-            entry.delete_text(0, search_term.len() as i32);
+        search_entry.connect_search_changed(gtk::glib::clone!(@strong sender => move |entry| {
+            sender.input(AppInput::SuggestQuestions(entry.text().to_string()));
         }));
 
+        // Up and Down keys move through the suggestions without leaving the entry.
+        let search_keys = gtk::EventControllerKey::new();
+        search_keys.set_propagation_phase(gtk::PropagationPhase::Capture);
+        search_keys.connect_key_pressed(
+            gtk::glib::clone!(@strong suggestions_popover => move |_, key, _, _| {
+                let step = match key {
+                    gtk::gdk::Key::Down => 1,
+                    gtk::gdk::Key::Up => -1,
+                    _ => return gtk::Inhibit(false),
+                };
+
+                let Some(suggestions) = suggestions_popover
+                    .child()
+                    .and_downcast::<gtk::ListBox>()
+                    .filter(|_| suggestions_popover.is_visible())
+                else {
+                    return gtk::Inhibit(false);
+                };
+
+                let index = suggestions
+                    .selected_row()
+                    .map_or(if step > 0 { -1 } else { 0 }, |row| row.index());
+                if let Some(row) = suggestions.row_at_index(index + step) {
+                    suggestions.select_row(Some(&row));
+                }
+
+                gtk::Inhibit(true)
+            }),
+        );
+        search_entry.add_controller(search_keys);
+
+        search_entry.connect_stop_search(
+            gtk::glib::clone!(@strong suggestions_popover => move |_entry| {
+                suggestions_popover.popdown();
+            }),
+        );
+
+        search_entry.connect_activate(
+            gtk::glib::clone!(@strong sender, @strong suggestions_popover => move |entry| {
+                // Open the selected suggestion instead of searching.
+                if let Some(row) = suggestions_popover
+                    .child()
+                    .and_downcast::<gtk::ListBox>()
+                    .filter(|_| suggestions_popover.is_visible())
+                    .and_then(|suggestions| suggestions.selected_row())
+                {
+                    row.emit_activate();
+                    return;
+                }
+
+                suggestions_popover.popdown();
+                let search_term = entry.text();
+                sender.input(AppInput::SubmitSearchTerm(search_term.trim().to_owned()));
+                entry.delete_text(0, search_term.len() as i32);
+            }),
+        );
+
         // Create tab actions
         relm4::new_action_group!(TabActionGroup, "tab");
         relm4::new_stateless_action!(PinTabAction, TabActionGroup, "toggle_pin");
         relm4::new_stateless_action!(CloseTabAction, TabActionGroup, "close");
-        {
+        relm4::new_stateless_action!(CloseOtherTabsAction, TabActionGroup, "close_others");
+        relm4::new_stateless_action!(CloseTabsToRightAction, TabActionGroup, "close_to_right");
+        relm4::new_stateless_action!(OverviewTabAction, TabActionGroup, "overview");
+        relm4::new_stateless_action!(SwitcherTabAction, TabActionGroup, "switcher");
+        relm4::new_stateless_action!(NewTabAction, TabActionGroup, "new");
+        relm4::new_stateless_action!(NextTabAction, TabActionGroup, "next");
+        relm4::new_stateless_action!(PreviousTabAction, TabActionGroup, "previous");
+        relm4::new_stateless_action!(RecentTabAction, TabActionGroup, "recent");
+        relm4::new_stateless_action!(PreviousRecentTabAction, TabActionGroup, "previous_recent");
+        relm4::new_stateful_action!(SelectTabAction, TabActionGroup, "select", u32, ());
+        relm4::new_stateless_action!(MoveTabRightAction, TabActionGroup, "move_right");
+        relm4::new_stateless_action!(MoveTabLeftAction, TabActionGroup, "move_left");
+        relm4::new_stateless_action!(FocusSearchAction, TabActionGroup, "search");
+        relm4::new_stateless_action!(FindAction, TabActionGroup, "find");
+        relm4::new_stateless_action!(ZoomInAction, TabActionGroup, "zoom_in");
+        relm4::new_stateless_action!(ZoomOutAction, TabActionGroup, "zoom_out");
+        relm4::new_stateless_action!(ResetZoomAction, TabActionGroup, "reset_zoom");
+        relm4::new_stateless_action!(BookmarkTabAction, TabActionGroup, "bookmark");
+        relm4::new_stateless_action!(CopyLinkTabAction, TabActionGroup, "copy_link");
+        relm4::new_stateless_action!(DuplicateTabAction, TabActionGroup, "duplicate");
+        relm4::new_stateless_action!(OpenInBrowserTabAction, TabActionGroup, "open_in_browser");
+        relm4::new_stateless_action!(SiteHomeTabAction, TabActionGroup, "site_home");
+        relm4::new_stateless_action!(WatchTabAction, TabActionGroup, "watch");
+        relm4::new_stateless_action!(RefreshTabAction, TabActionGroup, "refresh");
+        relm4::new_stateless_action!(SplitTabAction, TabActionGroup, "split");
+        relm4::new_stateless_action!(BackTabAction, TabActionGroup, "back");
+        relm4::new_stateless_action!(ForwardTabAction, TabActionGroup, "forward");
+        relm4::new_stateful_action!(
+            AutoRefreshTabAction,
+            TabActionGroup,
+            "auto_refresh",
+            u32,
+            ()
+        );
+        relm4::new_stateless_action!(MoveTabAction, TabActionGroup, "move_to_new_window");
+        relm4::new_stateful_action!(ReopenTabAction, TabActionGroup, "reopen", u32, ());
+        relm4::new_stateless_action!(ReopenLastTabAction, TabActionGroup, "reopen_last");
+        let tab_actions = {
             let mut group = relm4::actions::RelmActionGroup::<TabActionGroup>::new();
 
             let tab_pin_action: relm4::actions::RelmAction<PinTabAction> =
@@ -208,228 +1000,4499 @@ impl AsyncComponent for AppModel {
                 );
             group.add_action(close_tab_action);
 
-            root.insert_action_group("tab", Some(&group.into_action_group()))
-        }
+            let close_other_tabs_action: relm4::actions::RelmAction<CloseOtherTabsAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::CloseOtherTabs);
+                    }),
+                );
+            group.add_action(close_other_tabs_action);
 
-        relm4::menu! {
-            tab_menu: {
-                "Pin/Unpin" => PinTabAction,
-                "Close" => CloseTabAction,
-            }
-        }
+            let close_tabs_to_right_action: relm4::actions::RelmAction<CloseTabsToRightAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::CloseTabsToRight);
+                    }),
+                );
+            group.add_action(close_tabs_to_right_action);
 
-        relm4::main_application().set_accelerators_for_action::<CloseTabAction>(&["<Control>w"]);
+            let overview_tab_action: relm4::actions::RelmAction<OverviewTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ToggleTabOverview);
+                    }),
+                );
+            group.add_action(overview_tab_action);
 
-        // Create tab bar
-        let tab_bar = adw::TabBar::builder().css_classes(["inline"]).build();
-        main_layout.append(&tab_bar);
+            let switcher_tab_action: relm4::actions::RelmAction<SwitcherTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ShowTabSwitcher);
+                    }),
+                );
+            group.add_action(switcher_tab_action);
 
-        // Create tab view
-        let tab_view = adw::TabView::builder().menu_model(&tab_menu).build();
-        main_layout.append(&tab_view);
+            let new_tab_action: relm4::actions::RelmAction<NewTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::NewTab);
+                    }),
+                );
+            group.add_action(new_tab_action);
 
-        tab_bar.set_view(Some(&tab_view));
+            let next_tab_action: relm4::actions::RelmAction<NextTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::CycleTabs(true));
+                    }),
+                );
+            group.add_action(next_tab_action);
 
-        tab_view.connect_setup_menu(|view, page| {
-            if let Some(page) = page {
-                view.set_selected_page(page);
-            }
-        });
+            let previous_tab_action: relm4::actions::RelmAction<PreviousTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::CycleTabs(false));
+                    }),
+                );
+            group.add_action(previous_tab_action);
 
-        // Create tab button in the header
-        let tab_button = adw::TabButton::builder()
-            .view(&tab_view)
-            .action_name("overview.open")
-            .build();
-        header.pack_end(&tab_button);
+            let recent_tab_action: relm4::actions::RelmAction<RecentTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::CycleRecentTabs(true));
+                    }),
+                );
+            group.add_action(recent_tab_action);
 
-        // Create tabs overview
-        // FIX: Whene the last tab is closed, close the overview.
-        let tab_overview = adw::TabOverview::builder()
-            .view(&tab_view)
-            // TODO: Implement new tab
-            // .enable_new_tab(true)
-            .child(&main_layout)
-            .build();
-        root.set_content(Some(&tab_overview));
+            let previous_recent_tab_action: relm4::actions::RelmAction<PreviousRecentTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::CycleRecentTabs(false));
+                    }),
+                );
+            group.add_action(previous_recent_tab_action);
 
-        let widgets = AppWidgets {
-            tab_view,
-            header,
-            search_button,
-            search_entry,
-            title_widget,
-        };
+            let select_tab_action: relm4::actions::RelmAction<SelectTabAction> =
+                relm4::actions::RelmAction::new_with_target_value(
+                    gtk::glib::clone!(@strong sender => move |_, position| {
+                        sender.input(AppInput::SelectTab(position));
+                    }),
+                );
+            group.add_action(select_tab_action);
 
-        AsyncComponentParts { model, widgets }
-    }
+            let move_tab_right_action: relm4::actions::RelmAction<MoveTabRightAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::MoveTab(true));
+                    }),
+                );
+            group.add_action(move_tab_right_action);
 
-    async fn update_with_view(
-        &mut self,
-        widgets: &mut Self::Widgets,
-        message: Self::Input,
-        sender: AsyncComponentSender<Self>,
-        _root: &Self::Root,
-    ) {
-        match message {
-            AppInput::RequestPagesByUri(uri) => {
-                // TODO: Handle request errors.
-                let questions = self
-                    .stackexchange_client
-                    .get_questions_from_uri(&uri)
-                    .await
-                    .unwrap();
+            let move_tab_left_action: relm4::actions::RelmAction<MoveTabLeftAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::MoveTab(false));
+                    }),
+                );
+            group.add_action(move_tab_left_action);
 
-                for question in questions {
-                    let question_box = componant_builders::st_question(&question);
+            let focus_search_action: relm4::actions::RelmAction<FocusSearchAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::FocusSearch);
+                    }),
+                );
+            group.add_action(focus_search_action);
 
-                    let tab_page = widgets.tab_view.append(
-                        &gtk::ScrolledWindow::builder()
-                            .child(&question_box)
-                            .vexpand(true)
-                            .hexpand(true)
-                            .build(),
-                    );
+            let find_action: relm4::actions::RelmAction<FindAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ShowFindBar);
+                    }),
+                );
+            group.add_action(find_action);
 
-                    // TODO: Pass question tags as keywords.
-                    // tab_page.set_keyword(keyword);
+            let zoom_in_action: relm4::actions::RelmAction<ZoomInAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ZoomTab(1));
+                    }),
+                );
+            group.add_action(zoom_in_action);
 
-                    tab_page.set_title(&question.title);
-                }
-            }
-            AppInput::ToggleSearchEntry => {
-                if widgets.search_button.is_active() {
-                    widgets.header.set_title_widget(Some(&widgets.search_entry));
-                    widgets.search_entry.show();
-                    widgets.search_entry.grab_focus();
-                } else {
-                    widgets.search_entry.hide();
-                    widgets.header.set_title_widget(Some(&widgets.title_widget));
+            let zoom_out_action: relm4::actions::RelmAction<ZoomOutAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ZoomTab(-1));
+                    }),
+                );
+            group.add_action(zoom_out_action);
+
+            let reset_zoom_action: relm4::actions::RelmAction<ResetZoomAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ZoomTab(0));
+                    }),
+                );
+            group.add_action(reset_zoom_action);
+
+            let bookmark_tab_action: relm4::actions::RelmAction<BookmarkTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ToggleBookmark);
+                    }),
+                );
+            group.add_action(bookmark_tab_action);
+
+            let open_in_browser_tab_action: relm4::actions::RelmAction<OpenInBrowserTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::OpenInBrowser);
+                    }),
+                );
+            group.add_action(open_in_browser_tab_action);
+
+            let site_home_tab_action: relm4::actions::RelmAction<SiteHomeTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::OpenSiteHome);
+                    }),
+                );
+            group.add_action(site_home_tab_action);
+
+            let watch_tab_action: relm4::actions::RelmAction<WatchTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ToggleWatch);
+                    }),
+                );
+            group.add_action(watch_tab_action);
+
+            let refresh_tab_action: relm4::actions::RelmAction<RefreshTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::RefreshSelectedTab);
+                    }),
+                );
+            group.add_action(refresh_tab_action);
+
+            let split_tab_action: relm4::actions::RelmAction<SplitTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::SplitTab);
+                    }),
+                );
+            group.add_action(split_tab_action);
+
+            let back_tab_action: relm4::actions::RelmAction<BackTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::NavigateBack);
+                    }),
+                );
+            group.add_action(back_tab_action);
+
+            let forward_tab_action: relm4::actions::RelmAction<ForwardTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::NavigateForward);
+                    }),
+                );
+            group.add_action(forward_tab_action);
+
+            let auto_refresh_tab_action: relm4::actions::RelmAction<AutoRefreshTabAction> =
+                relm4::actions::RelmAction::new_with_target_value(
+                    gtk::glib::clone!(@strong sender => move |_, minutes| {
+                        sender.input(AppInput::SetAutoRefresh(minutes));
+                    }),
+                );
+            group.add_action(auto_refresh_tab_action);
+
+            let copy_link_tab_action: relm4::actions::RelmAction<CopyLinkTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::CopyLink);
+                    }),
+                );
+            group.add_action(copy_link_tab_action);
+
+            let duplicate_tab_action: relm4::actions::RelmAction<DuplicateTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::DuplicateTab);
+                    }),
+                );
+            group.add_action(duplicate_tab_action);
+
+            let move_tab_action: relm4::actions::RelmAction<MoveTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::MoveTabToNewWindow);
+                    }),
+                );
+            group.add_action(move_tab_action);
+
+            let reopen_tab_action: relm4::actions::RelmAction<ReopenTabAction> =
+                relm4::actions::RelmAction::new_with_target_value(
+                    gtk::glib::clone!(@strong sender => move |_, index| {
+                        sender.input(AppInput::ReopenClosedTab(index));
+                    }),
+                );
+            group.add_action(reopen_tab_action);
+
+            let reopen_last_tab_action: relm4::actions::RelmAction<ReopenLastTabAction> =
+                relm4::actions::RelmAction::new_stateless(
+                    gtk::glib::clone!(@strong sender => move |_| {
+                        sender.input(AppInput::ReopenClosedTab(0));
+                    }),
+                );
+            group.add_action(reopen_last_tab_action);
+
+            let tab_actions = group.into_action_group();
+            root.insert_action_group("tab", Some(&tab_actions));
+            tab_actions
+        };
+
+        relm4::menu! {
+            tab_menu: {
+                "Refresh" => RefreshTabAction,
+                "Auto Refresh" {
+                    "Every 5 Minutes" => AutoRefreshTabAction(5),
+                    "Every 15 Minutes" => AutoRefreshTabAction(15),
+                    "Every Hour" => AutoRefreshTabAction(60),
+                    "Never" => AutoRefreshTabAction(0),
+                },
+                "Pin/Unpin" => PinTabAction,
+                "Duplicate" => DuplicateTabAction,
+                "Show in Split View" => SplitTabAction,
+                "Move to New Window" => MoveTabAction,
+                "Bookmark/Remove Bookmark" => BookmarkTabAction,
+                "Watch/Stop Watching for New Answers" => WatchTabAction,
+                "Copy Link" => CopyLinkTabAction,
+                "Open in Web Browser" => OpenInBrowserTabAction,
+                "Open Site's Hot Questions" => SiteHomeTabAction,
+                "Close" => CloseTabAction,
+                "Close Other Tabs" => CloseOtherTabsAction,
+                "Close Tabs to the Right" => CloseTabsToRightAction,
+            }
+        }
+
+        relm4::main_application().set_accelerators_for_action::<CloseTabAction>(&["<Control>w"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<OverviewTabAction>(&["<Control><Shift>o"]);
+        relm4::main_application().set_accelerators_for_action::<SwitcherTabAction>(&["<Control>k"]);
+        relm4::main_application().set_accelerators_for_action::<NewTabAction>(&["<Control>t"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<ReopenLastTabAction>(&["<Control><Shift>t"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<NextTabAction>(&["<Control>Page_Down"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<PreviousTabAction>(&["<Control>Page_Up"]);
+        relm4::main_application().set_accelerators_for_action::<RecentTabAction>(&["<Control>Tab"]);
+        relm4::main_application().set_accelerators_for_action::<PreviousRecentTabAction>(&[
+            "<Control><Shift>Tab",
+            "<Control><Shift>ISO_Left_Tab",
+        ]);
+        relm4::main_application()
+            .set_accelerators_for_action::<MoveTabRightAction>(&["<Control><Shift>Page_Down"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<MoveTabLeftAction>(&["<Control><Shift>Page_Up"]);
+        relm4::main_application().set_accelerators_for_action::<PinTabAction>(&["<Control>p"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<RefreshTabAction>(&["F5", "<Control>r"]);
+        relm4::main_application().set_accelerators_for_action::<BackTabAction>(&["<Alt>Left"]);
+        relm4::main_application().set_accelerators_for_action::<ForwardTabAction>(&["<Alt>Right"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<DuplicateTabAction>(&["<Control><Shift>d"]);
+        relm4::main_application().set_accelerators_for_action::<BookmarkTabAction>(&["<Control>d"]);
+        relm4::main_application()
+            .set_accelerators_for_action::<CopyLinkTabAction>(&["<Control><Shift>c"]);
+        relm4::main_application().set_accelerators_for_action::<FocusSearchAction>(&["<Control>l"]);
+        relm4::main_application().set_accelerators_for_action::<FindAction>(&["<Control>f"]);
+        relm4::main_application().set_accelerators_for_action::<ZoomInAction>(&[
+            "<Control>plus",
+            "<Control>equal",
+            "<Control>KP_Add",
+        ]);
+        relm4::main_application().set_accelerators_for_action::<ZoomOutAction>(&[
+            "<Control>minus",
+            "<Control>KP_Subtract",
+        ]);
+        relm4::main_application()
+            .set_accelerators_for_action::<ResetZoomAction>(&["<Control>0", "<Control>KP_0"]);
+        for position in 1..=9 {
+            relm4::main_application().set_accels_for_action(
+                &format!(
+                    "{}(uint32 {position})",
+                    <SelectTabAction as relm4::actions::ActionName>::action_name()
+                ),
+                &[&format!("<Alt>{position}")],
+            );
+        }
+
+        // Create tab bar
+        let tab_bar = adw::TabBar::builder().css_classes(["inline"]).build();
+
+        // Breakpoints need libadwaita 1.4, until then a squeezer tells when the window is narrow:
+        // it only shows its first child when there is room for it.
+        let width_squeezer = adw::Squeezer::new();
+        width_squeezer.add(&gtk::Box::builder().width_request(NARROW_WIDTH).build());
+        let narrow_child = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        width_squeezer.add(&narrow_child);
+
+        width_squeezer.connect_visible_child_notify(
+            gtk::glib::clone!(@strong sender => move |squeezer| {
+                sender.input(AppInput::SetNarrow(
+                    squeezer.visible_child().as_ref() == Some(narrow_child.upcast_ref()),
+                ));
+            }),
+        );
+
+        let tab_bar_layout = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        tab_bar_layout.append(&width_squeezer);
+        tab_bar_layout.append(&tab_bar);
+        main_layout.append(&tab_bar_layout);
+
+        // Create tab view
+        let tab_view = adw::TabView::builder().menu_model(&tab_menu).build();
+        // Replaced by the tab actions above, so they don't run twice.
+        tab_view.remove_shortcuts(
+            adw::TabViewShortcuts::CONTROL_TAB
+                | adw::TabViewShortcuts::CONTROL_SHIFT_TAB
+                | adw::TabViewShortcuts::CONTROL_PAGE_UP
+                | adw::TabViewShortcuts::CONTROL_PAGE_DOWN
+                | adw::TabViewShortcuts::CONTROL_SHIFT_PAGE_UP
+                | adw::TabViewShortcuts::CONTROL_SHIFT_PAGE_DOWN
+                | adw::TabViewShortcuts::ALT_DIGITS,
+        );
+        let tabs_layout = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        tabs_layout.append(&tab_view);
+
+        // Shown instead of the tab view once every tab is closed.
+        let empty_page = componant_builders::st_empty_page(sender.input_sender());
+        empty_page.set_visible(false);
+        tabs_layout.append(&empty_page);
+
+        // A question can be shown on the right of the tabs, with a movable divider.
+        let split_view = gtk::Paned::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .start_child(&tabs_layout)
+            .shrink_start_child(false)
+            .shrink_end_child(false)
+            .vexpand(true)
+            .build();
+        main_layout.append(&split_view);
+        tab_view.connect_n_pages_notify(gtk::glib::clone!(@strong empty_page => move |view| {
+            let empty = view.n_pages() == 0;
+            view.set_visible(!empty);
+            empty_page.set_visible(empty);
+        }));
+
+        tab_bar.set_view(Some(&tab_view));
+
+        // Dropped links open in new tabs, wherever they're dropped.
+        let drop_types = [gtk::gdk::FileList::static_type(), String::static_type()];
+        tab_bar.setup_extra_drop_target(gtk::gdk::DragAction::COPY, &drop_types);
+        tab_bar.connect_extra_drag_drop(gtk::glib::clone!(@strong sender => move |_, _, value| {
+            open_dropped_links(value, sender.input_sender())
+        }));
+        let drop_target =
+            gtk::DropTarget::new(gtk::glib::Type::INVALID, gtk::gdk::DragAction::COPY);
+        drop_target.set_types(&drop_types);
+        drop_target.connect_drop(gtk::glib::clone!(@strong sender => move |_, value, _, _| {
+            open_dropped_links(value, sender.input_sender())
+        }));
+        root.add_controller(drop_target);
+
+        // Going through the recently used tabs ends once Ctrl is released.
+        let cycle_keys = gtk::EventControllerKey::builder()
+            .propagation_phase(gtk::PropagationPhase::Capture)
+            .build();
+        cycle_keys.connect_key_released(gtk::glib::clone!(@strong sender => move |_, key, _, _| {
+            if matches!(key, gtk::gdk::Key::Control_L | gtk::gdk::Key::Control_R) {
+                sender.input(AppInput::EndTabCycle);
+            }
+        }));
+        root.add_controller(cycle_keys);
+        // It's never released here when the window loses the focus first.
+        root.connect_is_active_notify(gtk::glib::clone!(
+            @strong sender,
+            @strong model.active_tab_view as active_tab_view => move |window| {
+            if window.is_active() {
+                active_tab_view.borrow_mut().take();
+            } else {
+                sender.input(AppInput::EndTabCycle);
+            }
+        }));
+
+        tab_view.connect_close_page(gtk::glib::clone!(
+            @strong sender,
+            @strong model.pending_requests as pending_requests,
+            @strong model.question_tabs as question_tabs,
+            @strong model.unloaded_tabs as unloaded_tabs,
+            @strong model.watched_tabs as watched_tabs,
+            @strong model.auto_refresh_tabs as auto_refresh_tabs => move |view, page| {
+            // Like a middle click on it in the tab bar, it's closed once it's unpinned.
+            if page.is_pinned() {
+                view.close_page_finish(page, false);
+                sender.input(AppInput::ConfirmCloseTab(page.downgrade().into()));
+                return true;
+            }
+
+            if let Some(handle) = pending_requests.borrow_mut().remove(page) {
+                handle.abort();
+            }
+            remember_closed_tab(
+                page,
+                &question_tabs.borrow(),
+                &unloaded_tabs.borrow(),
+                sender.input_sender(),
+            );
+            question_tabs.borrow_mut().remove(page);
+            unloaded_tabs.borrow_mut().remove(page);
+            watched_tabs.borrow_mut().remove(page);
+            if let Some(source_id) = auto_refresh_tabs.borrow_mut().remove(page) {
+                source_id.remove();
+            }
+
+            // Let the default handler close it.
+            false
+        }));
+
+        // Tabs dragged out of the window get a window of their own.
+        let tab_window_context = model.tab_window_context(&tab_menu, &tab_actions, &sender);
+        tab_view.connect_create_window(move |_| Some(new_tab_window(&tab_window_context)));
+
+        // Filled by `update_closed_tabs_menu`.
+        let closed_tabs_menu = gtk::gio::Menu::new();
+        update_closed_tabs_menu(&closed_tabs_menu, &[]);
+        main_menu.insert_submenu(6, Some("Recently Closed"), &closed_tabs_menu);
+
+        tab_view.connect_setup_menu(|view, page| {
+            if let Some(page) = page {
+                view.set_selected_page(page);
+            }
+        });
+
+        // Create tab button in the header, it opens and closes the tabs overview
+        let tab_button = adw::TabButton::builder()
+            .view(&tab_view)
+            .action_name("tab.overview")
+            .tooltip_text("Show All Tabs")
+            .build();
+        header.pack_end(&tab_button);
+
+        // Local bookmark of the question in the selected tab
+        let bookmark_button = gtk::ToggleButton::builder()
+            .icon_name("non-starred-symbolic")
+            .tooltip_text("Bookmark")
+            .sensitive(false)
+            .build();
+
+        bookmark_button.connect_clicked(gtk::glib::clone!(@strong sender => move |_| {
+            sender.input(AppInput::ToggleBookmark);
+        }));
+
+        header.pack_end(&bookmark_button);
+
+        let browser_button = gtk::Button::builder()
+            .icon_name("web-browser-symbolic")
+            .tooltip_text("Open in Web Browser")
+            .action_name("tab.open_in_browser")
+            .sensitive(false)
+            .build();
+        header.pack_end(&browser_button);
+
+        let copy_link_button = gtk::Button::builder()
+            .icon_name("edit-copy-symbolic")
+            .tooltip_text("Copy Link")
+            .action_name("tab.copy_link")
+            .sensitive(false)
+            .build();
+        header.pack_end(&copy_link_button);
+
+        // Through the questions opened by following links in the selected tab.
+        let back_button = gtk::Button::builder()
+            .icon_name("go-previous-symbolic")
+            .tooltip_text("Back")
+            .action_name("tab.back")
+            .sensitive(false)
+            .build();
+        header.pack_start(&back_button);
+
+        let forward_button = gtk::Button::builder()
+            .icon_name("go-next-symbolic")
+            .tooltip_text("Forward")
+            .action_name("tab.forward")
+            .sensitive(false)
+            .build();
+        header.pack_start(&forward_button);
+
+        tab_view.connect_selected_page_notify(gtk::glib::clone!(@strong sender => move |view| {
+            if let Some(page) = view.selected_page() {
+                page.set_needs_attention(false);
+                sender.input(AppInput::LoadTab(page.downgrade().into()));
+            }
+            sender.input(AppInput::UpdateQuestionButtons);
+            sender.input(AppInput::UpdateTabRecency);
+        }));
+
+        // Reputation of the logged in user
+        let account_avatar = adw::Avatar::new(24, None, true);
+        let account_button = gtk::MenuButton::builder()
+            .child(&account_avatar)
+            .popover(&gtk::Popover::new())
+            .css_classes(["flat", "circular"])
+            .visible(false)
+            .build();
+        header.pack_end(&account_button);
+
+        // Unread inbox items, only shown while logged in
+        let inbox_popover = gtk::Popover::new();
+        let inbox_button = gtk::MenuButton::builder()
+            .icon_name("mail-read-symbolic")
+            .tooltip_text("Inbox")
+            .popover(&inbox_popover)
+            .visible(false)
+            .build();
+        header.pack_end(&inbox_button);
+
+        // Remaining API quota, hidden until the first request
+        let quota_label = gtk::Label::builder()
+            .css_classes(["dim-label", "caption"])
+            .visible(false)
+            .build();
+        header.pack_end(&quota_label);
+
+        // Lists the recently used tabs while Ctrl+Tab goes through them.
+        let tab_cycle_popover = gtk::Popover::builder()
+            .autohide(false)
+            .has_arrow(false)
+            .can_focus(false)
+            .build();
+        tab_cycle_popover.set_parent(&header);
+        // Popovers aren't unparented along with their parent.
+        root.connect_destroy(gtk::glib::clone!(@strong tab_cycle_popover => move |_| {
+            tab_cycle_popover.unparent();
+        }));
+
+        // The only way out of reading mode without the keyboard, floating over the content.
+        let reading_mode_button = gtk::Button::builder()
+            .icon_name("view-restore-symbolic")
+            .tooltip_text("Leave Reading Mode")
+            .css_classes(["osd", "circular"])
+            .halign(gtk::Align::End)
+            .valign(gtk::Align::Start)
+            .margin_top(12)
+            .margin_end(12)
+            .visible(false)
+            .build();
+        reading_mode_button.connect_clicked(gtk::glib::clone!(@strong sender => move |_| {
+            sender.input(AppInput::ToggleReadingMode);
+        }));
+
+        // AdwOverlaySplitView needs libadwaita 1.4, until then a flap holds the sidebar. Its lists
+        // are built when it's revealed.
+        let sidebar_stack = adw::ViewStack::new();
+        for (name, title, icon_name) in SIDEBAR_PAGES {
+            sidebar_stack.add_titled_with_icon(
+                &gtk::ScrolledWindow::builder().vexpand(true).build(),
+                Some(name),
+                title,
+                icon_name,
+            );
+        }
+
+        let sidebar_layout = gtk::Box::builder()
+            .orientation(gtk::Orientation::Vertical)
+            .width_request(SIDEBAR_WIDTH)
+            .css_classes(["background"])
+            .build();
+        sidebar_layout.append(
+            &adw::ViewSwitcher::builder()
+                .stack(&sidebar_stack)
+                .policy(adw::ViewSwitcherPolicy::Narrow)
+                .margin_top(5)
+                .margin_bottom(5)
+                .build(),
+        );
+        sidebar_layout.append(&sidebar_stack);
+
+        let sidebar = adw::Flap::builder()
+            .flap(&sidebar_layout)
+            .content(&main_layout)
+            .separator(&gtk::Separator::new(gtk::Orientation::Vertical))
+            .reveal_flap(false)
+            .locked(true)
+            .build();
+        sidebar
+            .bind_property("reveal-flap", &sidebar_button, "active")
+            .bidirectional()
+            .sync_create()
+            .build();
+        sidebar.connect_reveal_flap_notify(gtk::glib::clone!(@strong sender => move |_| {
+            sender.input(AppInput::UpdateSidebar);
+        }));
+
+        let reading_mode_overlay = gtk::Overlay::builder().child(&sidebar).build();
+        reading_mode_overlay.add_overlay(&reading_mode_button);
+
+        // Non-blocking messages are shown over the tabs.
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&reading_mode_overlay));
+
+        // Only one backoff toast is shown at once, however many requests were asked to wait.
+        let backoff_toast: Rc<RefCell<Option<adw::Toast>>> = Rc::new(RefCell::new(None));
+        model
+            .stackexchange_client
+            .connect_backoff(gtk::glib::clone!(
+                @strong toast_overlay => move |backoff| {
+                if backoff_toast.borrow().is_some() {
+                    return;
+                }
+
+                let toast = adw::Toast::new(&format!(
+                    "StackExchange asked to slow down, waiting {} seconds between requests",
+                    backoff.as_secs_f64().ceil()
+                ));
+                toast.connect_dismissed(gtk::glib::clone!(@strong backoff_toast => move |_| {
+                    backoff_toast.borrow_mut().take();
+                }));
+                toast_overlay.add_toast(toast.clone());
+                *backoff_toast.borrow_mut() = Some(toast);
+            }));
+
+        // Create tabs overview, a grid with thumbnails of every tab
+        let tab_overview = adw::TabOverview::builder()
+            .view(&tab_view)
+            .enable_new_tab(true)
+            .enable_search(true)
+            .child(&toast_overlay)
+            .build();
+        root.set_content(Some(&tab_overview));
+
+        // New tabs start at the home page.
+        tab_overview.connect_create_tab(gtk::glib::clone!(
+            @strong sender,
+            @strong tab_view,
+            @strong model.home_site as home_site => move |_| {
+            append_home_tab(&tab_view, &home_site.borrow(), sender.input_sender())
+        }));
+
+        // Nothing is left to choose from once the last tab is closed.
+        tab_view.connect_n_pages_notify(gtk::glib::clone!(@strong tab_overview => move |view| {
+            if view.n_pages() == 0 {
+                tab_overview.set_open(false);
+            }
+        }));
+
+        let widgets = AppWidgets {
+            tab_view,
+            tab_overview,
+            tab_bar,
+            reading_mode_button,
+            toast_overlay,
+            header,
+            progress_bar,
+            search_button,
+            search_entry,
+            suggestions_popover,
+            inbox_button,
+            bookmark_button,
+            browser_button,
+            copy_link_button,
+            back_button,
+            forward_button,
+            account_button,
+            account_avatar,
+            title_widget,
+            quota_label,
+            closed_tabs_menu,
+            tab_cycle_popover,
+            split_view,
+            sidebar,
+            sidebar_stack,
+            main_menu,
+            tab_menu,
+            tab_actions,
+        };
+
+        // Save the open questions when the window is closed.
+        root.connect_close_request(gtk::glib::clone!(
+            @strong sender,
+            @strong widgets.tab_view as tab_view,
+            @strong model.question_tabs as question_tabs,
+            @strong model.unloaded_tabs as unloaded_tabs,
+            @strong model.tab_windows as tab_windows,
+            @strong model.private as private,
+            @strong model.restore_session as restore_session => move |window| {
+                // Pinned tabs are always restored, but the others would be lost.
+                let unpinned_tabs = unpinned_tabs(&tab_view, &tab_windows.borrow());
+                if unpinned_tabs > QUIT_CONFIRMATION_TABS && !restore_session.get() {
+                    confirm_quit(window, unpinned_tabs, private.get(), sender.input_sender());
+                    return gtk::Inhibit(true);
+                }
+
+                save_session(
+                    &tab_view,
+                    &tab_windows.borrow(),
+                    &question_tabs.borrow(),
+                    &unloaded_tabs.borrow(),
+                    false,
+                    private.get(),
+                );
+                save_window_state(window);
+                // Detached windows don't outlive the main one.
+                for tab_window in tab_windows.take() {
+                    if let Some(window) = tab_window.root().and_downcast::<gtk::Window>() {
+                        window.close();
+                    }
+                }
+                gtk::Inhibit(false)
+            }
+        ));
+
+        // Continue the last session, or start with something to read, even without a link.
+        let session = Session::load();
+        let restored_tabs: Vec<SessionTab> =
+            if (model.settings.restore_session || session.restore) && !session.tabs.is_empty() {
+                session.tabs
+            } else {
+                sender.input(AppInput::OpenHome);
+                // Pinned tabs are kept even without restoring the others.
+                session.tabs.into_iter().filter(|tab| tab.pinned).collect()
+            };
+        if !restored_tabs.is_empty() {
+            sender.input(AppInput::RestoreSession(restored_tabs.clone()));
+        }
+
+        // The last run crashed if its snapshot wasn't removed on quit.
+        if let Some(recovery) = Session::load_recovery() {
+            if !recovery.tabs.is_empty() && recovery.tabs != restored_tabs {
+                offer_recovery(&widgets.toast_overlay, recovery.tabs, sender.input_sender());
+            }
+        }
+        gtk::glib::timeout_add_seconds_local(
+            RECOVERY_INTERVAL,
+            gtk::glib::clone!(@strong sender => move || {
+                sender.input(AppInput::SaveRecovery);
+                gtk::glib::Continue(true)
+            }),
+        );
+
+        // Check subscribed feeds now and then periodically.
+        sender.input(AppInput::PollSubscriptions);
+        gtk::glib::timeout_add_seconds_local(
+            SUBSCRIPTIONS_INTERVAL,
+            gtk::glib::clone!(@strong sender => move || {
+                sender.input(AppInput::PollSubscriptions);
+                gtk::glib::Continue(true)
+            }),
+        );
+
+        sender.input(AppInput::PollInbox);
+        gtk::glib::timeout_add_seconds_local(
+            INBOX_INTERVAL,
+            gtk::glib::clone!(@strong sender => move || {
+                sender.input(AppInput::PollInbox);
+                gtk::glib::Continue(true)
+            }),
+        );
+
+        sender.input(AppInput::RefreshReputation);
+        gtk::glib::timeout_add_seconds_local(
+            REPUTATION_INTERVAL,
+            gtk::glib::clone!(@strong sender => move || {
+                sender.input(AppInput::RefreshReputation);
+                gtk::glib::Continue(true)
+            }),
+        );
+
+        gtk::glib::timeout_add_seconds_local(
+            WATCH_INTERVAL,
+            gtk::glib::clone!(@strong sender => move || {
+                sender.input(AppInput::PollWatchedTabs);
+                gtk::glib::Continue(true)
+            }),
+        );
+
+        gtk::glib::timeout_add_seconds_local(
+            SUSPEND_INTERVAL,
+            gtk::glib::clone!(@strong sender => move || {
+                sender.input(AppInput::SuspendIdleTabs);
+                gtk::glib::Continue(true)
+            }),
+        );
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        sender: AsyncComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            AppInput::RequestPagesByUri(uri) => {
+                // Questions that are already open have their tab selected instead of a second one.
+                if let Ok(stackexchange::UriLink::Questions {
+                    site,
+                    question_ids,
+                    answer_id,
+                }) = stackexchange::parse_uri(&uri)
+                {
+                    let (open_tabs, closed_ids): (Vec<_>, Vec<_>) = question_ids
+                        .iter()
+                        .map(|&id| (id, self.question_tab(widgets, &site, id)))
+                        .partition(|(_, tab_page)| tab_page.is_some());
+
+                    if let Some((_, Some(tab_page))) = open_tabs.first() {
+                        widgets.tab_view.set_selected_page(tab_page);
+                        if let Some(answer_id) = answer_id {
+                            if let Some(question_box) =
+                                tab_clamp(tab_page).and_then(|clamp| clamp.child())
+                            {
+                                componant_builders::highlight_answer(
+                                    &question_box,
+                                    answer_id,
+                                    true,
+                                );
+                            }
+                        }
+                    }
+                    if closed_ids.is_empty() {
+                        return;
+                    }
+                    if !open_tabs.is_empty() {
+                        let uri = stackexchange::UriLink::Questions {
+                            site,
+                            question_ids: closed_ids.into_iter().map(|(id, _)| id).collect(),
+                            answer_id: None,
+                        }
+                        .to_uri();
+                        self.request_pages(widgets, &uri, false, &sender);
+                        return;
+                    }
+                }
+
+                self.request_pages(widgets, &uri, false, &sender);
+            }
+            AppInput::OpenLinkInNewTab { uri, background } => {
+                self.request_pages(widgets, &uri, background, &sender);
+            }
+            AppInput::FollowLink { uri, link } => {
+                let Some(link) = link.upgrade() else {
+                    return;
+                };
+                // Tabs moved to other windows follow their links too.
+                let tab_views: Vec<adw::TabView> = std::iter::once(widgets.tab_view.clone())
+                    .chain(self.tab_windows.borrow().iter().cloned())
+                    .collect();
+                let tab_page = tab_views
+                    .iter()
+                    .flat_map(|tab_view| {
+                        (0..tab_view.n_pages()).map(|position| tab_view.nth_page(position))
+                    })
+                    .find(|tab_page| link.is_ancestor(&tab_page.child()));
+                let location = tab_page
+                    .as_ref()
+                    .and_then(|tab_page| self.tab_location(tab_page));
+                // Like links of the split view, which keeps its question.
+                let (Some(tab_page), Some(location)) = (tab_page, location) else {
+                    self.request_pages(widgets, &uri, false, &sender);
+                    return;
+                };
+
+                let question_tabs = self.question_tabs.borrow().clone();
+                self.tab_histories
+                    .retain(|tab_page, _| question_tabs.contains_key(tab_page));
+
+                let history = self.tab_histories.entry(tab_page.clone()).or_default();
+                history.back.push(location);
+                history.forward.clear();
+
+                self.navigate_tab(widgets, &tab_page, &uri, None, &sender);
+            }
+            AppInput::NavigateBack => self.navigate_tab_history(widgets, true, &sender),
+            AppInput::NavigateForward => self.navigate_tab_history(widgets, false, &sender),
+            AppInput::RetryRequest { uri, tab_page } => {
+                let Some(tab_page) = tab_page.upgrade() else {
+                    return;
+                };
+
+                if let Some(bin) = tab_page.child().downcast_ref::<adw::Bin>() {
+                    bin.set_child(Some(&componant_builders::st_loading_page(None)));
+                }
+                tab_page.set_title("Loading…");
+                tab_page.set_loading(true);
+
+                self.load_questions(
+                    widgets,
+                    &uri,
+                    &tab_page,
+                    false,
+                    AfterLoad::default(),
+                    &sender,
+                );
+            }
+            AppInput::ToggleSearchEntry => {
+                if widgets.search_button.is_active() {
+                    widgets.header.set_title_widget(Some(&widgets.search_entry));
+                    widgets.search_entry.show();
+                    widgets.search_entry.grab_focus();
+                } else {
+                    widgets.suggestions_popover.popdown();
+                    widgets.search_entry.hide();
+                    widgets.header.set_title_widget(Some(&widgets.title_widget));
+                }
+            }
+            AppInput::SuggestQuestions(text) => {
+                let text = text.trim();
+
+                // Ids and very short terms don't have meaningful suggestions.
+                if text.len() < 3 || text.parse::<stackexchange::Id>().is_ok() {
+                    widgets.suggestions_popover.popdown();
+                    return;
+                }
+                // Typed over already, the newer text has its own message.
+                if widgets.search_entry.text().trim() != text {
+                    return;
+                }
+
+                let client = self.stackexchange_client.clone();
+                let site = self.settings.default_site.clone();
+                let text = text.to_owned();
+                let popover = widgets.suggestions_popover.clone();
+                let entry = widgets.search_entry.clone();
+                let sender = sender.input_sender().clone();
+
+                relm4::spawn_local(async move {
+                    let questions = match client.get_search_suggestions(&site, &text).await {
+                        // Titles rarely contain the exact text of longer terms, so fall back to
+                        // similar questions.
+                        Ok(questions) if questions.is_empty() => {
+                            client.get_similar_questions(&site, &text).await
+                        }
+                        questions => questions,
+                    };
+
+                    // The user might have kept typing while waiting for the response.
+                    if entry.text().trim() != text {
+                        return;
+                    }
+
+                    match questions {
+                        Ok(questions) if !questions.is_empty() => {
+                            let suggestions =
+                                componant_builders::st_question_list(&questions, &sender);
+                            suggestions.set_selection_mode(gtk::SelectionMode::Single);
+
+                            suggestions.connect_row_activated(gtk::glib::clone!(
+                                @strong popover,
+                                @strong entry => move |_list, _row| {
+                                    popover.popdown();
+                                    entry.set_text("");
+                                }
+                            ));
+
+                            popover.set_child(Some(&suggestions));
+                            popover.popup();
+                        }
+                        // Suggestions are optional, so errors are ignored.
+                        _ => popover.popdown(),
+                    }
+                });
+            }
+            AppInput::ShowSearchHistory => {
+                let terms = self.history.borrow().search_terms.clone();
+                if terms.is_empty() || !widgets.search_entry.text().is_empty() {
+                    widgets.suggestions_popover.popdown();
+                    return;
+                }
+
+                let search_history =
+                    componant_builders::st_search_history(&terms, sender.input_sender());
+                search_history.connect_row_activated(gtk::glib::clone!(
+                    @strong widgets.suggestions_popover as popover => move |_list, _row| {
+                        popover.popdown();
+                    }
+                ));
+
+                widgets.suggestions_popover.set_child(Some(&search_history));
+                widgets.suggestions_popover.popup();
+            }
+            AppInput::RemoveSearchTerm(term) => {
+                {
+                    let mut history = self.history.borrow_mut();
+                    history.remove_search(&term);
+                    history.save();
+                }
+                sender.input(AppInput::ShowSearchHistory);
+            }
+            AppInput::Search { site, query } => {
+                // The results tab is shown right away, closing it aborts the search.
+                let loading_page = componant_builders::st_loading_page(Some(&query));
+                loading_page.set_title("Searching…");
+                let results_bin = adw::Bin::builder().child(&loading_page).build();
+
+                let tab_page = widgets.tab_view.append(&results_bin);
+                tab_page.set_title(&format!("Search: {query}"));
+                tab_page.set_keyword(&site);
+                tab_page.set_loading(true);
+                widgets.tab_view.set_selected_page(&tab_page);
+
+                let (handle, registration) = AbortHandle::new_pair();
+                self.pending_requests
+                    .borrow_mut()
+                    .insert(tab_page.clone(), handle);
+                self.show_progress(widgets);
+
+                let client = self.stackexchange_client.clone();
+                let pending_requests = self.pending_requests.clone();
+                let tab_view = widgets.tab_view.clone();
+                let sender = sender.input_sender().clone();
+
+                relm4::spawn_local(async move {
+                    let results = Abortable::new(
+                        client.search_excerpts(&site, &SearchQuery::parse(&query)),
+                        registration,
+                    )
+                    .await;
+
+                    // Nothing to show if it was aborted.
+                    let Ok(results) = results else {
+                        return;
+                    };
+                    pending_requests.borrow_mut().remove(&tab_page);
+
+                    match results {
+                        Ok(results) => {
+                            let results_box =
+                                componant_builders::st_search_results(&query, &results, &sender);
+
+                            results_bin.set_child(Some(
+                                &gtk::ScrolledWindow::builder()
+                                    .child(&results_box)
+                                    .vexpand(true)
+                                    .hexpand(true)
+                                    .build(),
+                            ));
+                            tab_page.set_loading(false);
+                            sender.emit(AppInput::LoadSiteIcon {
+                                site,
+                                tab_page: tab_page.downgrade().into(),
+                            });
+                        }
+                        Err(error) => {
+                            open_error_tab(&tab_view, &error, &sender);
+                            tab_view.close_page(&tab_page);
+                        }
+                    }
+                });
+            }
+            AppInput::ShowAboutWindow => {
+                let developers: Vec<&str> = env!("CARGO_PKG_AUTHORS").split(':').collect();
+
+                let windowing_backend_name = match gtk::gdk::Display::default() {
+                    Some(display) => {
+                        match display.backend() {
+                            gtk::gdk::Backend::Wayland => "Wayland".to_owned(),
+                            gtk::gdk::Backend::X11 => "X11".to_owned(),
+                            // When unsupported windowing system is used: win32, macos, broadway.
+                            _ => "Unsupported".to_owned(),
+                        }
+                    }
+                    None => "Undetected".to_owned(),
+                };
+
+                let transfer_stats = self.stackexchange_client.transfer_stats();
+                let saved_bandwidth = if transfer_stats.decompressed_bytes > 0 {
+                    100 - transfer_stats.compressed_bytes * 100 / transfer_stats.decompressed_bytes
+                } else {
+                    0
+                };
+
+                let about_window = adw::AboutWindow::builder()
+                    .application_name(APP_NAME)
+                    .version(env!("CARGO_PKG_VERSION"))
+                    .license_type(gtk::License::Gpl30Only)
+                    .comments(env!("CARGO_PKG_DESCRIPTION"))
+                    .developers(developers)
+                    .website(env!("CARGO_PKG_HOMEPAGE"))
+                    .issue_url("https://github.com/zer0-x/stackbloatless/issues")
+                    .application(&relm4::main_application())
+                    .transient_for(&relm4::main_application().active_window().unwrap())
+                    .debug_info(format!(
+                        "[rust]\n\
+                        {}\n\
+                        {}\n\
+                        {}\n\
+                        {}\n\n\
+                        [traget]\n\
+                        {}\n\n\
+                        [source]\n\
+                        branch: {}\n\
+                        commit: {}\n\
+                        clean: {}\n\n\
+                        [runtime]\n\
+                        GTK: {}.{}.{}\n\
+                        Adwaita: {}.{}.{}\n\
+                        Cairo: {}\n\
+                        Pango: {}\n\
+                        GDK Windowing Backend: {}\n\
+                        Session Desktop: {}\n\
+                        Current Desktop: {}\n\n\
+                        [network]\n\
+                        Measure compression: {}\n\
+                        Measured requests: {}\n\
+                        Compressed: {} bytes\n\
+                        Decompressed: {} bytes\n\
+                        Saved bandwidth: {}%",
+                        build::BUILD_OS,
+                        build::CARGO_VERSION,
+                        build::RUST_CHANNEL,
+                        build::RUST_VERSION,
+                        build::BUILD_TARGET,
+                        build::BRANCH,
+                        build::COMMIT_HASH,
+                        build::GIT_CLEAN,
+                        gtk::major_version(),
+                        gtk::minor_version(),
+                        gtk::micro_version(),
+                        adw::major_version(),
+                        adw::minor_version(),
+                        adw::micro_version(),
+                        gtk::cairo::version_string(),
+                        gtk::pango::version_string(),
+                        windowing_backend_name,
+                        std::env::var("XDG_SESSION_DESKTOP").unwrap_or("Undetected".to_owned()),
+                        std::env::var("XDG_CURRENT_DESKTOP").unwrap_or("Undetected".to_owned()),
+                        self.stackexchange_client.measure_compression(),
+                        transfer_stats.requests,
+                        transfer_stats.compressed_bytes,
+                        transfer_stats.decompressed_bytes,
+                        saved_bandwidth,
+                    ))
+                    .build();
+
+                about_window.add_link(
+                    "Release Notes",
+                    "https://github.com/zer0-x/stackbloatless/blob/main/CHANGELOG.md",
+                );
+
+                about_window.present();
+            }
+            AppInput::ShowPreferences => {
+                preferences::preferences_window(&self.settings, sender.input_sender()).present();
+            }
+            AppInput::Quit { restore_session } => {
+                save_session(
+                    &widgets.tab_view,
+                    &self.tab_windows.borrow(),
+                    &self.question_tabs.borrow(),
+                    &self.unloaded_tabs.borrow(),
+                    restore_session,
+                    self.private.get(),
+                );
+                save_window_state(root);
+                relm4::main_application().quit();
+            }
+            AppInput::ToggleSelectedTabPin => {
+                let tab_view = self.active_tab_view(widgets);
+                if let Some(selected_page) = tab_view.selected_page() {
+                    tab_view.set_page_pinned(&selected_page, !selected_page.is_pinned());
+                }
+            }
+            AppInput::ShowFindBar => {
+                if let Some(find_bar) = self
+                    .active_tab_view(widgets)
+                    .selected_page()
+                    .and_then(|tab_page| tab_find_bar(&tab_page))
+                {
+                    find_bar.set_search_mode(true);
+                }
+            }
+            AppInput::DuplicateTab => {
+                let tab_view = self.active_tab_view(widgets);
+                let Some(selected_page) = tab_view.selected_page() else {
+                    return;
+                };
+                let Some((site, id)) = self.question_tabs.borrow().get(&selected_page).cloned()
+                else {
+                    return;
+                };
+
+                // Next to it, in its window. It's loaded once it's selected.
+                let tab_page = self.insert_unloaded_tab(
+                    &tab_view,
+                    tab_view.page_position(&selected_page) + 1,
+                    &site,
+                    id,
+                    UnloadedTab {
+                        scroll_position: tab_scroll_position(
+                            &selected_page,
+                            &self.unloaded_tabs.borrow(),
+                        ),
+                        answer_id: None,
+                    },
+                );
+                tab_view.set_selected_page(&tab_page);
+            }
+            AppInput::RememberClosedTab(closed_tab) => {
+                self.closed_tabs.insert(0, closed_tab);
+                self.closed_tabs.truncate(MAX_CLOSED_TABS);
+                update_closed_tabs_menu(&widgets.closed_tabs_menu, &self.closed_tabs);
+            }
+            AppInput::ReopenClosedTab(index) => {
+                let index = index as usize;
+                if index >= self.closed_tabs.len() {
+                    return;
+                }
+
+                let closed_tab = self.closed_tabs.remove(index);
+                update_closed_tabs_menu(&widgets.closed_tabs_menu, &self.closed_tabs);
+
+                self.reopen_question(
+                    widgets,
+                    &closed_tab.site,
+                    closed_tab.question_id,
+                    closed_tab.scroll_position,
+                    &sender,
+                );
+            }
+            AppInput::MoveTabToNewWindow => {
+                let tab_view = self.active_tab_view(widgets);
+                if let Some(selected_page) = tab_view.selected_page() {
+                    let tab_window = new_tab_window(&self.tab_window_context(
+                        &widgets.tab_menu,
+                        &widgets.tab_actions,
+                        &sender,
+                    ));
+                    tab_view.transfer_page(&selected_page, &tab_window, 0);
+                }
+            }
+            AppInput::ToggleTabOverview => {
+                widgets
+                    .tab_overview
+                    .set_open(!widgets.tab_overview.is_open());
+            }
+            AppInput::ShowTabSwitcher => {
+                let entries = (0..widgets.tab_view.n_pages())
+                    .map(|position| {
+                        let tab_page = widgets.tab_view.nth_page(position);
+                        componant_builders::PaletteEntry {
+                            title: tab_page.title().to_string(),
+                            // The site and the tags, so tabs can be found by them too.
+                            subtitle: tab_page
+                                .keyword()
+                                .map(|keyword| keyword.to_string())
+                                .unwrap_or_default(),
+                            message: AppInput::FocusTab(tab_page.downgrade().into()),
+                        }
+                    })
+                    .collect();
+
+                componant_builders::st_palette(
+                    root.upcast_ref(),
+                    "Switch to tab",
+                    entries,
+                    sender.input_sender(),
+                )
+                .present();
+            }
+            AppInput::FocusTab(tab_page) => {
+                let Some(tab_page) = tab_page.upgrade() else {
+                    return;
+                };
+
+                // It might have been moved to another window since.
+                if tab_view_contains(&widgets.tab_view, &tab_page) {
+                    widgets.tab_view.set_selected_page(&tab_page);
+                }
+                // A folded sidebar covers the tabs.
+                if widgets.sidebar.is_folded() {
+                    widgets.sidebar.set_reveal_flap(false);
+                }
+            }
+            AppInput::NewTab => {
+                sender.input(AppInput::OpenHome);
+                sender.input(AppInput::FocusSearch);
+            }
+            AppInput::CycleTabs(forward) => {
+                let tab_view = &self.active_tab_view(widgets);
+                let moved = if forward {
+                    tab_view.select_next_page()
+                } else {
+                    tab_view.select_previous_page()
+                };
+
+                if !moved && tab_view.n_pages() > 0 {
+                    let position = if forward { 0 } else { tab_view.n_pages() - 1 };
+                    tab_view.set_selected_page(&tab_view.nth_page(position));
+                }
+            }
+            AppInput::CycleRecentTabs(forward) => {
+                if !self.settings.recent_tab_cycling {
+                    sender.input(AppInput::CycleTabs(forward));
+                    return;
+                }
+
+                // Tabs that were never selected come last, in their order.
+                let open_tabs: Vec<adw::TabPage> = (0..widgets.tab_view.n_pages())
+                    .map(|position| widgets.tab_view.nth_page(position))
+                    .collect();
+                let open_set: HashSet<&adw::TabPage> = open_tabs.iter().collect();
+                self.tab_recency
+                    .retain(|tab_page| open_set.contains(tab_page));
+                let recent_set: HashSet<adw::TabPage> = self.tab_recency.iter().cloned().collect();
+                self.tab_recency.extend(
+                    open_tabs
+                        .into_iter()
+                        .filter(|tab_page| !recent_set.contains(tab_page)),
+                );
+
+                let count = self.tab_recency.len();
+                if count < 2 {
+                    return;
+                }
+                // The selected tab is the first one when it starts.
+                let position = self.tab_cycle.unwrap_or(0);
+                let position = if forward {
+                    (position + 1) % count
+                } else {
+                    (position + count - 1) % count
+                };
+                self.tab_cycle = Some(position);
+                widgets
+                    .tab_view
+                    .set_selected_page(&self.tab_recency[position]);
+
+                let start = (position + 1).saturating_sub(TAB_CYCLE_ENTRIES);
+                let titles: Vec<String> = self.tab_recency[start..]
+                    .iter()
+                    .take(TAB_CYCLE_ENTRIES)
+                    .map(|tab_page| tab_page.title().to_string())
+                    .collect();
+                widgets
+                    .tab_cycle_popover
+                    .set_child(Some(&componant_builders::st_tab_cycle_list(
+                        &titles,
+                        position - start,
+                    )));
+                widgets.tab_cycle_popover.popup();
+            }
+            AppInput::EndTabCycle => {
+                if self.tab_cycle.take().is_some() {
+                    widgets.tab_cycle_popover.popdown();
+                    sender.input(AppInput::UpdateTabRecency);
+                }
+            }
+            AppInput::UpdateTabRecency => {
+                // Only the tab Ctrl+Tab ends on counts.
+                if self.tab_cycle.is_some() {
+                    return;
+                }
+                let Some(selected_page) = widgets.tab_view.selected_page() else {
+                    return;
+                };
+
+                let open_tabs: HashSet<adw::TabPage> = (0..widgets.tab_view.n_pages())
+                    .map(|position| widgets.tab_view.nth_page(position))
+                    .collect();
+                self.tab_recency
+                    .retain(|tab_page| *tab_page != selected_page && open_tabs.contains(tab_page));
+                self.tab_recency.insert(0, selected_page);
+            }
+            AppInput::SelectTab(position) => {
+                let tab_view = self.active_tab_view(widgets);
+                if (1..=tab_view.n_pages() as u32).contains(&position) {
+                    tab_view.set_selected_page(&tab_view.nth_page(position as i32 - 1));
+                }
+            }
+            AppInput::MoveTab(forward) => {
+                let tab_view = self.active_tab_view(widgets);
+                if let Some(selected_page) = tab_view.selected_page() {
+                    // Both stop at the boundary between pinned and other tabs.
+                    if forward {
+                        tab_view.reorder_forward(&selected_page);
+                    } else {
+                        tab_view.reorder_backward(&selected_page);
+                    }
+                }
+            }
+            AppInput::FocusSearch => {
+                widgets.search_button.set_active(true);
+                sender.input(AppInput::ToggleSearchEntry);
+            }
+            AppInput::CloseTab => {
+                // Pinned tabs are asked about first.
+                let tab_view = self.active_tab_view(widgets);
+                if let Some(selected_page) = tab_view.selected_page() {
+                    tab_view.close_page(&selected_page);
+                }
+            }
+            AppInput::ConfirmCloseTab(tab_page) => {
+                let warning_message = adw::MessageDialog::builder()
+                    .transient_for(root)
+                    .heading("Close pinned tab?")
+                    .body("Do you really want to close a pinned tab?")
+                    .build();
+
+                warning_message.add_responses(&[("yes", "Yes"), ("no", "No")]);
+                warning_message.set_default_response(Some("no"));
+                warning_message
+                    .set_response_appearance("yes", adw::ResponseAppearance::Destructive);
+
+                warning_message.show();
+
+                warning_message.connect_response(
+                    None,
+                    gtk::glib::clone!(@strong sender => move |dialog, responde| {
+                        if responde == "yes" {
+                            sender.input(AppInput::ClosePinnedTab(tab_page.clone()));
+                        }
+                        dialog.close();
+                    }),
+                );
+            }
+            AppInput::CloseOtherTabs => {
+                let tab_view = self.active_tab_view(widgets);
+                if let Some(selected_page) = tab_view.selected_page() {
+                    for tab_page in unpinned_pages(&tab_view) {
+                        if tab_page != selected_page {
+                            tab_view.close_page(&tab_page);
+                        }
+                    }
+                }
+            }
+            AppInput::CloseTabsToRight => {
+                let tab_view = self.active_tab_view(widgets);
+                if let Some(selected_page) = tab_view.selected_page() {
+                    let position = tab_view.page_position(&selected_page);
+                    for tab_page in unpinned_pages(&tab_view) {
+                        if tab_view.page_position(&tab_page) > position {
+                            tab_view.close_page(&tab_page);
+                        }
+                    }
+                }
+            }
+            AppInput::ClosePinnedTab(tab_page) => {
+                let Some(tab_page) = tab_page.upgrade() else {
+                    return;
+                };
+
+                // It might have been moved to another window since.
+                if tab_view_contains(&widgets.tab_view, &tab_page) {
+                    widgets.tab_view.set_page_pinned(&tab_page, false);
+                    widgets.tab_view.close_page(&tab_page);
+                }
+            }
+            AppInput::Login => {
+                let Some(login_url) = stackexchange::StackExchange::login_url() else {
+                    show_error_dialog(
+                        "Logging in is unavailable",
+                        "This build doesn't include StackExchange API credentials.",
+                    );
+                    return;
+                };
+
+                // Authorize the app in the web browser, then the user pastes the link they were
+                // redirected to since desktop apps can't receive the redirect themselves.
+                if let Err(error) = gtk::gio::AppInfo::launch_default_for_uri(
+                    login_url.as_str(),
+                    None::<&gtk::gio::AppLaunchContext>,
+                ) {
+                    sender.input(AppInput::ShowToast(format!(
+                        "Failed to open the web browser: {error}"
+                    )));
+                }
+
+                let redirect_entry = gtk::Entry::builder()
+                    .placeholder_text(
+                        "https://stackoverflow.com/oauth/login_success#access_token=…",
+                    )
+                    .build();
+
+                let login_dialog = adw::MessageDialog::builder()
+                    .transient_for(&relm4::main_application().active_window().unwrap())
+                    .heading("Log in to StackExchange")
+                    .body(format!(
+                        "Authorize the app in your web browser, then paste the link of the page \
+                        you were redirected to.\n\nIf the browser didn't open, visit:\n{login_url}"
+                    ))
+                    .extra_child(&redirect_entry)
+                    .build();
+
+                login_dialog.add_responses(&[("cancel", "Cancel"), ("login", "Log In")]);
+                login_dialog.set_default_response(Some("login"));
+                login_dialog.set_response_appearance("login", adw::ResponseAppearance::Suggested);
+
+                login_dialog.connect_response(
+                    None,
+                    gtk::glib::clone!(@strong sender => move |dialog, response| {
+                        if response == "login" {
+                            sender.input(AppInput::FinishLogin(redirect_entry.text().to_string()));
+                        }
+                        dialog.close();
+                    }),
+                );
+
+                login_dialog.present();
+            }
+            AppInput::FinishLogin(redirect_url) => {
+                match self.stackexchange_client.login(&redirect_url) {
+                    Ok(()) => {
+                        sender.input(AppInput::PollInbox);
+                        sender.input(AppInput::RefreshReputation);
+                    }
+                    Err(error) => show_error_dialog("Logging in failed", &error.to_string()),
+                }
+            }
+            AppInput::Logout => {
+                self.stackexchange_client.logout();
+                widgets.inbox_button.set_visible(false);
+                widgets.account_button.set_visible(false);
+            }
+            AppInput::RefreshReputation => {
+                if !self.stackexchange_client.is_authenticated()
+                    || self.stackexchange_client.is_offline()
+                {
+                    return;
+                }
+
+                // Reputation is per site, the one of the default site is shown.
+                let client = self.stackexchange_client.clone();
+                let site = self.settings.default_site.clone();
+                let account_avatar = widgets.account_avatar.clone();
+                let account_button = widgets.account_button.clone();
+
+                relm4::spawn_local(async move {
+                    let reputation = async {
+                        let user = client.get_me(&site).await?;
+                        let changes = client.get_reputation_history(&site).await?;
+
+                        Ok::<_, stackexchange::StackExchangeError>((user, changes))
+                    };
+
+                    let (user, changes) = match reputation.await {
+                        Ok(reputation) => reputation,
+                        Err(error) => {
+                            sender.input(AppInput::ShowToast(format!(
+                                "Failed to refresh the reputation: {error}"
+                            )));
+                            return;
+                        }
+                    };
+
+                    account_avatar.set_text(Some(&user.display_name));
+                    account_button.set_tooltip_text(Some(&format!(
+                        "{} · {} reputation",
+                        user.display_name, user.reputation
+                    )));
+                    if let Some(popover) = account_button.popover() {
+                        popover
+                            .set_child(Some(&componant_builders::st_reputation(&user, &changes)));
+                    }
+                    account_button.set_visible(true);
+                });
+            }
+            AppInput::PollInbox => {
+                if !self.stackexchange_client.is_authenticated()
+                    || self.stackexchange_client.is_offline()
+                {
+                    return;
+                }
+
+                let client = self.stackexchange_client.clone();
+                let inbox_button = widgets.inbox_button.clone();
+
+                relm4::spawn_local(async move {
+                    let items = match client.get_unread_inbox().await {
+                        Ok(items) => items,
+                        Err(error) => {
+                            sender.input(AppInput::ShowToast(format!(
+                                "Failed to check the inbox: {error}"
+                            )));
+                            return;
+                        }
+                    };
+
+                    let inbox = componant_builders::st_inbox(&items, sender.input_sender());
+                    if let Some(popover) = inbox_button.popover() {
+                        popover.set_child(Some(&inbox));
+                    }
+
+                    inbox_button.set_icon_name(if items.is_empty() {
+                        "mail-read-symbolic"
+                    } else {
+                        "mail-unread-symbolic"
+                    });
+                    inbox_button.set_tooltip_text(Some(&format!("Inbox: {} unread", items.len())));
+                    inbox_button.set_visible(true);
+                });
+            }
+            AppInput::Vote {
+                site,
+                question_id,
+                post_kind,
+                id,
+                controls,
+                previous,
+                next,
+            } => {
+                let client = self.stackexchange_client.clone();
+
+                relm4::spawn_local(async move {
+                    // Undo the removed votes first, since a post can't be upvoted and downvoted at
+                    // the same time.
+                    let changes = [
+                        (Vote::Up, previous.upvoted, next.upvoted),
+                        (Vote::Down, previous.downvoted, next.downvoted),
+                    ];
+                    let undos = changes.iter().filter(|(_, was, is)| *was && !*is);
+                    let casts = changes.iter().filter(|(_, was, is)| !*was && *is);
+
+                    // What the server has, the steps before a failed one are kept.
+                    let mut applied = previous;
+                    let mut result = Ok(());
+                    for (vote, _, is) in undos.chain(casts) {
+                        result = client
+                            .vote(&site, question_id, post_kind, id, *vote, !*is)
+                            .await;
+
+                        if result.is_err() {
+                            break;
+                        }
+                        match vote {
+                            Vote::Up => applied.upvoted = *is,
+                            Vote::Down => applied.downvoted = *is,
+                        }
+                    }
+
+                    if let Err(error) = result {
+                        // Roll back the optimistic update, as far as the failed step.
+                        applied.score = previous.score
+                            - (previous.upvoted as i32 - previous.downvoted as i32)
+                            + (applied.upvoted as i32 - applied.downvoted as i32);
+                        controls.set_state(applied);
+
+                        show_request_error("Voting failed", &error, sender.input_sender());
+                    }
+                });
+            }
+            AppInput::Favorite {
+                site,
+                id,
+                favorite,
+                button,
+            } => {
+                let client = self.stackexchange_client.clone();
+
+                relm4::spawn_local(async move {
+                    if let Err(error) = client.set_favorite(&site, id, favorite).await {
+                        // Roll back the optimistic update, unless the tab was closed.
+                        if let Some(button) = button.upgrade() {
+                            button.set_active(!favorite);
+                        }
+
+                        show_request_error(
+                            "Updating favorites failed",
+                            &error,
+                            sender.input_sender(),
+                        );
+                    }
+                });
+            }
+            AppInput::SetMeasureCompression(measure_compression) => {
+                self.stackexchange_client
+                    .set_measure_compression(measure_compression);
+            }
+            AppInput::SetOffline(offline) => {
+                self.stackexchange_client.set_offline(offline);
+            }
+            AppInput::SetPrivate(private) => {
+                self.private.set(private);
+                self.stackexchange_client.set_private(private);
+                self.update_subtitle(widgets);
+            }
+            AppInput::SetProxy(proxy) => {
+                match self.stackexchange_client.set_proxy(proxy.as_deref()) {
+                    Ok(()) => {
+                        self.settings.proxy = proxy;
+                        self.settings.save();
+                    }
+                    Err(error) => show_error_dialog("Invalid proxy", &error.to_string()),
+                }
+            }
+            AppInput::SetTimeouts {
+                connect_timeout,
+                request_timeout,
+            } => {
+                self.settings.connect_timeout = connect_timeout;
+                self.settings.request_timeout = request_timeout;
+
+                match self
+                    .stackexchange_client
+                    .set_timeouts(self.settings.timeouts())
+                {
+                    Ok(()) => self.settings.save(),
+                    Err(error) => show_error_dialog("Changing timeouts failed", &error.to_string()),
+                }
+            }
+            AppInput::SetMaxConcurrentRequests(limit) => {
+                self.settings.max_concurrent_requests = limit;
+                self.stackexchange_client.set_max_concurrent_requests(limit);
+                self.settings.save();
+            }
+            AppInput::QuestionsLoaded {
+                uri,
+                placeholder,
+                pages,
+                refresh,
+                after_load,
+            } => {
+                let Some(placeholder) = placeholder.upgrade() else {
+                    return;
+                };
+                // It was asked to show another question in the meantime.
+                if self.pending_requests.borrow().contains_key(&placeholder) {
+                    return;
+                }
+
+                self.show_loaded_questions(widgets, &uri, &placeholder, pages, after_load, &sender);
+
+                // Changes are now shown by the tab.
+                let question = self.question_tabs.borrow().get(&placeholder).cloned();
+                if let (true, Some((site, id))) = (refresh, question) {
+                    if let Some(watched) = self.watched_tabs.borrow_mut().get_mut(&placeholder) {
+                        if let Some(activity) = self.stackexchange_client.cached_activity(&site, id)
+                        {
+                            *watched = activity;
+                        }
+                    }
+                }
+            }
+            AppInput::LoadSiteIcon { site, tab_page } => {
+                if let Some(tab_page) = tab_page.upgrade() {
+                    self.set_site_icon(&tab_page, &site);
+                }
+            }
+            AppInput::LoadRelatedQuestions {
+                site,
+                id,
+                relation,
+                section,
+            } => {
+                let client = self.stackexchange_client.clone();
+
+                relm4::spawn_local(async move {
+                    let questions = client.get_related_questions(&site, id, relation).await;
+
+                    // The tab might be closed in the meantime.
+                    if let Some(section) = section.upgrade() {
+                        componant_builders::fill_questions_section(
+                            &section,
+                            questions,
+                            sender.input_sender(),
+                        );
+                    }
+                });
+            }
+            AppInput::OpenUserProfile { site, id } => {
+                let client = self.stackexchange_client.clone();
+                let profile = {
+                    let site = site.clone();
+                    async move {
+                        let user = client.get_users(&site, &[id]).await?.remove(0);
+                        let top_tags = client.get_user_top_tags(&site, id).await?;
+                        let answers = client.get_user_answers(&site, id).await?;
+
+                        Ok((user, top_tags, answers))
+                    }
+                };
+
+                let input_sender = sender.input_sender().clone();
+                self.open_loading_tab(
+                    widgets,
+                    "Profile",
+                    &site,
+                    profile,
+                    move |(user, top_tags, answers), tab_page| {
+                        tab_page.set_title(&user.display_name);
+                        componant_builders::st_user_profile(
+                            &user,
+                            &top_tags,
+                            &answers,
+                            &input_sender,
+                        )
+                        .upcast()
+                    },
+                    &sender,
+                );
+            }
+            AppInput::OpenTag { site, tag } => {
+                let client = self.stackexchange_client.clone();
+                let tag_page = {
+                    let site = site.clone();
+                    let tags = [tag.clone()];
+                    async move {
+                        let info = client.get_tags_info(&site, &tags).await?;
+                        let wikis = client.get_tags_wikis(&site, &tags).await?;
+
+                        Ok((info, wikis))
+                    }
+                };
+
+                let subscription = Subscription {
+                    site: site.clone(),
+                    tag: tag.clone(),
+                };
+                let subscribed = self.settings.subscriptions.contains(&subscription);
+                let input_sender = sender.input_sender().clone();
+                self.open_loading_tab(
+                    widgets,
+                    &format!("[{tag}]"),
+                    &site,
+                    tag_page,
+                    move |(info, wikis), _tab_page| {
+                        componant_builders::st_tag_page(
+                            &subscription.site,
+                            &subscription.tag,
+                            info.first(),
+                            wikis.first(),
+                            subscribed,
+                            &input_sender,
+                        )
+                        .upcast()
+                    },
+                    &sender,
+                );
+            }
+            AppInput::SetSubscribed {
+                site,
+                tag,
+                subscribed,
+            } => {
+                let subscription = Subscription { site, tag };
+
+                self.settings
+                    .subscriptions
+                    .retain(|other| *other != subscription);
+                if subscribed {
+                    self.settings.subscriptions.push(subscription);
+                    sender.input(AppInput::PollSubscriptions);
+                } else {
+                    self.feed_entries
+                        .retain(|(other, _)| *other != subscription);
+                }
+
+                self.settings.save();
+                sender.input(AppInput::UpdateSidebar);
+            }
+            AppInput::PollSubscriptions => {
+                if self.stackexchange_client.is_offline() {
+                    return;
+                }
+
+                // Every feed is fetched at once, they don't count against the quota.
+                let client = self.stackexchange_client.clone();
+                let subscriptions = self.settings.subscriptions.clone();
+
+                relm4::spawn_local(async move {
+                    let feeds =
+                        futures::future::join_all(subscriptions.iter().map(|subscription| {
+                            client.get_tag_feed(&subscription.site, &subscription.tag)
+                        }))
+                        .await;
+
+                    sender.input(AppInput::FeedsChecked(
+                        subscriptions.into_iter().zip(feeds).collect(),
+                    ));
+                });
+            }
+            AppInput::FeedsChecked(feeds) => {
+                let mut new_questions = 0;
+                for (subscription, feed) in feeds {
+                    // Unsubscribed while it was fetched.
+                    if !self.settings.subscriptions.contains(&subscription) {
+                        continue;
+                    }
+                    let entries = match feed {
+                        Ok(entries) => entries,
+                        Err(error) => {
+                            sender.input(AppInput::ShowToast(format!(
+                                "Failed to check the feed of [{}] on {}: {error}",
+                                subscription.tag, subscription.site
+                            )));
+                            continue;
+                        }
+                    };
+
+                    let seen = self
+                        .feed_entries
+                        .iter()
+                        .filter(|(other, _)| *other == subscription)
+                        .map(|(_, entry)| entry.question_id)
+                        .collect::<HashSet<_>>();
+
+                    // Everything is new the first time a feed is checked.
+                    if !seen.is_empty() {
+                        new_questions += entries
+                            .iter()
+                            .filter(|entry| !seen.contains(&entry.question_id))
+                            .count();
+                    }
+
+                    self.feed_entries
+                        .retain(|(other, _)| *other != subscription);
+                    self.feed_entries.extend(
+                        entries
+                            .into_iter()
+                            .map(|entry| (subscription.clone(), entry)),
+                    );
+                }
+
+                // Newest first, RFC 3339 dates in UTC are sorted as text.
+                self.feed_entries
+                    .sort_by(|(_, a), (_, b)| b.published.cmp(&a.published));
+
+                if new_questions > 0 && self.settings.notify_subscriptions {
+                    let notification = gtk::gio::Notification::new(APP_NAME);
+                    notification.set_body(Some(&format!(
+                        "{new_questions} new questions in your subscriptions"
+                    )));
+                    relm4::main_application()
+                        .send_notification(Some("subscriptions"), &notification);
+                }
+            }
+            AppInput::OpenHistory => {
+                let history_box = componant_builders::st_history(
+                    &self.history.borrow().entries,
+                    sender.input_sender(),
+                );
+
+                let tab_page = widgets.tab_view.append(
+                    &gtk::ScrolledWindow::builder()
+                        .child(&history_box)
+                        .vexpand(true)
+                        .hexpand(true)
+                        .build(),
+                );
+                tab_page.set_title("History");
+                widgets.tab_view.set_selected_page(&tab_page);
+            }
+            AppInput::ClearHistory => {
+                let mut history = self.history.borrow_mut();
+                history.clear();
+                history.save();
+                sender.input(AppInput::UpdateSidebar);
+            }
+            AppInput::ToggleBookmark => {
+                let question = self
+                    .active_tab_view(widgets)
+                    .selected_page()
+                    .and_then(|tab_page| {
+                        let (site, id) = self.question_tabs.borrow().get(&tab_page)?.clone();
+                        Some((site, id, tab_page))
+                    });
+
+                let Some((site, id, tab_page)) = question else {
+                    self.update_question_buttons(widgets);
+                    return;
+                };
+
+                // Lazy and suspended tabs are only titled after their question once it's loaded,
+                // it's fetched if it isn't cached.
+                match self.stackexchange_client.cached_title(&site, id) {
+                    Some(title) => {
+                        sender.input(AppInput::ToggleQuestionBookmark { site, id, title })
+                    }
+                    None if !self.bookmarks.contains(&site, id)
+                        && self.unloaded_tabs.borrow().contains_key(&tab_page) =>
+                    {
+                        let client = self.stackexchange_client.clone();
+
+                        relm4::spawn_local(async move {
+                            let title = match client.get_questions(&site, &[id]).await {
+                                Ok(questions) if !questions.is_empty() => {
+                                    questions[0].title.clone()
+                                }
+                                _ => tab_page.title().to_string(),
+                            };
+                            sender.input(AppInput::ToggleQuestionBookmark { site, id, title });
+                        });
+                    }
+                    None => sender.input(AppInput::ToggleQuestionBookmark {
+                        site,
+                        id,
+                        title: tab_page.title().to_string(),
+                    }),
+                }
+            }
+            AppInput::ToggleQuestionBookmark { site, id, title } => {
+                self.bookmarks.toggle(&site, id, &title);
+                self.bookmarks.save();
+                sender.input(AppInput::UpdateSidebar);
+                self.update_question_buttons(widgets);
+            }
+            AppInput::UpdateQuestionButtons => self.update_question_buttons(widgets),
+            AppInput::CopyLink => {
+                let question = self
+                    .active_tab_view(widgets)
+                    .selected_page()
+                    .and_then(|tab_page| self.question_tabs.borrow().get(&tab_page).cloned());
+
+                if let Some((site, id)) = question {
+                    widgets
+                        .tab_view
+                        .clipboard()
+                        .set_text(&stackexchange::question_url(&site, id));
+                    sender.input(AppInput::ShowToast("Link copied to clipboard".to_owned()));
+                }
+            }
+            AppInput::OpenInBrowser => {
+                let question = self
+                    .active_tab_view(widgets)
+                    .selected_page()
+                    .and_then(|tab_page| self.question_tabs.borrow().get(&tab_page).cloned());
+
+                if let Some((site, id)) = question {
+                    if let Err(error) = gtk::gio::AppInfo::launch_default_for_uri(
+                        &stackexchange::question_page_url(&site, id),
+                        None::<&gtk::gio::AppLaunchContext>,
+                    ) {
+                        sender.input(AppInput::ShowToast(format!(
+                            "Failed to open the web browser: {error}"
+                        )));
+                    }
+                }
+            }
+            AppInput::OpenSiteHome => {
+                // Only question tabs know their site, the others open the default one.
+                let site = widgets
+                    .tab_view
+                    .selected_page()
+                    .and_then(|tab_page| self.question_tabs.borrow().get(&tab_page).cloned())
+                    .map_or_else(|| self.settings.default_site.clone(), |(site, _)| site);
+
+                let tab_page = append_home_tab(&widgets.tab_view, &site, sender.input_sender());
+                widgets.tab_view.set_selected_page(&tab_page);
+            }
+            AppInput::ToggleWatch => {
+                let Some(tab_page) = self.active_tab_view(widgets).selected_page() else {
+                    return;
+                };
+                if self.watched_tabs.borrow_mut().remove(&tab_page).is_some() {
+                    sender.input(AppInput::ShowToast(
+                        "Stopped watching for new answers".to_owned(),
+                    ));
+                    sender.input(AppInput::UpdateSidebar);
+                    return;
+                }
+                let Some((site, id)) = self.question_tabs.borrow().get(&tab_page).cloned() else {
+                    return;
+                };
+
+                let client = self.stackexchange_client.clone();
+                let watched_tabs = self.watched_tabs.clone();
+
+                relm4::spawn_local(async move {
+                    // Compared with what the tab shows, when it came from the cache.
+                    let activity =
+                        match client.cached_activity(&site, id) {
+                            Some(activity) => Ok(activity),
+                            None => client.get_question_activity(&site, &[id]).await.and_then(
+                                |activity| {
+                                    activity
+                                        .into_iter()
+                                        .next()
+                                        .ok_or(stackexchange::StackExchangeError::NotFound)
+                                },
+                            ),
+                        };
+
+                    match activity {
+                        Ok(activity) => {
+                            watched_tabs.borrow_mut().insert(tab_page, activity);
+                            sender
+                                .input(AppInput::ShowToast("Watching for new answers".to_owned()));
+                            sender.input(AppInput::UpdateSidebar);
+                        }
+                        Err(error) => sender.input(AppInput::ShowToast(format!(
+                            "Failed to watch the question: {error}"
+                        ))),
+                    }
+                });
+            }
+            AppInput::PollWatchedTabs => {
+                if self.stackexchange_client.is_offline() {
+                    return;
+                }
+
+                // Closed tabs aren't question tabs anymore.
+                let question_tabs = self.question_tabs.borrow().clone();
+                self.watched_tabs
+                    .borrow_mut()
+                    .retain(|tab_page, _| question_tabs.contains_key(tab_page));
+
+                // One request per site.
+                let mut sites: HashMap<String, Vec<stackexchange::Id>> = HashMap::new();
+                for tab_page in self.watched_tabs.borrow().keys() {
+                    let (site, id) = &question_tabs[tab_page];
+                    sites.entry(site.clone()).or_default().push(*id);
+                }
+
+                // The sites are checked at once, up to the limit of concurrent requests.
+                let client = self.stackexchange_client.clone();
+                let question_tabs = self.question_tabs.clone();
+                let watched_tabs = self.watched_tabs.clone();
+
+                relm4::spawn_local(async move {
+                    let activities =
+                        futures::future::join_all(sites.into_iter().map(|(site, ids)| {
+                            let client = &client;
+                            async move {
+                                let activity = client.get_question_activity(&site, &ids).await;
+                                (site, activity)
+                            }
+                        }))
+                        .await;
+
+                    for (site, activity) in activities {
+                        let activity = match activity {
+                            Ok(activity) => activity,
+                            Err(error) => {
+                                sender.input(AppInput::ShowToast(format!(
+                                    "Failed to check the watched questions of {site}: {error}"
+                                )));
+                                continue;
+                            }
+                        };
+
+                        // Tabs might have been closed or watched in the meantime.
+                        let question_tabs = question_tabs.borrow();
+                        for (tab_page, watched) in watched_tabs.borrow_mut().iter_mut() {
+                            let Some((tab_site, _)) = question_tabs.get(tab_page) else {
+                                continue;
+                            };
+                            if *tab_site != site {
+                                continue;
+                            }
+                            let Some(current) = activity
+                                .iter()
+                                .find(|current| current.question_id == watched.question_id)
+                            else {
+                                continue;
+                            };
+                            if current == watched {
+                                continue;
+                            }
+
+                            if !tab_page.is_selected() {
+                                tab_page.set_needs_attention(true);
+                            }
+                            if let Some(banner) = tab_banner(tab_page) {
+                                banner.set_title(if current.answer_count > watched.answer_count {
+                                    "New answers were posted"
+                                } else {
+                                    "This question has new activity"
+                                });
+                                banner.set_revealed(true);
+                            }
+                            // Only told once about every change.
+                            *watched = current.clone();
+                        }
+                    }
+                });
+            }
+            AppInput::RefreshTab(tab_page) => {
+                let Some(tab_page) = tab_page.upgrade() else {
+                    return;
+                };
+                let Some((site, id)) = self.question_tabs.borrow().get(&tab_page).cloned() else {
+                    return;
+                };
+
+                let scroll_position = tab_scrolled_window(&tab_page)
+                    .map_or(0.0, |scrolled_window| scrolled_window.vadjustment().value());
+                if let Some(bin) = tab_page.child().downcast_ref::<adw::Bin>() {
+                    bin.set_child(Some(&componant_builders::st_loading_page(Some(
+                        &tab_page.title(),
+                    ))));
+                }
+                tab_page.set_loading(true);
+
+                let uri = stackexchange::UriLink::Questions {
+                    site,
+                    question_ids: vec![id],
+                    answer_id: None,
+                }
+                .to_uri();
+                self.load_questions(
+                    widgets,
+                    &uri,
+                    &tab_page,
+                    true,
+                    AfterLoad {
+                        scroll_position: Some(scroll_position),
+                        ..AfterLoad::default()
+                    },
+                    &sender,
+                );
+            }
+            AppInput::RefreshSelectedTab => {
+                if let Some(tab_page) = self.active_tab_view(widgets).selected_page() {
+                    sender.input(AppInput::RefreshTab(tab_page.downgrade().into()));
+                }
+            }
+            AppInput::SetAutoRefresh(minutes) => {
+                let Some(tab_page) = self.active_tab_view(widgets).selected_page() else {
+                    return;
+                };
+                if !self.question_tabs.borrow().contains_key(&tab_page) {
+                    return;
+                }
+
+                if let Some(source_id) = self.auto_refresh_tabs.borrow_mut().remove(&tab_page) {
+                    source_id.remove();
+                }
+                if minutes == 0 {
+                    sender.input(AppInput::ShowToast(
+                        "Stopped refreshing automatically".to_owned(),
+                    ));
+                    return;
+                }
+
+                let weak_tab_page: gtk::glib::SendWeakRef<adw::TabPage> =
+                    tab_page.downgrade().into();
+                let source_id = gtk::glib::timeout_add_seconds_local(
+                    minutes * 60,
+                    gtk::glib::clone!(@strong sender => move || {
+                        sender.input(AppInput::AutoRefreshTab(weak_tab_page.clone()));
+                        gtk::glib::Continue(true)
+                    }),
+                );
+                self.auto_refresh_tabs
+                    .borrow_mut()
+                    .insert(tab_page, source_id);
+
+                sender.input(AppInput::ShowToast(if minutes == 60 {
+                    "Refreshing every hour".to_owned()
+                } else {
+                    format!("Refreshing every {minutes} minutes")
+                }));
+            }
+            AppInput::AutoRefreshTab(tab_page) => {
+                let Some(tab_page) = tab_page.upgrade() else {
+                    return;
+                };
+                let question = self.question_tabs.borrow().get(&tab_page).cloned();
+                // It doesn't show a question anymore.
+                let Some((site, id)) = question else {
+                    if let Some(source_id) = self.auto_refresh_tabs.borrow_mut().remove(&tab_page) {
+                        source_id.remove();
+                    }
+                    return;
+                };
+                if self.stackexchange_client.is_offline() || tab_page.is_loading() {
+                    return;
+                }
+
+                let client = self.stackexchange_client.clone();
+
+                relm4::spawn_local(async move {
+                    // Unchanged questions aren't loaded again, to spare the quota and the reader.
+                    // Checked again on the next tick if it fails.
+                    let Ok(activity) = client.get_question_activity(&site, &[id]).await else {
+                        return;
+                    };
+                    // Deleted questions are refreshed too, to tell it.
+                    if activity.first() != client.cached_activity(&site, id).as_ref() {
+                        sender.input(AppInput::RefreshTab(tab_page.downgrade().into()));
+                    }
+                });
+            }
+            AppInput::ShowToast(message) => {
+                widgets.toast_overlay.add_toast(adw::Toast::new(&message));
+            }
+            AppInput::OpenBookmarks => {
+                let bookmarks_box =
+                    componant_builders::st_bookmarks(&self.bookmarks, sender.input_sender());
+
+                let tab_page = widgets.tab_view.append(
+                    &gtk::ScrolledWindow::builder()
+                        .child(&bookmarks_box)
+                        .vexpand(true)
+                        .hexpand(true)
+                        .build(),
+                );
+                tab_page.set_title("Bookmarks");
+                widgets.tab_view.set_selected_page(&tab_page);
+            }
+            AppInput::SetBookmarkFolder {
+                site,
+                question_id,
+                folder,
+            } => {
+                self.bookmarks.set_folder(&site, question_id, folder);
+                self.bookmarks.save();
+            }
+            AppInput::RemoveBookmark { site, question_id } => {
+                self.bookmarks.remove(&site, question_id);
+                self.bookmarks.save();
+                self.update_question_buttons(widgets);
+                sender.input(AppInput::UpdateSidebar);
+            }
+            AppInput::OpenSubscriptions => {
+                let subscriptions_box =
+                    componant_builders::st_subscriptions(&self.feed_entries, sender.input_sender());
+
+                let tab_page = widgets.tab_view.append(
+                    &gtk::ScrolledWindow::builder()
+                        .child(&subscriptions_box)
+                        .vexpand(true)
+                        .hexpand(true)
+                        .build(),
+                );
+                tab_page.set_title("Subscriptions");
+                widgets.tab_view.set_selected_page(&tab_page);
+            }
+            AppInput::SetNotifySubscriptions(notify) => {
+                self.settings.notify_subscriptions = notify;
+                self.settings.save();
+            }
+            AppInput::SetRestoreSession(restore) => {
+                self.settings.restore_session = restore;
+                self.restore_session.set(restore);
+                self.settings.save();
+            }
+            AppInput::SetSuspendTabsAfter(minutes) => {
+                self.settings.suspend_tabs_after = minutes;
+                self.settings.save();
+            }
+            AppInput::SetRecentTabCycling(recent) => {
+                self.settings.recent_tab_cycling = recent;
+                self.settings.save();
+            }
+            AppInput::SetDefaultSite(site) => {
+                // Aliases like `au` are expanded, an empty site restores the default one.
+                self.settings.default_site = match stackexchange::site_from_alias(&site) {
+                    Some(site) => site,
+                    None if site.is_empty() => Settings::default().default_site,
+                    None => site,
+                };
+                self.home_site.replace(self.settings.default_site.clone());
+                self.settings.save();
+            }
+            AppInput::SetTheme(theme) => {
+                apply_theme(theme);
+                self.settings.theme = theme;
+                self.settings.save();
+            }
+            AppInput::SetNarrow(narrow) => {
+                // Tabs are still reachable from the tabs overview button.
+                self.narrow = narrow;
+                widgets.tab_bar.set_visible(!narrow && !self.reading_mode);
+                self.update_subtitle(widgets);
+                // Both are in the tab menu too.
+                widgets.browser_button.set_visible(!narrow);
+                widgets.copy_link_button.set_visible(!narrow);
+                self.show_quota(widgets);
+            }
+            AppInput::SplitTab => {
+                let question = widgets
+                    .tab_view
+                    .selected_page()
+                    .and_then(|tab_page| self.question_tabs.borrow().get(&tab_page).cloned());
+                let Some((site, id)) = question else {
+                    sender.input(AppInput::ShowToast(
+                        "Only questions can be shown in the split view".to_owned(),
+                    ));
+                    return;
+                };
+
+                let provider = self.question_provider();
+                let answer_sort = self.settings.answer_sort;
+                let authenticated = self.stackexchange_client.is_authenticated();
+                let split_view = widgets.split_view.clone();
+
+                relm4::spawn_local(async move {
+                    // Usually answered from the cache, the tab has just loaded it.
+                    let question = match provider.get_questions(&site, &[id]).await {
+                        Ok(mut questions) if !questions.is_empty() => questions.remove(0),
+                        Ok(_) => return,
+                        Err(error) => {
+                            sender.input(AppInput::ShowToast(format!(
+                                "Failed to show the question in the split view: {error}"
+                            )));
+                            return;
+                        }
+                    };
+
+                    let question_box = componant_builders::st_question(
+                        &question,
+                        None,
+                        answer_sort,
+                        authenticated,
+                        sender.input_sender(),
+                    );
+                    let pane = componant_builders::st_split_pane(
+                        &question_box,
+                        &question.title,
+                        sender.input_sender(),
+                    );
+
+                    // Starts in the middle.
+                    let width = split_view.width();
+                    split_view.set_end_child(Some(&pane));
+                    split_view.set_position(width / 2);
+                });
+            }
+            AppInput::CloseSplitView => {
+                widgets.split_view.set_end_child(None::<&gtk::Widget>);
+            }
+            AppInput::ToggleSidebar => {
+                widgets
+                    .sidebar
+                    .set_reveal_flap(!widgets.sidebar.reveals_flap());
+            }
+            AppInput::UpdateSidebar => {
+                if !widgets.sidebar.reveals_flap() {
+                    return;
+                }
+
+                let history = self
+                    .history
+                    .borrow()
+                    .entries
+                    .iter()
+                    .take(SIDEBAR_HISTORY_ENTRIES)
+                    .map(|entry| {
+                        sidebar_question_entry(&entry.site, entry.question_id, &entry.title)
+                    })
+                    .collect();
+                let bookmarks = self
+                    .bookmarks
+                    .items
+                    .iter()
+                    .map(|bookmark| {
+                        sidebar_question_entry(
+                            &bookmark.site,
+                            bookmark.question_id,
+                            &bookmark.title,
+                        )
+                    })
+                    .collect();
+
+                // Watched tabs, then subscribed tags.
+                let question_tabs = self.question_tabs.borrow();
+                let watched_tabs = self.watched_tabs.borrow();
+                let watched = watched_tabs.keys().filter_map(|tab_page| {
+                    let (site, _) = question_tabs.get(tab_page)?;
+                    Some(componant_builders::PaletteEntry {
+                        title: tab_page.title().to_string(),
+                        subtitle: site.clone(),
+                        message: AppInput::FocusTab(tab_page.downgrade().into()),
+                    })
+                });
+                let subscribed = self.settings.subscriptions.iter().map(|subscription| {
+                    componant_builders::PaletteEntry {
+                        title: format!("[{}]", subscription.tag),
+                        subtitle: subscription.site.clone(),
+                        message: AppInput::OpenTag {
+                            site: subscription.site.clone(),
+                            tag: subscription.tag.clone(),
+                        },
+                    }
+                });
+                let watching = watched.chain(subscribed).collect();
+
+                for (name, entries, placeholder) in [
+                    ("history", history, "No visited questions"),
+                    ("bookmarks", bookmarks, "No bookmarks"),
+                    (
+                        "watching",
+                        watching,
+                        "No watched questions or subscribed tags",
+                    ),
+                ] {
+                    if let Some(page) = widgets
+                        .sidebar_stack
+                        .child_by_name(name)
+                        .and_downcast::<gtk::ScrolledWindow>()
+                    {
+                        page.set_child(Some(&componant_builders::st_sidebar_list(
+                            entries,
+                            placeholder,
+                            sender.input_sender(),
+                        )));
+                    }
+                }
+            }
+            AppInput::OpenQuestion { site, id } => {
+                match self.question_tab(widgets, &site, id) {
+                    Some(tab_page) => widgets.tab_view.set_selected_page(&tab_page),
+                    None => sender.input(AppInput::RequestPagesByUri(
+                        stackexchange::UriLink::Questions {
+                            site,
+                            question_ids: vec![id],
+                            answer_id: None,
+                        }
+                        .to_uri(),
+                    )),
+                }
+                // A folded sidebar covers the tabs.
+                if widgets.sidebar.is_folded() {
+                    widgets.sidebar.set_reveal_flap(false);
+                }
+            }
+            AppInput::ShowCommandPalette => {
+                let mut entries = Vec::new();
+                menu_palette_entries(widgets.main_menu.upcast_ref(), "", &mut entries);
+                menu_palette_entries(widgets.tab_menu.upcast_ref(), "Tab", &mut entries);
+                entries.extend(
+                    PALETTE_ACTIONS
+                        .iter()
+                        .map(|(title, action)| palette_entry(title, action, None)),
+                );
+
+                componant_builders::st_palette(
+                    root.upcast_ref(),
+                    "Run a command",
+                    entries,
+                    sender.input_sender(),
+                )
+                .present();
+            }
+            AppInput::ActivateAction { name, target } => {
+                if let Err(error) = root.activate_action(&name, target.as_ref()) {
+                    sender.input(AppInput::ShowToast(format!(
+                        "Failed to activate {name}: {error}"
+                    )));
+                }
+            }
+            AppInput::ToggleReadingMode => {
+                self.reading_mode = !self.reading_mode;
+                if self.reading_mode {
+                    root.fullscreen();
+                } else {
+                    root.unfullscreen();
+                }
+
+                if self.reading_mode {
+                    widgets.sidebar.set_reveal_flap(false);
+                }
+                // Along with the progress bar over it.
+                if let Some(header_overlay) = widgets.header.parent() {
+                    header_overlay.set_visible(!self.reading_mode);
+                }
+                widgets
+                    .tab_bar
+                    .set_visible(!self.narrow && !self.reading_mode);
+                widgets.reading_mode_button.set_visible(self.reading_mode);
+
+                for position in 0..widgets.tab_view.n_pages() {
+                    if let Some(clamp) = tab_clamp(&widgets.tab_view.nth_page(position)) {
+                        set_reading_width(&clamp, self.reading_mode);
+                    }
+                }
+            }
+            AppInput::SetContentFont(font) => {
+                self.settings.content_font = font;
+                load_content_css(&self.content_css, &self.settings);
+                self.settings.save();
+            }
+            AppInput::SetCodeFont(font) => {
+                self.settings.code_font = font;
+                load_content_css(&self.content_css, &self.settings);
+                self.settings.save();
+            }
+            AppInput::SetDefaultZoom(zoom) => {
+                // Tabs that weren't zoomed follow it.
+                self.settings.zoom = zoom;
+                load_content_css(&self.content_css, &self.settings);
+                self.settings.save();
+            }
+            AppInput::ZoomTab(steps) => {
+                let Some(selected_page) = self.active_tab_view(widgets).selected_page() else {
+                    return;
+                };
+
+                let zoom = match steps {
+                    0 => None,
+                    steps => {
+                        let zoom = tab_zoom(&selected_page).unwrap_or(self.settings.zoom);
+                        // Zoom levels are multiples of the step, like the CSS classes.
+                        let zoom = (zoom / ZOOM_STEP) as i32 + steps;
+                        Some((zoom.max(0) as u32 * ZOOM_STEP).clamp(MIN_ZOOM, MAX_ZOOM))
+                    }
+                };
+                set_tab_zoom(&selected_page, zoom);
+            }
+            AppInput::SetAnswerSort(sort) => {
+                // Applies to questions opened from now on.
+                self.settings.answer_sort = sort;
+                self.settings.save();
+            }
+            AppInput::SetApiKey(api_key) => {
+                self.stackexchange_client.set_api_key(api_key.clone());
+                self.settings.api_key = api_key;
+                self.settings.save();
+            }
+            AppInput::SubmitSearchTerm(term) => {
+                if !term.is_empty() && !self.private.get() {
+                    let mut history = self.history.borrow_mut();
+                    history.record_search(&term);
+                    history.save();
+                }
+                if let Some(input) = parse_search_term(&term, &self.settings.default_site) {
+                    sender.input(input);
+                }
+            }
+            AppInput::PasteLink => {
+                let links = match widgets.tab_view.clipboard().read_text_future().await {
+                    Ok(Some(text)) => stackexchange_links(text.split_whitespace()),
+                    _ => Vec::new(),
+                };
+
+                if links.is_empty() {
+                    sender.input(AppInput::ShowToast(
+                        "No StackExchange link in the clipboard".to_owned(),
+                    ));
+                }
+                for link in links {
+                    sender.input(AppInput::RequestPagesByUri(link));
+                }
+            }
+            AppInput::SaveRecovery => {
+                // Nothing is written in private mode.
+                if self.private.get() {
+                    return;
+                }
+
+                let tabs = session_tabs(
+                    &widgets.tab_view,
+                    &self.tab_windows.borrow(),
+                    &self.question_tabs.borrow(),
+                    &self.unloaded_tabs.borrow(),
+                );
+                if tabs != self.recovery_tabs {
+                    Session {
+                        tabs: tabs.clone(),
+                        restore: true,
+                    }
+                    .save_recovery();
+                    self.recovery_tabs = tabs;
+                }
+            }
+            AppInput::RestoreSession(tabs) => {
+                // Tabs are only loaded once they're selected, so many of them open right away.
+                // Detached windows by their number in the session.
+                let mut tab_windows: HashMap<u32, adw::TabView> = HashMap::new();
+                for tab in tabs {
+                    let tab_view = match tab.window {
+                        0 => widgets.tab_view.clone(),
+                        window => tab_windows
+                            .entry(window)
+                            .or_insert_with(|| {
+                                new_tab_window(&self.tab_window_context(
+                                    &widgets.tab_menu,
+                                    &widgets.tab_actions,
+                                    &sender,
+                                ))
+                            })
+                            .clone(),
+                    };
+
+                    let tab_page = self.insert_unloaded_tab(
+                        &tab_view,
+                        tab_view.n_pages(),
+                        &tab.site,
+                        tab.question_id,
+                        UnloadedTab {
+                            scroll_position: tab.scroll_position,
+                            answer_id: tab.answer_id,
+                        },
+                    );
+                    if tab.pinned {
+                        tab_view.set_page_pinned(&tab_page, true);
+                    }
+                }
+
+                if widgets.tab_view.n_pages() == 0 {
+                    sender.input(AppInput::OpenHome);
+                }
+            }
+            AppInput::SuspendIdleTabs => {
+                // Questions aren't cached in private mode, they would be requested again.
+                if self.private.get() || self.settings.suspend_tabs_after == 0 {
+                    return;
+                }
+
+                let question_tabs = self.question_tabs.borrow().clone();
+                self.idle_tabs
+                    .retain(|tab_page, _| question_tabs.contains_key(tab_page));
+
+                for tab_page in question_tabs.keys() {
+                    // Loading tabs have nothing to drop yet.
+                    if self.unloaded_tabs.borrow().contains_key(tab_page)
+                        || self.pending_requests.borrow().contains_key(tab_page)
+                    {
+                        self.idle_tabs.remove(tab_page);
+                        continue;
+                    }
+
+                    let idle = self.idle_tabs.entry(tab_page.clone()).or_insert(0);
+                    if tab_page.is_selected() {
+                        *idle = 0;
+                        continue;
+                    }
+                    *idle += SUSPEND_INTERVAL / 60;
+
+                    if *idle < self.settings.suspend_tabs_after {
+                        continue;
+                    }
+                    self.idle_tabs.remove(tab_page);
+                    self.suspend_tab(tab_page);
+                }
+            }
+            AppInput::LoadTab(tab_page) => {
+                let Some(tab_page) = tab_page.upgrade() else {
+                    return;
+                };
+                let Some(unloaded) = self.unloaded_tabs.borrow_mut().remove(&tab_page) else {
+                    return;
+                };
+                let Some((site, id)) = self.question_tabs.borrow().get(&tab_page).cloned() else {
+                    return;
+                };
+
+                tab_page.set_loading(true);
+                let uri = stackexchange::UriLink::Questions {
+                    site,
+                    question_ids: vec![id],
+                    answer_id: None,
+                }
+                .to_uri();
+                // Continues where it was left.
+                self.load_questions(
+                    widgets,
+                    &uri,
+                    &tab_page,
+                    false,
+                    AfterLoad {
+                        scroll_position: Some(unloaded.scroll_position),
+                        answer_id: unloaded.answer_id,
+                        ..AfterLoad::default()
+                    },
+                    &sender,
+                );
+            }
+            AppInput::OpenRevisions { site, id, title } => {
+                let client = self.stackexchange_client.clone();
+                let revisions = {
+                    let site = site.clone();
+                    async move { client.get_revisions(&site, id).await }
+                };
+
+                self.open_loading_tab(
+                    widgets,
+                    &format!("History: {title}"),
+                    &site,
+                    revisions,
+                    move |revisions, _tab_page| {
+                        componant_builders::st_revisions(&title, &revisions).upcast()
+                    },
+                    &sender,
+                );
+            }
+            AppInput::OpenHome => {
+                let tab_page = append_home_tab(
+                    &widgets.tab_view,
+                    &self.settings.default_site,
+                    sender.input_sender(),
+                );
+                widgets.tab_view.set_selected_page(&tab_page);
+            }
+            AppInput::BrowseTags { site, tags } => {
+                let browser_box =
+                    componant_builders::st_tag_browser(&site, &tags, sender.input_sender());
+
+                let tab_page = widgets.tab_view.append(
+                    &gtk::ScrolledWindow::builder()
+                        .child(&browser_box)
+                        .vexpand(true)
+                        .hexpand(true)
+                        .build(),
+                );
+                tab_page.set_title(
+                    &tags
+                        .iter()
+                        .map(|tag| format!("[{tag}]"))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                );
+                widgets.tab_view.set_selected_page(&tab_page);
+                self.set_site_icon(&tab_page, &site);
+            }
+            AppInput::LoadTaggedQuestions {
+                site,
+                tags,
+                sort,
+                section,
+            } => {
+                let client = self.stackexchange_client.clone();
+
+                relm4::spawn_local(async move {
+                    let questions = client
+                        .get_tagged_questions(&stackexchange::site_parameter(&site), &tags, sort)
+                        .await;
+
+                    // The tab might be closed in the meantime.
+                    if let Some(section) = section.upgrade() {
+                        componant_builders::fill_questions_section(
+                            &section,
+                            questions,
+                            sender.input_sender(),
+                        );
+                    }
+                });
+            }
+            AppInput::AddComment {
+                site,
+                question_id,
+                post_id,
+                body,
+                composer,
+            } => {
+                let client = self.stackexchange_client.clone();
+
+                relm4::spawn_local(async move {
+                    let comment = client.add_comment(&site, question_id, post_id, &body).await;
+
+                    if let Err(error) = &comment {
+                        show_request_error(
+                            "Adding the comment failed",
+                            error,
+                            sender.input_sender(),
+                        );
+                    }
+
+                    // The tab might be closed in the meantime.
+                    if let Some(composer) = composer.upgrade() {
+                        componant_builders::finish_comment(&composer, comment.as_ref().ok());
+                    }
+                });
+            }
+            AppInput::OpenFavorites => {
+                // Of the site of the selected question tab, or the default one.
+                let site = widgets
+                    .tab_view
+                    .selected_page()
+                    .and_then(|tab_page| self.question_tabs.borrow().get(&tab_page).cloned())
+                    .map_or_else(|| self.settings.default_site.clone(), |(site, _)| site);
+
+                let client = self.stackexchange_client.clone();
+
+                relm4::spawn_local(async move {
+                    sender.input(AppInput::FavoritesLoaded(client.get_favorites(&site).await));
+                });
+            }
+            AppInput::FavoritesLoaded(favorites) => match favorites {
+                Ok(questions) => {
+                    self.open_question_tabs(widgets, &questions, None, None, &sender);
+                }
+                Err(error) => {
+                    show_request_error("Loading favorites failed", &error, sender.input_sender());
+                }
+            },
+        }
+
+        // Every response reports the remaining quota.
+        self.quota = self.stackexchange_client.quota();
+        self.show_quota(widgets);
+    }
+
+    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {}
+}
+
+/// What to open for the text of the search entry: links, question ids or search terms. Ids and
+/// search terms can be prefixed with a site, like `so:12345` or `au:wifi driver`.
+fn parse_search_term(term: &str, default_site: &str) -> Option<AppInput> {
+    // Pasted links, like https://stackoverflow.com/questions/123456/some-title
+    if let Ok(link) = stackexchange::parse_uri(term) {
+        return Some(AppInput::RequestPagesByUri(link.to_uri()));
+    }
+
+    let (site, term) = term
+        .split_once(':')
+        .and_then(|(prefix, term)| Some((stackexchange::site_from_alias(prefix)?, term.trim())))
+        .unwrap_or_else(|| (default_site.to_owned(), term));
+
+    if term.is_empty() {
+        return None;
+    }
+
+    // Only tags, like [rust] [gtk], are browsed instead of searched.
+    let query = SearchQuery::parse(term);
+    if !query.tagged.is_empty()
+        && query.text.is_empty()
+        && query.user.is_none()
+        && query.accepted.is_none()
+    {
+        let mut tags = query.tagged;
+        return Some(match tags.len() {
+            1 => AppInput::OpenTag {
+                site,
+                tag: tags.remove(0),
+            },
+            _ => AppInput::BrowseTags { site, tags },
+        });
+    }
+
+    let ids = term
+        .split([';', ','])
+        .map(|id| id.trim().parse::<stackexchange::Id>())
+        .collect::<Result<Vec<_>, _>>();
+
+    Some(match ids {
+        Ok(question_ids) => AppInput::RequestPagesByUri(
+            stackexchange::UriLink::Questions {
+                site,
+                question_ids,
+                answer_id: None,
+            }
+            .to_uri(),
+        ),
+        Err(_) => AppInput::Search {
+            site,
+            query: term.to_owned(),
+        },
+    })
+}
+
+/// Save the question tabs in their order, other tabs aren't restored. They're restored on the next
+/// launch if `restore`, even if it's disabled. The last session is kept as is in `private` mode.
+fn save_session(
+    tab_view: &adw::TabView,
+    tab_windows: &[adw::TabView],
+    question_tabs: &HashMap<adw::TabPage, (String, stackexchange::Id)>,
+    unloaded_tabs: &HashMap<adw::TabPage, UnloadedTab>,
+    restore: bool,
+    private: bool,
+) {
+    // It's saved on quit, so there's no crash to recover from.
+    Session::remove_recovery();
+    if private {
+        return;
+    }
+
+    Session {
+        tabs: session_tabs(tab_view, tab_windows, question_tabs, unloaded_tabs),
+        restore,
+    }
+    .save();
+}
+
+/// The question tabs of every window, in their order.
+fn session_tabs(
+    tab_view: &adw::TabView,
+    tab_windows: &[adw::TabView],
+    question_tabs: &HashMap<adw::TabPage, (String, stackexchange::Id)>,
+    unloaded_tabs: &HashMap<adw::TabPage, UnloadedTab>,
+) -> Vec<SessionTab> {
+    std::iter::once(tab_view)
+        .chain(tab_windows)
+        .zip(0..)
+        .flat_map(|(tab_view, window)| {
+            (0..tab_view.n_pages()).map(move |position| (tab_view.nth_page(position), window))
+        })
+        .filter_map(|(tab_page, window)| {
+            let (site, question_id) = question_tabs.get(&tab_page)?;
+            let unloaded = unloaded_tabs.get(&tab_page);
+
+            Some(SessionTab {
+                site: site.clone(),
+                question_id: *question_id,
+                pinned: tab_page.is_pinned(),
+                window,
+                scroll_position: tab_scroll_position(&tab_page, unloaded_tabs),
+                answer_id: match unloaded {
+                    Some(unloaded) => unloaded.answer_id,
+                    None => tab_clamp(&tab_page)
+                        .and_then(|clamp| clamp.child())
+                        .and_then(|question_box| componant_builders::focused_answer(&question_box)),
+                },
+            })
+        })
+        .collect()
+}
+
+/// Offer to open the tabs of the last run again, it crashed before saving them.
+fn offer_recovery(
+    toast_overlay: &adw::ToastOverlay,
+    tabs: Vec<SessionTab>,
+    sender: &relm4::Sender<AppInput>,
+) {
+    let toast = adw::Toast::builder()
+        .title(format!(
+            "The last session ended unexpectedly with {} tabs open",
+            tabs.len()
+        ))
+        .button_label("Restore")
+        // Until it's dismissed.
+        .timeout(0)
+        .build();
+    toast.connect_button_clicked(gtk::glib::clone!(@strong sender => move |_| {
+        sender.emit(AppInput::RestoreSession(tabs.clone()));
+    }));
+
+    toast_overlay.add_toast(toast);
+}
+
+/// Remember the size of the main window for the next launch.
+fn save_window_state(window: &adw::Window) {
+    let (width, height) = window.default_size();
+
+    let mut settings = Settings::load();
+    settings.window_width = width;
+    settings.window_height = height;
+    settings.window_maximized = window.is_maximized();
+    settings.save();
+}
+
+/// Tabs of every window that aren't pinned.
+fn unpinned_tabs(tab_view: &adw::TabView, tab_windows: &[adw::TabView]) -> u32 {
+    std::iter::once(tab_view)
+        .chain(tab_windows)
+        .map(|tab_view| (tab_view.n_pages() - tab_view.n_pinned_pages()) as u32)
+        .sum()
+}
+
+/// Offer to save the session before quitting with `unpinned_tabs` that won't be restored. Nothing
+/// is saved in private mode, so it only asks to quit then.
+fn confirm_quit(
+    window: &adw::Window,
+    unpinned_tabs: u32,
+    private: bool,
+    sender: &relm4::Sender<AppInput>,
+) {
+    let dialog = adw::MessageDialog::builder()
+        .transient_for(window)
+        .heading("Quit with open tabs?")
+        .build();
+
+    if private {
+        dialog.set_body("Private mode is on, so none of the tabs will be reopened.");
+        dialog.add_responses(&[("cancel", "Cancel"), ("quit", "Quit")]);
+    } else {
+        dialog.set_body(&format!(
+            "Restoring tabs is disabled, so the {unpinned_tabs} unpinned tabs won't be reopened."
+        ));
+        dialog.add_responses(&[
+            ("cancel", "Cancel"),
+            ("save", "Quit and Save Session"),
+            ("quit", "Quit"),
+        ]);
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+    }
+    dialog.set_default_response(Some("cancel"));
+    dialog.set_close_response("cancel");
+    dialog.set_response_appearance("quit", adw::ResponseAppearance::Destructive);
+
+    dialog.connect_response(
+        None,
+        gtk::glib::clone!(@strong sender => move |_, response| {
+            let restore_session = match response {
+                "save" => true,
+                "quit" => false,
+                _ => return,
+            };
+
+            // Requests and their retries are sent outside of `update`, so it's handled right away.
+            sender.emit(AppInput::Quit { restore_session });
+        }),
+    );
+
+    dialog.present();
+}
+
+/// Force the light or dark style, or follow the system.
+fn apply_theme(theme: Theme) {
+    adw::StyleManager::default().set_color_scheme(match theme {
+        Theme::System => adw::ColorScheme::Default,
+        Theme::Light => adw::ColorScheme::ForceLight,
+        Theme::Dark => adw::ColorScheme::ForceDark,
+    });
+}
+
+/// Family and size of a font for CSS, from a Pango font description.
+struct CssFont {
+    family: Option<String>,
+    // With its unit, `None` to inherit it.
+    size: Option<(f64, &'static str)>,
+}
+
+impl CssFont {
+    fn parse(font: Option<&str>) -> Self {
+        let Some(font) = font.map(gtk::pango::FontDescription::from_string) else {
+            return Self {
+                family: None,
+                size: None,
+            };
+        };
+
+        Self {
+            family: font.family().map(|family| family.to_string()),
+            size: match font.size() {
+                0 => None,
+                size if font.is_size_absolute() => {
+                    Some((size as f64 / gtk::pango::SCALE as f64, "px"))
+                }
+                size => Some((size as f64 / gtk::pango::SCALE as f64, "pt")),
+            },
+        }
+    }
+
+    /// Empty to inherit the family if there is no default.
+    fn family_css(&self, default: Option<&str>) -> String {
+        self.family
+            .as_deref()
+            .or(default)
+            .map(|family| format!("font-family: \"{family}\";"))
+            .unwrap_or_default()
+    }
+
+    /// The size scaled by `zoom`, in percent.
+    fn size_css(size: (f64, &str), zoom: u32) -> String {
+        format!("font-size: {:.1}{};", size.0 * zoom as f64 / 100.0, size.1)
+    }
+}
+
+/// Apply the content and code fonts of `settings`, or the default fonts if they aren't set.
+///
+/// Sizes are scaled by the default zoom, and by the zoom of tabs having a `zoom-{percent}` class,
+/// see `set_tab_zoom`.
+fn load_content_css(provider: &gtk::CssProvider, settings: &Settings) {
+    let content_font = CssFont::parse(settings.content_font.as_deref());
+    let code_font = CssFont::parse(settings.code_font.as_deref());
+
+    // The default is the `large` size of style.css.
+    let content_size = content_font.size.unwrap_or((120.0, "%"));
+
+    let mut css = format!(
+        ".body_buffer {{ {} {} }}\n\
+        frame.code-block label {{ {} {} }}",
+        content_font.family_css(None),
+        CssFont::size_css(content_size, settings.zoom),
+        code_font.family_css(Some("monospace")),
+        code_font
+            .size
+            .map(|size| CssFont::size_css(size, settings.zoom))
+            .unwrap_or_default(),
+    );
+    for zoom in (MIN_ZOOM..=MAX_ZOOM).step_by(ZOOM_STEP as usize) {
+        css.push_str(&format!(
+            "\n.zoom-{zoom} .body_buffer {{ {} }}",
+            CssFont::size_css(content_size, zoom)
+        ));
+        // Code without its own size follows the size of the content around it.
+        if let Some(size) = code_font.size {
+            css.push_str(&format!(
+                "\n.zoom-{zoom} frame.code-block label {{ {} }}",
+                CssFont::size_css(size, zoom)
+            ));
+        }
+    }
+
+    provider.load_from_data(&css);
+}
+
+/// Zoom of a tab in percent, `None` if it follows the default zoom.
+fn tab_zoom(tab_page: &adw::TabPage) -> Option<u32> {
+    tab_page
+        .child()
+        .css_classes()
+        .iter()
+        .find_map(|class| class.strip_prefix("zoom-")?.parse().ok())
+}
+
+/// Zoom the content of a tab, or make it follow the default zoom if `zoom` is `None`.
+fn set_tab_zoom(tab_page: &adw::TabPage, zoom: Option<u32>) {
+    let child = tab_page.child();
+
+    for class in child.css_classes() {
+        if class.starts_with("zoom-") {
+            child.remove_css_class(&class);
+        }
+    }
+    if let Some(zoom) = zoom {
+        child.add_css_class(&format!("zoom-{zoom}"));
+    }
+}
+
+/// Open a window for tabs moved out of the main one, it closes with its last tab. Its tabs have
+/// the same actions and menu as the main window. Returns its tab view to move tabs to.
+fn new_tab_window(context: &TabWindowContext) -> adw::TabView {
+    let tab_view = adw::TabView::builder()
+        .menu_model(&context.tab_menu)
+        .build();
+    let tab_bar = adw::TabBar::builder()
+        .view(&tab_view)
+        .autohide(false)
+        .css_classes(["inline"])
+        .build();
+
+    let header = adw::HeaderBar::new();
+    header.pack_end(
+        &gtk::MenuButton::builder()
+            .icon_name("view-more-symbolic")
+            .tooltip_text("Tab Menu")
+            .menu_model(&context.tab_menu)
+            .build(),
+    );
+
+    let layout = gtk::Box::new(gtk::Orientation::Vertical, 5);
+    layout.append(&header);
+    layout.append(&tab_bar);
+    layout.append(&tab_view);
+
+    let window = adw::Window::builder()
+        .application(&relm4::main_application())
+        .title(APP_NAME)
+        .default_width(800)
+        .default_height(600)
+        .content(&layout)
+        .build();
+    window.insert_action_group("tab", Some(&context.tab_actions));
+
+    // Same cleanup as tabs of the main window.
+    tab_view.connect_close_page(gtk::glib::clone!(@strong context => move |_view, page| {
+        if let Some(handle) = context.pending_requests.borrow_mut().remove(page) {
+            handle.abort();
+        }
+        remember_closed_tab(
+            page,
+            &context.question_tabs.borrow(),
+            &context.unloaded_tabs.borrow(),
+            &context.sender,
+        );
+        context.question_tabs.borrow_mut().remove(page);
+        context.unloaded_tabs.borrow_mut().remove(page);
+        context.watched_tabs.borrow_mut().remove(page);
+        if let Some(source_id) = context.auto_refresh_tabs.borrow_mut().remove(page) {
+            source_id.remove();
+        }
+
+        false
+    }));
+
+    tab_view.connect_create_window(
+        gtk::glib::clone!(@strong context => move |_| Some(new_tab_window(&context))),
+    );
+
+    tab_view.connect_selected_page_notify(
+        gtk::glib::clone!(@strong context.sender as sender => move |view| {
+            if let Some(page) = view.selected_page() {
+                sender.emit(AppInput::LoadTab(page.downgrade().into()));
+            }
+        }),
+    );
+
+    // Once the last tab is closed or moved away. The window owns its tab view, so it's only
+    // referenced weakly.
+    let weak_window = window.downgrade();
+    tab_view.connect_n_pages_notify(move |view| {
+        if view.n_pages() == 0 {
+            if let Some(window) = weak_window.upgrade() {
+                window.close();
+            }
+        }
+    });
+
+    // The tab actions apply to the window focused last.
+    window.connect_is_active_notify(gtk::glib::clone!(
+        @strong tab_view,
+        @strong context.active_tab_view as active_tab_view => move |window| {
+        if window.is_active() {
+            *active_tab_view.borrow_mut() = Some(tab_view.clone());
+        }
+    }));
+
+    window.connect_close_request(gtk::glib::clone!(
+        @strong tab_view,
+        @strong context => move |_| {
+        for position in 0..tab_view.n_pages() {
+            let page = tab_view.nth_page(position);
+            if let Some(handle) = context.pending_requests.borrow_mut().remove(&page) {
+                handle.abort();
+            }
+            context.question_tabs.borrow_mut().remove(&page);
+            context.unloaded_tabs.borrow_mut().remove(&page);
+            context.watched_tabs.borrow_mut().remove(&page);
+            if let Some(source_id) = context.auto_refresh_tabs.borrow_mut().remove(&page) {
+                source_id.remove();
+            }
+        }
+        context.tab_windows.borrow_mut().retain(|other| other != &tab_view);
+        let mut active_tab_view = context.active_tab_view.borrow_mut();
+        if active_tab_view.as_ref() == Some(&tab_view) {
+            active_tab_view.take();
+        }
+
+        gtk::Inhibit(false)
+    }));
+
+    context.tab_windows.borrow_mut().push(tab_view.clone());
+    window.present();
+
+    tab_view
+}
+
+/// Keep a closed question tab to be reopened, other tabs are forgotten.
+fn remember_closed_tab(
+    tab_page: &adw::TabPage,
+    question_tabs: &HashMap<adw::TabPage, (String, stackexchange::Id)>,
+    unloaded_tabs: &HashMap<adw::TabPage, UnloadedTab>,
+    sender: &relm4::Sender<AppInput>,
+) {
+    let Some((site, question_id)) = question_tabs.get(tab_page) else {
+        return;
+    };
+
+    sender.emit(AppInput::RememberClosedTab(ClosedTab {
+        site: site.clone(),
+        question_id: *question_id,
+        title: tab_page.title().to_string(),
+        scroll_position: tab_scroll_position(tab_page, unloaded_tabs),
+    }));
+}
+
+/// How far the tab is scrolled, or will be once it's loaded.
+fn tab_scroll_position(
+    tab_page: &adw::TabPage,
+    unloaded_tabs: &HashMap<adw::TabPage, UnloadedTab>,
+) -> f64 {
+    match unloaded_tabs.get(tab_page) {
+        Some(unloaded) => unloaded.scroll_position,
+        None => tab_scrolled_window(tab_page)
+            .map_or(0.0, |scrolled_window| scrolled_window.vadjustment().value()),
+    }
+}
+
+/// List the closed tabs in the "Recently Closed" menu, picking one reopens it.
+fn update_closed_tabs_menu(menu: &gtk::gio::Menu, closed_tabs: &[ClosedTab]) {
+    menu.remove_all();
+
+    // Without an action it's shown disabled.
+    if closed_tabs.is_empty() {
+        menu.append(Some("No Closed Tabs"), None);
+    }
+
+    for (index, closed_tab) in (0u32..).zip(closed_tabs).take(CLOSED_TABS_MENU_ENTRIES) {
+        let item = gtk::gio::MenuItem::new(Some(&closed_tab.title), None);
+        item.set_action_and_target_value(Some("tab.reopen"), Some(&index.to_variant()));
+        menu.append_item(&item);
+    }
+}
+
+/// A sidebar entry opening the question, or selecting its tab.
+fn sidebar_question_entry(
+    site: &str,
+    id: stackexchange::Id,
+    title: &str,
+) -> componant_builders::PaletteEntry {
+    componant_builders::PaletteEntry {
+        title: title.to_owned(),
+        subtitle: site.to_owned(),
+        message: AppInput::OpenQuestion {
+            site: site.to_owned(),
+            id,
+        },
+    }
+}
+
+/// The tabs of `tab_view` that aren't pinned, in their order.
+fn unpinned_pages(tab_view: &adw::TabView) -> Vec<adw::TabPage> {
+    (tab_view.n_pinned_pages()..tab_view.n_pages())
+        .map(|position| tab_view.nth_page(position))
+        .collect()
+}
+
+/// Whether the tab is in `tab_view`, and not in the view of another window.
+fn tab_view_contains(tab_view: &adw::TabView, tab_page: &adw::TabPage) -> bool {
+    (0..tab_view.n_pages()).any(|position| tab_view.nth_page(position) == *tab_page)
+}
+
+/// Command palette entries for the items of `menu` and its submenus, their titles start with
+/// `prefix` and the labels of the submenus they're in.
+fn menu_palette_entries(
+    menu: &gtk::gio::MenuModel,
+    prefix: &str,
+    entries: &mut Vec<componant_builders::PaletteEntry>,
+) {
+    let string_attribute = |index: i32, attribute: &str| {
+        menu.item_attribute_value(index, attribute, Some(gtk::glib::VariantTy::STRING))
+            .and_then(|value| value.get::<String>())
+    };
+
+    for index in 0..menu.n_items() {
+        let label = string_attribute(index, "label").unwrap_or_default();
+        let title = if prefix.is_empty() {
+            label
+        } else {
+            format!("{prefix}: {label}")
+        };
+
+        if let Some(action) = string_attribute(index, "action") {
+            let target = menu.item_attribute_value(index, "target", None);
+            entries.push(palette_entry(&title, &action, target));
+        }
+        if let Some(submenu) = menu.item_link(index, "submenu") {
+            menu_palette_entries(&submenu, &title, entries);
+        }
+        if let Some(section) = menu.item_link(index, "section") {
+            menu_palette_entries(&section, prefix, entries);
+        }
+    }
+}
+
+/// A command palette entry that activates `action` with `target`, its subtitle is the shortcut.
+fn palette_entry(
+    title: &str,
+    action: &str,
+    target: Option<gtk::glib::Variant>,
+) -> componant_builders::PaletteEntry {
+    let detailed_name = gtk::gio::Action::print_detailed_name(action, target.as_ref());
+    let shortcut = relm4::main_application()
+        .accels_for_action(&detailed_name)
+        .first()
+        .and_then(gtk::accelerator_parse)
+        .map(|(key, modifiers)| gtk::accelerator_get_label(key, modifiers).to_string())
+        .unwrap_or_default();
+
+    componant_builders::PaletteEntry {
+        title: title.to_owned(),
+        subtitle: shortcut,
+        message: AppInput::ActivateAction {
+            name: action.to_owned(),
+            target,
+        },
+    }
+}
+
+/// The scrolled window of a tab. Tabs that were loading have it wrapped in a bin, and question
+/// tabs have it below their find bar.
+fn tab_scrolled_window(tab_page: &adw::TabPage) -> Option<gtk::ScrolledWindow> {
+    let mut widget = Some(tab_page.child());
+
+    while let Some(current) = widget {
+        if let Some(scrolled_window) = current.downcast_ref::<gtk::ScrolledWindow>() {
+            return Some(scrolled_window.clone());
+        }
+        widget = current.last_child();
+    }
+
+    None
+}
+
+/// The clamp around the content of a question tab, inside the viewport of its scrolled window.
+fn tab_clamp(tab_page: &adw::TabPage) -> Option<adw::Clamp> {
+    tab_scrolled_window(tab_page)?
+        .child()?
+        .first_child()
+        .and_downcast::<adw::Clamp>()
+}
+
+/// Center the content at `READING_WIDTH` in reading mode, or let it fill the tab.
+fn set_reading_width(clamp: &adw::Clamp, reading_mode: bool) {
+    let width = if reading_mode {
+        READING_WIDTH
+    } else {
+        i32::MAX
+    };
+    // The child is as wide as the clamp below the threshold.
+    clamp.set_maximum_size(width);
+    clamp.set_tightening_threshold(width);
+}
+
+/// The find bar of a question tab, the first child of its layout.
+fn tab_find_bar(tab_page: &adw::TabPage) -> Option<gtk::SearchBar> {
+    let child = tab_page.child();
+    let layout = match child.downcast_ref::<adw::Bin>() {
+        Some(bin) => bin.child()?,
+        None => child,
+    };
+
+    layout.first_child().and_downcast::<gtk::SearchBar>()
+}
+
+/// The banner of a question tab telling it changed, right below its find bar.
+fn tab_banner(tab_page: &adw::TabPage) -> Option<adw::Banner> {
+    tab_find_bar(tab_page)?
+        .next_sibling()
+        .and_downcast::<adw::Banner>()
+}
+
+/// Scroll to `position` once the content is tall enough, it's empty until the first layout.
+fn restore_scroll_position(scrolled_window: &gtk::ScrolledWindow, position: f64) {
+    let adjustment = scrolled_window.vadjustment();
+    let handler: Rc<RefCell<Option<gtk::glib::SignalHandlerId>>> = Rc::new(RefCell::new(None));
+
+    let handler_id =
+        adjustment.connect_changed(gtk::glib::clone!(@strong handler => move |adjustment| {
+            if adjustment.upper() - adjustment.page_size() < position {
+                return;
+            }
+
+            adjustment.set_value(position);
+            if let Some(handler_id) = handler.borrow_mut().take() {
+                adjustment.disconnect(handler_id);
+            }
+        }));
+    *handler.borrow_mut() = Some(handler_id);
+}
+
+/// Append a home page tab, used at startup and for new tabs.
+fn append_home_tab(
+    tab_view: &adw::TabView,
+    site: &str,
+    sender: &relm4::Sender<AppInput>,
+) -> adw::TabPage {
+    let home_box = componant_builders::st_home_page(site, sender);
+
+    let tab_page = tab_view.append(
+        &gtk::ScrolledWindow::builder()
+            .child(&home_box)
+            .vexpand(true)
+            .hexpand(true)
+            .build(),
+    );
+    tab_page.set_title("Home");
+    tab_page.set_keyword(site);
+    sender.emit(AppInput::LoadSiteIcon {
+        site: site.to_owned(),
+        tab_page: tab_page.downgrade().into(),
+    });
+
+    tab_page
+}
+
+/// The `stackexchange://` uris of the web links or uris to StackExchange, others are skipped.
+fn stackexchange_links<'a>(uris: impl Iterator<Item = &'a str>) -> Vec<String> {
+    uris.filter_map(|uri| stackexchange::parse_uri(uri).ok())
+        .map(|link| link.to_uri())
+        .collect()
+}
+
+/// Open the StackExchange links dropped as files or text, returns false if there were none.
+fn open_dropped_links(value: &gtk::glib::Value, sender: &relm4::Sender<AppInput>) -> bool {
+    let links = if let Ok(files) = value.get::<gtk::gdk::FileList>() {
+        let uris: Vec<String> = files
+            .files()
+            .iter()
+            .map(|file| file.uri().to_string())
+            .collect();
+        stackexchange_links(uris.iter().map(String::as_str))
+    } else if let Ok(text) = value.get::<String>() {
+        stackexchange_links(text.split_whitespace())
+    } else {
+        Vec::new()
+    };
+
+    for link in &links {
+        sender.emit(AppInput::RequestPagesByUri(link.clone()));
+    }
+
+    !links.is_empty()
+}
+
+/// Point at a question that loaded while the user was reading another tab, or another window.
+fn notify_loaded(tab_page: &adw::TabPage, title: &str) {
+    if !tab_page.is_selected() {
+        tab_page.set_needs_attention(true);
+    }
+
+    let window_active = tab_page
+        .child()
+        .root()
+        .and_downcast::<gtk::Window>()
+        .is_some_and(|window| window.is_active());
+    if !window_active {
+        let notification = gtk::gio::Notification::new(APP_NAME);
+        notification.set_body(Some(&format!("Loaded \"{title}\"")));
+        relm4::main_application().send_notification(Some("loaded"), &notification);
+    }
+}
+
+/// Open a tab describing why loading it failed, errors returned by the API are shown like other
+/// failed requests instead.
+fn open_error_tab(
+    tab_view: &adw::TabView,
+    error: &stackexchange::StackExchangeError,
+    sender: &relm4::Sender<AppInput>,
+) {
+    if let stackexchange::StackExchangeError::Api { .. } = error {
+        show_request_error("Loading failed", error, sender);
+        return;
+    }
+
+    let tab_page = tab_view.append(&componant_builders::st_error_page(error));
+    tab_page.set_title("Error");
+    tab_view.set_selected_page(&tab_page);
+}
+
+/// Show a toast for a failed request, or a modal dialog offering to log in again when the session
+/// expired.
+fn show_request_error(
+    heading: &str,
+    error: &stackexchange::StackExchangeError,
+    sender: &relm4::Sender<AppInput>,
+) {
+    if let stackexchange::StackExchangeError::Api { kind, .. } = error {
+        if kind.needs_login() {
+            let error_message = adw::MessageDialog::builder()
+                .transient_for(&relm4::main_application().active_window().unwrap())
+                .heading(heading)
+                .body(componant_builders::error_description(error))
+                .build();
+            error_message.add_response("cancel", "Cancel");
+            error_message.add_response("login", "Log In");
+            error_message.set_response_appearance("login", adw::ResponseAppearance::Suggested);
+            error_message.connect_response(
+                Some("login"),
+                gtk::glib::clone!(@strong sender => move |_, _| {
+                    sender.emit(AppInput::Login);
+                }),
+            );
+            error_message.present();
+            return;
+        }
+    }
+
+    sender.emit(AppInput::ShowToast(format!("{heading}: {error}")));
+}
+
+/// Show a modal dialog for errors that don't belong to a tab.
+fn show_error_dialog(heading: &str, body: &str) {
+    let error_message = adw::MessageDialog::builder()
+        .transient_for(&relm4::main_application().active_window().unwrap())
+        .heading(heading)
+        .body(body)
+        .build();
+    error_message.add_response("ok", "OK");
+    error_message.present();
+}
+
+impl AppModel {
+    /// Show the icon of `site` on a tab once it's downloaded, tabs without it are still usable so
+    /// errors are ignored.
+    fn set_site_icon(&self, tab_page: &adw::TabPage, site: &str) {
+        let client = self.stackexchange_client.clone();
+        let tab_page = tab_page.clone();
+        let site = site.to_owned();
+
+        relm4::spawn_local(async move {
+            if let Ok(path) = client.get_site_icon(&site).await {
+                let icon = gtk::gio::FileIcon::new(&gtk::gio::File::for_path(path));
+                tab_page.set_icon(Some(&icon));
+            }
+        });
+    }
+
+    /// Append a selected tab that shows a loading page until `content` is loaded, then the page
+    /// `build` makes of it. Closing the tab aborts the request, errors are shown in another tab.
+    fn open_loading_tab<T: 'static>(
+        &self,
+        widgets: &AppWidgets,
+        title: &str,
+        site: &str,
+        content: impl std::future::Future<Output = Result<T, stackexchange::StackExchangeError>>
+            + 'static,
+        build: impl FnOnce(T, &adw::TabPage) -> gtk::Widget + 'static,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let loading_page = componant_builders::st_loading_page(Some(title));
+        loading_page.set_title("Loading…");
+        let bin = adw::Bin::builder().child(&loading_page).build();
+
+        let tab_page = widgets.tab_view.append(&bin);
+        tab_page.set_title(title);
+        tab_page.set_keyword(site);
+        tab_page.set_loading(true);
+        widgets.tab_view.set_selected_page(&tab_page);
+        self.set_site_icon(&tab_page, site);
+
+        let (handle, registration) = AbortHandle::new_pair();
+        self.pending_requests
+            .borrow_mut()
+            .insert(tab_page.clone(), handle);
+        self.show_progress(widgets);
+
+        let pending_requests = self.pending_requests.clone();
+        let tab_view = widgets.tab_view.clone();
+        let sender = sender.input_sender().clone();
+
+        relm4::spawn_local(async move {
+            // Nothing to show if it was aborted.
+            let Ok(content) = Abortable::new(content, registration).await else {
+                return;
+            };
+            pending_requests.borrow_mut().remove(&tab_page);
+
+            match content {
+                Ok(content) => {
+                    bin.set_child(Some(
+                        &gtk::ScrolledWindow::builder()
+                            .child(&build(content, &tab_page))
+                            .vexpand(true)
+                            .hexpand(true)
+                            .build(),
+                    ));
+                    tab_page.set_loading(false);
+                }
+                Err(error) => {
+                    open_error_tab(&tab_view, &error, &sender);
+                    tab_view.close_page(&tab_page);
                 }
             }
-            AppInput::ShowAboutWindow => {
-                let developers: Vec<&str> = env!("CARGO_PKG_AUTHORS").split(':').collect();
+        });
+    }
 
-                let windowing_backend_name = match gtk::gdk::Display::default() {
-                    Some(display) => {
-                        match display.backend() {
-                            gtk::gdk::Backend::Wayland => "Wayland".to_owned(),
-                            gtk::gdk::Backend::X11 => "X11".to_owned(),
-                            // When unsupported windowing system is used: win32, macos, broadway.
-                            _ => "Unsupported".to_owned(),
-                        }
-                    }
-                    None => "Undetected".to_owned(),
+    /// Open a question in a new tab at the end, scrolled to `scroll_position`.
+    fn reopen_question(
+        &self,
+        widgets: &AppWidgets,
+        site: &str,
+        id: stackexchange::Id,
+        scroll_position: f64,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let placeholder = self.append_placeholder(&widgets.tab_view, Some(site), Some(id));
+        widgets.tab_view.set_selected_page(&placeholder);
+
+        let uri = stackexchange::UriLink::Questions {
+            site: site.to_owned(),
+            question_ids: vec![id],
+            answer_id: None,
+        }
+        .to_uri();
+        // Usually answered from the cache of unchanged responses.
+        self.load_questions(
+            widgets,
+            &uri,
+            &placeholder,
+            false,
+            AfterLoad {
+                scroll_position: Some(scroll_position),
+                ..AfterLoad::default()
+            },
+            sender,
+        );
+    }
+
+    /// Placeholder tab until the questions are loaded, it's replaced by the first one. It's titled
+    /// from the cache if the question was opened before. Closing it aborts the request.
+    fn append_placeholder(
+        &self,
+        tab_view: &adw::TabView,
+        site: Option<&str>,
+        id: Option<stackexchange::Id>,
+    ) -> adw::TabPage {
+        // Titles of questions opened before are known without any request.
+        let cached_title = site
+            .zip(id)
+            .and_then(|(site, id)| self.stackexchange_client.cached_title(site, id));
+
+        let placeholder = tab_view.append(
+            &adw::Bin::builder()
+                .child(&componant_builders::st_loading_page(
+                    cached_title.as_deref(),
+                ))
+                .build(),
+        );
+        placeholder.set_title(cached_title.as_deref().unwrap_or("Loading…"));
+        placeholder.set_loading(true);
+        // The icon is downloaded with the question if it's not cached yet.
+        if let Some(path) = site.and_then(|site| self.stackexchange_client.cached_site_icon(site)) {
+            let icon = gtk::gio::FileIcon::new(&gtk::gio::File::for_path(path));
+            placeholder.set_icon(Some(&icon));
+        }
+
+        placeholder
+    }
+
+    /// Open the questions of `uri` in new tabs, without leaving the selected tab if `background`.
+    fn request_pages(
+        &self,
+        widgets: &AppWidgets,
+        uri: &str,
+        background: bool,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        if let Ok(stackexchange::UriLink::Search { site, query }) = stackexchange::parse_uri(uri) {
+            sender.input(AppInput::Search { site, query });
+            return;
+        }
+
+        // Only the first of several questions is loaded now, the others once their tab is selected.
+        let (uri, unloaded_ids) = match stackexchange::parse_uri(uri) {
+            Ok(stackexchange::UriLink::Questions {
+                site,
+                question_ids,
+                answer_id,
+            }) if question_ids.len() > 1 => {
+                let first = stackexchange::UriLink::Questions {
+                    site: site.clone(),
+                    question_ids: vec![question_ids[0]],
+                    answer_id,
+                }
+                .to_uri();
+                (first, Some((site, question_ids[1..].to_vec())))
+            }
+            _ => (uri.to_owned(), None),
+        };
+        let uri = uri.as_str();
+
+        let (site, id) = match stackexchange::parse_uri(uri) {
+            Ok(stackexchange::UriLink::Questions {
+                site, question_ids, ..
+            }) => (Some(site), question_ids.first().copied()),
+            Ok(stackexchange::UriLink::Answer { site, .. }) => (Some(site), None),
+            _ => (None, None),
+        };
+
+        let placeholder = self.append_placeholder(&widgets.tab_view, site.as_deref(), id);
+        if let Some((site, ids)) = unloaded_ids {
+            for (offset, id) in (1..).zip(ids) {
+                self.insert_unloaded_tab(
+                    &widgets.tab_view,
+                    widgets.tab_view.page_position(&placeholder) + offset,
+                    &site,
+                    id,
+                    UnloadedTab::default(),
+                );
+            }
+        }
+        let selected_page = widgets.tab_view.selected_page();
+        if !background {
+            widgets.tab_view.set_selected_page(&placeholder);
+        }
+
+        let after_load = AfterLoad {
+            reselect: selected_page
+                .filter(|_| background)
+                .map(|selected_page| selected_page.downgrade().into()),
+            ..AfterLoad::default()
+        };
+        self.load_questions(widgets, uri, &placeholder, false, after_load, sender);
+    }
+
+    /// A tab for a question that's only loaded once the tab is selected, at `position` of
+    /// `tab_view`. It's titled from the cache, if it was opened before.
+    fn insert_unloaded_tab(
+        &self,
+        tab_view: &adw::TabView,
+        position: i32,
+        site: &str,
+        id: stackexchange::Id,
+        unloaded: UnloadedTab,
+    ) -> adw::TabPage {
+        let cached_title = self.stackexchange_client.cached_title(site, id);
+
+        let tab_page = tab_view.insert(
+            &adw::Bin::builder()
+                .child(&componant_builders::st_loading_page(
+                    cached_title.as_deref(),
+                ))
+                .build(),
+            position,
+        );
+        tab_page.set_title(&cached_title.unwrap_or_else(|| format!("Question {id}")));
+        tab_page.set_keyword(site);
+        if let Some(path) = self.stackexchange_client.cached_site_icon(site) {
+            let icon = gtk::gio::FileIcon::new(&gtk::gio::File::for_path(path));
+            tab_page.set_icon(Some(&icon));
+        }
+
+        self.question_tabs
+            .borrow_mut()
+            .insert(tab_page.clone(), (site.to_owned(), id));
+        self.unloaded_tabs
+            .borrow_mut()
+            .insert(tab_page.clone(), unloaded);
+
+        tab_page
+    }
+
+    /// Replace the page of a question tab with a placeholder, it's loaded again from the cache
+    /// at the same place once it's selected.
+    fn suspend_tab(&self, tab_page: &adw::TabPage) {
+        let Ok(bin) = tab_page.child().downcast::<adw::Bin>() else {
+            return;
+        };
+
+        let unloaded = UnloadedTab {
+            scroll_position: tab_scrolled_window(tab_page)
+                .map_or(0.0, |scrolled_window| scrolled_window.vadjustment().value()),
+            answer_id: tab_clamp(tab_page)
+                .and_then(|clamp| clamp.child())
+                .and_then(|question_box| componant_builders::focused_answer(&question_box)),
+        };
+        bin.set_child(Some(&componant_builders::st_loading_page(Some(
+            &tab_page.title(),
+        ))));
+        self.unloaded_tabs
+            .borrow_mut()
+            .insert(tab_page.clone(), unloaded);
+    }
+
+    /// Load the questions of `uri` into the `placeholder` tab, they're shown by `QuestionsLoaded`
+    /// so other messages are handled in the meantime. Closing the placeholder aborts the request.
+    /// With `refresh`, recently cached questions are fetched again.
+    fn load_questions(
+        &self,
+        widgets: &AppWidgets,
+        uri: &str,
+        placeholder: &adw::TabPage,
+        refresh: bool,
+        after_load: AfterLoad,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let (handle, registration) = AbortHandle::new_pair();
+        // A tab only shows the question it was asked last.
+        if let Some(previous) = self
+            .pending_requests
+            .borrow_mut()
+            .insert(placeholder.clone(), handle)
+        {
+            previous.abort();
+        }
+        self.show_progress(widgets);
+
+        let provider = self.question_provider();
+        let pending_requests = self.pending_requests.clone();
+        let placeholder = placeholder.clone();
+        let uri = uri.to_owned();
+        let sender = sender.input_sender().clone();
+
+        relm4::spawn_local(async move {
+            let pages = async {
+                let target = provider.resolve_uri(&uri).await?;
+                // Missing questions are shown below.
+                let questions = if refresh {
+                    provider
+                        .refresh_questions(&target.site, &target.question_ids)
+                        .await
+                } else {
+                    provider
+                        .get_questions(&target.site, &target.question_ids)
+                        .await
+                };
+                let questions = match questions {
+                    Err(stackexchange::StackExchangeError::NotFound) => Vec::new(),
+                    questions => questions?,
                 };
 
-                let about_window = adw::AboutWindow::builder()
-                    .application_name(APP_NAME)
-                    .version(env!("CARGO_PKG_VERSION"))
-                    .license_type(gtk::License::Gpl30Only)
-                    .comments(env!("CARGO_PKG_DESCRIPTION"))
-                    .developers(developers)
-                    .website(env!("CARGO_PKG_HOMEPAGE"))
-                    .issue_url("https://github.com/zer0-x/stackbloatless/issues")
-                    .application(&relm4::main_application())
-                    .transient_for(&relm4::main_application().active_window().unwrap())
-                    .debug_info(format!(
-                        "[rust]\n\
-                        {}\n\
-                        {}\n\
-                        {}\n\
-                        {}\n\n\
-                        [traget]\n\
-                        {}\n\n\
-                        [source]\n\
-                        branch: {}\n\
-                        commit: {}\n\
-                        clean: {}\n\n\
-                        [runtime]\n\
-                        GTK: {}.{}.{}\n\
-                        Adwaita: {}.{}.{}\n\
-                        Cairo: {}\n\
-                        Pango: {}\n\
-                        GDK Windowing Backend: {}\n\
-                        Session Desktop: {}\n\
-                        Current Desktop: {}",
-                        build::BUILD_OS,
-                        build::CARGO_VERSION,
-                        build::RUST_CHANNEL,
-                        build::RUST_VERSION,
-                        build::BUILD_TARGET,
-                        build::BRANCH,
-                        build::COMMIT_HASH,
-                        build::GIT_CLEAN,
-                        gtk::major_version(),
-                        gtk::minor_version(),
-                        gtk::micro_version(),
-                        adw::major_version(),
-                        adw::minor_version(),
-                        adw::micro_version(),
-                        gtk::cairo::version_string(),
-                        gtk::pango::version_string(),
-                        windowing_backend_name,
-                        std::env::var("XDG_SESSION_DESKTOP").unwrap_or("Undetected".to_owned()),
-                        std::env::var("XDG_CURRENT_DESKTOP").unwrap_or("Undetected".to_owned()),
-                    ))
-                    .build();
+                Ok::<_, stackexchange::StackExchangeError>((target, questions))
+            };
 
-                about_window.add_link(
-                    "Release Notes",
-                    "https://github.com/zer0-x/stackbloatless/blob/main/CHANGELOG.md",
+            // Nothing to show if it was aborted.
+            let Ok(pages) = Abortable::new(pages, registration).await else {
+                return;
+            };
+            pending_requests.borrow_mut().remove(&placeholder);
+
+            sender.emit(AppInput::QuestionsLoaded {
+                uri,
+                placeholder: placeholder.downgrade().into(),
+                pages,
+                refresh,
+                after_load,
+            });
+        });
+    }
+
+    /// Show the questions `load_questions` fetched for `placeholder`, or the error if it failed.
+    fn show_loaded_questions(
+        &self,
+        widgets: &AppWidgets,
+        uri: &str,
+        placeholder: &adw::TabPage,
+        pages: Result<
+            (stackexchange::UriTarget, Vec<stackexchange::Question>),
+            stackexchange::StackExchangeError,
+        >,
+        after_load: AfterLoad,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        match pages {
+            Ok((target, questions)) => {
+                self.open_question_tabs(
+                    widgets,
+                    &questions,
+                    target.answer_id,
+                    Some(placeholder),
+                    sender,
                 );
+                if let Some(question) = questions.first() {
+                    notify_loaded(placeholder, &question.title);
+                }
 
-                about_window.present();
+                // The first missing question takes the placeholder if no question did, so a
+                // followed link doesn't close its tab and its history.
+                let mut missing_placeholder = questions.is_empty().then_some(placeholder);
+                for &id in &target.question_ids {
+                    if questions.iter().all(|question| question.question_id != id) {
+                        self.show_missing_question(
+                            widgets,
+                            &target.site,
+                            id,
+                            uri,
+                            missing_placeholder.take(),
+                            sender,
+                        );
+                    }
+                }
             }
-            AppInput::Quit => {
-                relm4::main_application().quit();
+            Err(error) => {
+                // Expired sessions also offer to log in again.
+                if let stackexchange::StackExchangeError::Api { kind, .. } = &error {
+                    if kind.needs_login() {
+                        show_request_error("Loading failed", &error, sender.input_sender());
+                    }
+                }
+
+                let error_page = componant_builders::st_request_error_page(
+                    &error,
+                    uri,
+                    placeholder,
+                    sender.input_sender(),
+                );
+                placeholder.set_title(&error_page.title());
+                placeholder.set_loading(false);
+                if let Some(bin) = placeholder.child().downcast_ref::<adw::Bin>() {
+                    bin.set_child(Some(&error_page));
+                }
             }
-            AppInput::ToggleSelectedTabPin => {
-                let selected_page = widgets.tab_view.selected_page().unwrap();
+        }
+
+        // Continue where it was left, once it's shown.
+        if let Some(answer_id) = after_load.answer_id {
+            if let Some(question_box) = tab_clamp(placeholder).and_then(|clamp| clamp.child()) {
+                componant_builders::highlight_answer(&question_box, answer_id, false);
+            }
+        }
+        if let Some(scroll_position) = after_load.scroll_position {
+            if let Some(scrolled_window) = tab_scrolled_window(placeholder) {
+                restore_scroll_position(&scrolled_window, scroll_position);
+            }
+        }
+        // Tabs scrolled to an answer are selected when they're opened.
+        if let Some(selected_page) = after_load.reselect.and_then(|page| page.upgrade()) {
+            if widgets.tab_view.selected_page().as_ref() == Some(placeholder) {
+                widgets.tab_view.set_selected_page(&selected_page);
+            }
+        }
+        if after_load.navigated {
+            self.update_question_buttons(widgets);
+            sender.input(AppInput::UpdateSidebar);
+        }
+    }
+
+    /// Show a question that wasn't found in `placeholder`, or in a new tab without one. Its title
+    /// is looked up in the cache or in the slug of `uri` so it can be searched for.
+    fn show_missing_question(
+        &self,
+        widgets: &AppWidgets,
+        site: &str,
+        id: stackexchange::Id,
+        uri: &str,
+        placeholder: Option<&adw::TabPage>,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let title = self
+            .stackexchange_client
+            .cached_title(site, id)
+            .or_else(|| stackexchange::title_from_slug(uri));
+        let missing_page = componant_builders::st_missing_question(
+            site,
+            id,
+            title.as_deref(),
+            sender.input_sender(),
+        );
+
+        let tab_page = match placeholder {
+            Some(placeholder) => {
+                if let Some(bin) = placeholder.child().downcast_ref::<adw::Bin>() {
+                    bin.set_child(Some(&missing_page));
+                }
+                placeholder.set_loading(false);
+                placeholder.set_tooltip("");
+                // Still the question of the tab, so going back and refreshing keep working.
+                self.question_tabs
+                    .borrow_mut()
+                    .insert(placeholder.clone(), (site.to_owned(), id));
+                placeholder.clone()
+            }
+            None => {
+                let tab_page = widgets.tab_view.append(&missing_page);
+                widgets.tab_view.set_selected_page(&tab_page);
+                tab_page
+            }
+        };
+        tab_page.set_title(title.as_deref().unwrap_or("Not Found"));
+        self.set_site_icon(&tab_page, site);
+    }
+
+    /// Show whether the question of the selected tab is bookmarked, the header buttons for
+    /// questions are disabled for other tabs.
+    fn update_question_buttons(&self, widgets: &AppWidgets) {
+        let question = widgets
+            .tab_view
+            .selected_page()
+            .and_then(|tab_page| self.question_tabs.borrow().get(&tab_page).cloned());
+
+        let bookmarked = question
+            .as_ref()
+            .is_some_and(|(site, id)| self.bookmarks.contains(site, *id));
+
+        widgets.bookmark_button.set_sensitive(question.is_some());
+        widgets.browser_button.set_sensitive(question.is_some());
+        widgets.copy_link_button.set_sensitive(question.is_some());
+        widgets.bookmark_button.set_active(bookmarked);
+
+        let history = widgets
+            .tab_view
+            .selected_page()
+            .and_then(|tab_page| self.tab_histories.get(&tab_page));
+        widgets
+            .back_button
+            .set_sensitive(history.is_some_and(|history| !history.back.is_empty()));
+        widgets
+            .forward_button
+            .set_sensitive(history.is_some_and(|history| !history.forward.is_empty()));
+
+        widgets.bookmark_button.set_icon_name(if bookmarked {
+            "starred-symbolic"
+        } else {
+            "non-starred-symbolic"
+        });
+        widgets
+            .bookmark_button
+            .set_tooltip_text(Some(if bookmarked {
+                "Remove Bookmark"
+            } else {
+                "Bookmark"
+            }));
+    }
+
+    /// The question `tab_page` shows and how far it's scrolled, `None` if it isn't a question tab.
+    fn tab_location(&self, tab_page: &adw::TabPage) -> Option<TabLocation> {
+        let (site, question_id) = self.question_tabs.borrow().get(tab_page).cloned()?;
+        let scroll_position = tab_scrolled_window(tab_page)
+            .map_or(0.0, |scrolled_window| scrolled_window.vadjustment().value());
+
+        Some(TabLocation {
+            site,
+            question_id,
+            scroll_position,
+        })
+    }
+
+    /// Show `uri` in `tab_page` instead of its question, at `scroll_position` if it's known.
+    fn navigate_tab(
+        &mut self,
+        widgets: &AppWidgets,
+        tab_page: &adw::TabPage,
+        uri: &str,
+        scroll_position: Option<f64>,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        // Watching was for the previous question.
+        self.watched_tabs.borrow_mut().remove(tab_page);
 
+        if let Some(bin) = tab_page.child().downcast_ref::<adw::Bin>() {
+            bin.set_child(Some(&componant_builders::st_loading_page(None)));
+        }
+        tab_page.set_title("Loading…");
+        tab_page.set_loading(true);
+
+        self.load_questions(
+            widgets,
+            uri,
+            tab_page,
+            false,
+            AfterLoad {
+                scroll_position,
+                navigated: true,
+                ..AfterLoad::default()
+            },
+            sender,
+        );
+    }
+
+    /// Show the previous question of the selected tab if `back`, or the next one.
+    fn navigate_tab_history(
+        &mut self,
+        widgets: &AppWidgets,
+        back: bool,
+        sender: &AsyncComponentSender<Self>,
+    ) {
+        let Some(tab_page) = self.active_tab_view(widgets).selected_page() else {
+            return;
+        };
+        let Some(current) = self.tab_location(&tab_page) else {
+            return;
+        };
+        let Some(history) = self.tab_histories.get_mut(&tab_page) else {
+            return;
+        };
+
+        let (from, to) = if back {
+            (&mut history.back, &mut history.forward)
+        } else {
+            (&mut history.forward, &mut history.back)
+        };
+        let Some(location) = from.pop() else {
+            return;
+        };
+        to.push(current);
+
+        let uri = stackexchange::UriLink::Questions {
+            site: location.site,
+            question_ids: vec![location.question_id],
+            answer_id: None,
+        }
+        .to_uri();
+        self.navigate_tab(
+            widgets,
+            &tab_page,
+            &uri,
+            Some(location.scroll_position),
+            sender,
+        );
+    }
+
+    /// Tab view of the window focused last, the tab actions apply to its selected tab.
+    fn active_tab_view(&self, widgets: &AppWidgets) -> adw::TabView {
+        self.active_tab_view
+            .borrow()
+            .clone()
+            .unwrap_or_else(|| widgets.tab_view.clone())
+    }
+
+    fn tab_window_context(
+        &self,
+        tab_menu: &gtk::gio::Menu,
+        tab_actions: &gtk::gio::SimpleActionGroup,
+        sender: &AsyncComponentSender<Self>,
+    ) -> TabWindowContext {
+        TabWindowContext {
+            pending_requests: self.pending_requests.clone(),
+            question_tabs: self.question_tabs.clone(),
+            unloaded_tabs: self.unloaded_tabs.clone(),
+            watched_tabs: self.watched_tabs.clone(),
+            auto_refresh_tabs: self.auto_refresh_tabs.clone(),
+            tab_windows: self.tab_windows.clone(),
+            active_tab_view: self.active_tab_view.clone(),
+            tab_menu: tab_menu.clone(),
+            tab_actions: tab_actions.clone(),
+            sender: sender.input_sender().clone(),
+        }
+    }
+
+    /// Where opened questions come from.
+    fn question_provider(&self) -> Rc<dyn QuestionProvider> {
+        match &self.fixtures {
+            Some(fixtures) => fixtures.clone(),
+            None => self.stackexchange_client.clone(),
+        }
+    }
+
+    /// Private mode is told in the subtitle of the window, even when it's narrow.
+    fn update_subtitle(&self, widgets: &AppWidgets) {
+        widgets.title_widget.set_subtitle(if self.private.get() {
+            "Private Mode"
+        } else if self.narrow {
+            ""
+        } else {
+            APP_SUBTITLE
+        });
+    }
+
+    fn show_quota(&self, widgets: &AppWidgets) {
+        if let Some(quota) = self.quota {
+            // There's no room for it in narrow windows.
+            widgets.quota_label.set_visible(!self.narrow);
+            widgets
+                .quota_label
+                .set_label(&format!("{}/{}", quota.remaining, quota.max));
+            widgets.quota_label.set_tooltip_text(Some(&format!(
+                "{} of {} daily API requests remaining",
+                quota.remaining, quota.max
+            )));
+
+            // Warn when less than 10% of the quota is left.
+            if quota.remaining < quota.max / 10 {
+                widgets.quota_label.set_css_classes(&["warning", "caption"]);
+            } else {
                 widgets
-                    .tab_view
-                    .set_page_pinned(&selected_page, !selected_page.is_pinned())
+                    .quota_label
+                    .set_css_classes(&["dim-label", "caption"]);
             }
-            AppInput::CloseTab => {
-                let selected_page = widgets.tab_view.selected_page().unwrap();
+        }
+    }
 
-                // Ask before closing a pinned tab
-                if selected_page.is_pinned() {
-                    let warning_message = adw::MessageDialog::builder()
-                        .transient_for(&relm4::main_application().active_window().unwrap())
-                        .heading("Close pinned tab?")
-                        .body("Do you really want to close a pinned tab?")
-                        .build();
+    /// Pulse the progress bar until there are no more pending requests.
+    fn show_progress(&self, widgets: &AppWidgets) {
+        if widgets.progress_bar.is_visible() {
+            return;
+        }
 
-                    warning_message.add_responses(&[("yes", "Yes"), ("no", "No")]);
-                    warning_message.set_default_response(Some("no"));
-                    warning_message
-                        .set_response_appearance("yes", adw::ResponseAppearance::Destructive);
+        widgets.progress_bar.set_visible(true);
+        widgets.progress_bar.pulse();
 
-                    warning_message.show();
+        // Requests are removed from many places, so it's checked on every pulse.
+        gtk::glib::timeout_add_local(
+            std::time::Duration::from_millis(PROGRESS_PULSE_INTERVAL),
+            gtk::glib::clone!(
+                @strong self.pending_requests as pending_requests,
+                @strong widgets.progress_bar as progress_bar => move || {
+                if pending_requests.borrow().is_empty() {
+                    progress_bar.set_visible(false);
+                    return gtk::glib::Continue(false);
+                }
 
-                    warning_message.connect_response(
-                        None,
-                        gtk::glib::clone!(@strong sender => move |dialog, responde| {
-                            if responde == "yes" {
-                                sender.input(AppInput::ClosePinnedTab);
-                            }
-                            dialog.close();
-                        }),
-                    );
-                } else {
-                    widgets.tab_view.close_page(&selected_page);
+                progress_bar.pulse();
+                gtk::glib::Continue(true)
+            }),
+        );
+    }
+
+    /// The tab of this window showing the question, if there is one.
+    fn question_tab(
+        &self,
+        widgets: &AppWidgets,
+        site: &str,
+        id: stackexchange::Id,
+    ) -> Option<adw::TabPage> {
+        self.question_tabs
+            .borrow()
+            .iter()
+            .find(|(tab_page, (tab_site, tab_id))| {
+                tab_site == site && *tab_id == id && tab_view_contains(&widgets.tab_view, tab_page)
+            })
+            .map(|(tab_page, _)| tab_page.clone())
+    }
+
+    /// Append a tab for every question, `answer_id` is scrolled to and highlighted.
+    fn open_question_tabs(
+        &self,
+        widgets: &AppWidgets,
+        questions: &[stackexchange::Question],
+        answer_id: Option<stackexchange::Id>,
+        placeholder: Option<&adw::TabPage>,
+        sender: &AsyncComponentSender<Self>,
+    ) -> Vec<adw::TabPage> {
+        let mut tab_pages = Vec::with_capacity(questions.len());
+
+        for (index, question) in questions.iter().enumerate() {
+            let question_box = componant_builders::st_question(
+                question,
+                answer_id,
+                self.settings.answer_sort,
+                self.stackexchange_client.is_authenticated(),
+                sender.input_sender(),
+            );
+
+            // Only narrows the content in reading mode.
+            let clamp = adw::Clamp::builder().child(&question_box).build();
+            set_reading_width(&clamp, self.reading_mode);
+
+            let scrolled_window = gtk::ScrolledWindow::builder()
+                .child(&clamp)
+                .vexpand(true)
+                .hexpand(true)
+                .build();
+
+            // The find bar stays above the scrolled content.
+            let question_layout = gtk::Box::new(gtk::Orientation::Vertical, 0);
+            let find_bar = componant_builders::st_find_bar(question_box.upcast_ref());
+            question_layout.append(&find_bar);
+            question_layout.append(&scrolled_window);
+
+            // The first question replaces the placeholder, the others are opened next to it. They
+            // are in a bin like the placeholder, so they can be refreshed in place.
+            let tab_page = match placeholder {
+                Some(placeholder) if index == 0 => {
+                    if let Some(bin) = placeholder.child().downcast_ref::<adw::Bin>() {
+                        bin.set_child(Some(&question_layout));
+                    }
+                    placeholder.set_loading(false);
+                    placeholder.clone()
                 }
+                Some(placeholder) => widgets.tab_view.insert(
+                    &adw::Bin::builder().child(&question_layout).build(),
+                    widgets.tab_view.page_position(placeholder) + index as i32,
+                ),
+                None => widgets
+                    .tab_view
+                    .append(&adw::Bin::builder().child(&question_layout).build()),
+            };
+            question_layout.insert_child_after(
+                &componant_builders::st_activity_banner(&tab_page, sender.input_sender()),
+                Some(&find_bar),
+            );
+
+            // Matched by the search of the tabs overview and the tab switcher.
+            tab_page.set_keyword(&format!("{} {}", question.site, question.tags.join(" ")));
+            tab_page.set_title(&question.title);
+            self.question_tabs.borrow_mut().insert(
+                tab_page.clone(),
+                (question.site.clone(), question.question_id),
+            );
+            if !self.private.get() {
+                self.history.borrow_mut().record(
+                    &question.site,
+                    question.question_id,
+                    &question.title,
+                );
+            }
+            self.set_site_icon(&tab_page, &question.site);
+            if answer_id.is_some() {
+                // The answer is scrolled to once the tab is shown.
+                widgets.tab_view.set_selected_page(&tab_page);
             }
-            AppInput::ClosePinnedTab => {
-                let selected_page = widgets.tab_view.selected_page().unwrap();
+            tab_page.set_tooltip(&componant_builders::question_tooltip(question));
 
-                widgets.tab_view.set_page_pinned(&selected_page, false);
-                widgets.tab_view.close_page(&selected_page);
+            // Linked questions first, since they are often duplicates.
+            for (relation, title) in [
+                (stackexchange::Relation::Linked, "Linked"),
+                (stackexchange::Relation::Related, "Related"),
+            ] {
+                let section = componant_builders::st_questions_section(title);
+                question_box.append(&section);
+                sender.input(AppInput::LoadRelatedQuestions {
+                    site: question.site.clone(),
+                    id: question.question_id,
+                    relation,
+                    section: section.downgrade().into(),
+                });
             }
+
+            tab_pages.push(tab_page);
         }
-    }
 
-    fn shutdown(&mut self, _widgets: &mut Self::Widgets, _output: relm4::Sender<Self::Output>) {}
+        if !questions.is_empty() && !self.private.get() {
+            self.history.borrow().save();
+            sender.input(AppInput::UpdateSidebar);
+        }
+        // The selected tab might be one of them.
+        self.update_question_buttons(widgets);
+
+        tab_pages
+    }
 }
@@ -50,19 +50,29 @@ fn md_paragraph2buf(text_view: &gtk::TextView, buf: &gtk::TextBuffer, nodes: &Ve
 
                 text_view.add_child_at_anchor(&link_button, &anchor);
             }
-            mdast::Node::LinkReference(link_ref) => {
-                todo!("LinkRefrence")
-            }
             mdast::Node::Image(image) => {
-                todo!("Image")
-            }
-            mdast::Node::ImageReference(image_ref) => {
-                todo!("ImageReference")
+                let anchor = gtk::TextChildAnchor::new();
+
+                buf.insert_child_anchor(&mut buf.end_iter(), &anchor);
+
+                // TODO: Load the image itself.
+                let link_button = gtk::LinkButton::builder()
+                    .label(if image.alt.is_empty() {
+                        "Image"
+                    } else {
+                        &image.alt
+                    })
+                    .uri(&image.url)
+                    .build();
+
+                text_view.add_child_at_anchor(&link_button, &anchor);
             }
             mdast::Node::Paragraph(paragraph) => {
                 md_paragraph2buf(text_view, buf, &paragraph.children);
             }
-            _ => unimplemented!(),
+            // TODO: Resolve link and image references from their definitions.
+            // Fallback to the plain text of unsupported nodes, rather than not showing them.
+            _ => buf.insert(&mut buf.end_iter(), &node.to_string()),
         }
     }
 }
@@ -94,7 +104,8 @@ fn md_list2buf(
             mdast::Node::ListItem(list_item) => {
                 md_paragraph2buf(text_view, buf, &list_item.children)
             }
-            _ => unimplemented!(),
+            // Unsupported nodes are shown as plain text.
+            _ => buf.insert(&mut buf.end_iter(), &node.to_string()),
         }
 
         buf.insert(&mut buf.end_iter(), "\n");
@@ -160,9 +171,11 @@ pub fn md2gtk(markdown_text: &str) -> gtk::TextView {
 
                 buf.insert_child_anchor(&mut buf.end_iter(), &anchor);
 
-                let frame = gtk::Frame::builder().hexpand(true).build();
+                let frame = gtk::Frame::builder()
+                    .css_classes(["code-block"])
+                    .hexpand(true)
+                    .build();
 
-                // TODO: Apply monospace font.
                 let code_text = gtk::Label::builder()
                     .label(&code.value)
                     .selectable(true)
@@ -182,7 +195,8 @@ pub fn md2gtk(markdown_text: &str) -> gtk::TextView {
                 buf.insert(&mut buf.end_iter(), "\n\n");
             }
             mdast::Node::Html(html) => {}
-            _ => dbg!(),
+            // Unsupported nodes are shown as plain text.
+            _ => buf.insert(&mut buf.end_iter(), &node.to_string()),
         }
     }
 
@@ -212,7 +226,8 @@ fn load_text_tags(buf: &gtk::TextBuffer) {
         &gtk::TextTag::builder()
             .name("INLINE_CODE")
             .font("monospace")
-            .background("#050505")
+            // Translucent so it works with both the light and dark styles.
+            .background("rgba(127, 127, 127, 0.2)")
             .build(),
     );
 
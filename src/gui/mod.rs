@@ -1,3 +1,5 @@
 mod componant_builders;
+mod fuzzy;
 pub mod main_window;
 mod markdown2gtk;
+mod preferences;
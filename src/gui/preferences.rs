@@ -0,0 +1,401 @@
+use adw::prelude::*;
+use relm4::prelude::gtk::{self, glib};
+
+use super::main_window::AppInput;
+use crate::settings::{AnswerSort, Settings, Theme, MAX_ZOOM, MIN_ZOOM, ZOOM_STEP};
+
+/// Preferences window, every change is sent to the app to be applied and saved.
+pub fn preferences_window(
+    settings: &Settings,
+    sender: &relm4::Sender<AppInput>,
+) -> adw::PreferencesWindow {
+    let window = adw::PreferencesWindow::builder()
+        .transient_for(&relm4::main_application().active_window().unwrap())
+        .build();
+
+    // General page
+    let general_page = adw::PreferencesPage::builder()
+        .title("General")
+        .icon_name("preferences-system-symbolic")
+        .build();
+    window.add(&general_page);
+
+    let sites_group = adw::PreferencesGroup::builder().title("Sites").build();
+    general_page.add(&sites_group);
+
+    let default_site_row = adw::EntryRow::builder()
+        .title("Default Site")
+        .text(&settings.default_site)
+        .show_apply_button(true)
+        .tooltip_text("Opened on the home page and searched by default, like stackoverflow or au")
+        .build();
+
+    default_site_row.connect_apply(glib::clone!(@strong sender => move |row| {
+        sender.emit(AppInput::SetDefaultSite(row.text().trim().to_owned()));
+    }));
+
+    sites_group.add(&default_site_row);
+
+    let appearance_group = adw::PreferencesGroup::builder().title("Appearance").build();
+    general_page.add(&appearance_group);
+
+    let theme_labels: Vec<&str> = Theme::ALL.iter().map(|theme| theme.label()).collect();
+    let theme_row = adw::ComboRow::builder()
+        .title("Style")
+        .model(&gtk::StringList::new(&theme_labels))
+        .selected(
+            Theme::ALL
+                .iter()
+                .position(|&theme| theme == settings.theme)
+                .unwrap_or_default() as u32,
+        )
+        .build();
+
+    theme_row.connect_selected_notify(glib::clone!(@strong sender => move |row| {
+        if let Some(&theme) = Theme::ALL.get(row.selected() as usize) {
+            sender.emit(AppInput::SetTheme(theme));
+        }
+    }));
+
+    appearance_group.add(&theme_row);
+
+    let subscriptions_group = adw::PreferencesGroup::builder()
+        .title("Subscriptions")
+        .build();
+    general_page.add(&subscriptions_group);
+
+    let notify_switch = gtk::Switch::builder()
+        .active(settings.notify_subscriptions)
+        .valign(gtk::Align::Center)
+        .build();
+
+    notify_switch.connect_active_notify(glib::clone!(@strong sender => move |switch| {
+        sender.emit(AppInput::SetNotifySubscriptions(switch.is_active()));
+    }));
+
+    let notify_row = adw::ActionRow::builder()
+        .title("Notify About New Questions")
+        .subtitle("Send a notification when subscribed tags have new questions")
+        .build();
+    notify_row.add_suffix(&notify_switch);
+    notify_row.set_activatable_widget(Some(&notify_switch));
+    subscriptions_group.add(&notify_row);
+
+    let session_group = adw::PreferencesGroup::builder().title("Session").build();
+    general_page.add(&session_group);
+
+    let restore_switch = gtk::Switch::builder()
+        .active(settings.restore_session)
+        .valign(gtk::Align::Center)
+        .build();
+
+    restore_switch.connect_active_notify(glib::clone!(@strong sender => move |switch| {
+        sender.emit(AppInput::SetRestoreSession(switch.is_active()));
+    }));
+
+    let restore_row = adw::ActionRow::builder()
+        .title("Restore Tabs")
+        .subtitle("Reopen the questions that were open last time, pinned ones are always reopened")
+        .build();
+    restore_row.add_suffix(&restore_switch);
+    restore_row.set_activatable_widget(Some(&restore_switch));
+    session_group.add(&restore_row);
+
+    let suspend_button = gtk::SpinButton::builder()
+        .adjustment(&gtk::Adjustment::new(
+            settings.suspend_tabs_after as f64,
+            0.0,
+            24.0 * 60.0,
+            5.0,
+            30.0,
+            0.0,
+        ))
+        .valign(gtk::Align::Center)
+        .build();
+
+    suspend_button.connect_value_changed(glib::clone!(@strong sender => move |spin_button| {
+        sender.emit(AppInput::SetSuspendTabsAfter(spin_button.value() as u32));
+    }));
+
+    let suspend_row = adw::ActionRow::builder()
+        .title("Suspend Tabs After")
+        .subtitle("Minutes before unseen tabs are unloaded to save memory, 0 keeps them loaded")
+        .build();
+    suspend_row.add_suffix(&suspend_button);
+    suspend_row.set_activatable_widget(Some(&suspend_button));
+    session_group.add(&suspend_row);
+
+    let recent_switch = gtk::Switch::builder()
+        .active(settings.recent_tab_cycling)
+        .valign(gtk::Align::Center)
+        .build();
+
+    recent_switch.connect_active_notify(glib::clone!(@strong sender => move |switch| {
+        sender.emit(AppInput::SetRecentTabCycling(switch.is_active()));
+    }));
+
+    let recent_row = adw::ActionRow::builder()
+        .title("Switch to Recent Tabs First")
+        .subtitle("Ctrl+Tab goes through the tabs in the order they were last used")
+        .build();
+    recent_row.add_suffix(&recent_switch);
+    recent_row.set_activatable_widget(Some(&recent_switch));
+    session_group.add(&recent_row);
+
+    // Content page
+    let content_page = adw::PreferencesPage::builder()
+        .title("Content")
+        .icon_name("font-x-generic-symbolic")
+        .build();
+    window.add(&content_page);
+
+    let text_group = adw::PreferencesGroup::builder().title("Text").build();
+    content_page.add(&text_group);
+
+    let font_button = gtk::FontButton::builder()
+        .use_font(true)
+        .level(gtk::FontChooserLevel::FAMILY | gtk::FontChooserLevel::SIZE)
+        .valign(gtk::Align::Center)
+        .build();
+    if let Some(content_font) = &settings.content_font {
+        font_button.set_font(content_font);
+    }
+
+    font_button.connect_font_set(glib::clone!(@strong sender => move |font_button| {
+        sender.emit(AppInput::SetContentFont(font_button.font().map(|font| font.to_string())));
+    }));
+
+    let reset_font_button = gtk::Button::builder()
+        .icon_name("edit-undo-symbolic")
+        .tooltip_text("Use the Default Font")
+        .css_classes(["flat"])
+        .valign(gtk::Align::Center)
+        .build();
+
+    reset_font_button.connect_clicked(glib::clone!(@strong sender => move |_| {
+        sender.emit(AppInput::SetContentFont(None));
+    }));
+
+    let font_row = adw::ActionRow::builder()
+        .title("Font")
+        .subtitle("Used for questions, answers and comments")
+        .build();
+    font_row.add_suffix(&font_button);
+    font_row.add_suffix(&reset_font_button);
+    text_group.add(&font_row);
+
+    let code_font_button = gtk::FontButton::builder()
+        .use_font(true)
+        .level(gtk::FontChooserLevel::FAMILY | gtk::FontChooserLevel::SIZE)
+        .valign(gtk::Align::Center)
+        .build();
+    code_font_button.set_filter_func(|family, _face| family.is_monospace());
+    if let Some(code_font) = &settings.code_font {
+        code_font_button.set_font(code_font);
+    }
+
+    code_font_button.connect_font_set(glib::clone!(@strong sender => move |font_button| {
+        sender.emit(AppInput::SetCodeFont(font_button.font().map(|font| font.to_string())));
+    }));
+
+    let reset_code_font_button = gtk::Button::builder()
+        .icon_name("edit-undo-symbolic")
+        .tooltip_text("Use the Default Monospace Font")
+        .css_classes(["flat"])
+        .valign(gtk::Align::Center)
+        .build();
+
+    reset_code_font_button.connect_clicked(glib::clone!(@strong sender => move |_| {
+        sender.emit(AppInput::SetCodeFont(None));
+    }));
+
+    let code_font_row = adw::ActionRow::builder()
+        .title("Code Font")
+        .subtitle("Used for code blocks")
+        .build();
+    code_font_row.add_suffix(&code_font_button);
+    code_font_row.add_suffix(&reset_code_font_button);
+    text_group.add(&code_font_row);
+
+    let zoom_button = gtk::SpinButton::builder()
+        .adjustment(&gtk::Adjustment::new(
+            settings.zoom as f64,
+            MIN_ZOOM as f64,
+            MAX_ZOOM as f64,
+            ZOOM_STEP as f64,
+            ZOOM_STEP as f64 * 5.0,
+            0.0,
+        ))
+        .valign(gtk::Align::Center)
+        .build();
+
+    zoom_button.connect_value_changed(glib::clone!(@strong sender => move |spin_button| {
+        sender.emit(AppInput::SetDefaultZoom(spin_button.value() as u32));
+    }));
+
+    let zoom_row = adw::ActionRow::builder()
+        .title("Default Zoom")
+        .subtitle("In percent, for tabs that weren't zoomed with Ctrl+Plus or Ctrl+Minus")
+        .build();
+    zoom_row.add_suffix(&zoom_button);
+    zoom_row.set_activatable_widget(Some(&zoom_button));
+    text_group.add(&zoom_row);
+
+    let answers_group = adw::PreferencesGroup::builder().title("Answers").build();
+    content_page.add(&answers_group);
+
+    let sort_labels: Vec<&str> = AnswerSort::ALL.iter().map(|sort| sort.label()).collect();
+    let sort_row = adw::ComboRow::builder()
+        .title("Sort Answers")
+        .model(&gtk::StringList::new(&sort_labels))
+        .selected(
+            AnswerSort::ALL
+                .iter()
+                .position(|&sort| sort == settings.answer_sort)
+                .unwrap_or_default() as u32,
+        )
+        .build();
+
+    sort_row.connect_selected_notify(glib::clone!(@strong sender => move |row| {
+        if let Some(&sort) = AnswerSort::ALL.get(row.selected() as usize) {
+            sender.emit(AppInput::SetAnswerSort(sort));
+        }
+    }));
+
+    answers_group.add(&sort_row);
+
+    // Network page
+    let network_page = adw::PreferencesPage::builder()
+        .title("Network")
+        .icon_name("network-wired-symbolic")
+        .build();
+    window.add(&network_page);
+
+    let proxy_group = adw::PreferencesGroup::builder()
+        .title("Proxy")
+        .description(
+            "HTTP, HTTPS or SOCKS5 proxy, for example socks5://127.0.0.1:9050. When empty, the \
+            http_proxy, https_proxy and all_proxy environment variables are used.",
+        )
+        .build();
+    network_page.add(&proxy_group);
+
+    let proxy_row = adw::EntryRow::builder()
+        .title("Proxy")
+        .text(settings.proxy.as_deref().unwrap_or_default())
+        .show_apply_button(true)
+        .build();
+
+    proxy_row.connect_apply(glib::clone!(@strong sender => move |row| {
+        let proxy = row.text().trim().to_owned();
+        sender.emit(AppInput::SetProxy((!proxy.is_empty()).then_some(proxy)));
+    }));
+
+    proxy_group.add(&proxy_row);
+
+    let api_group = adw::PreferencesGroup::builder()
+        .title("API")
+        .description(
+            "A key registered on stackapps.com raises the daily quota from 300 to 10,000 \
+            requests. It's only used while logged out.",
+        )
+        .build();
+    network_page.add(&api_group);
+
+    let api_key_row = adw::EntryRow::builder()
+        .title("API Key")
+        .text(settings.api_key.as_deref().unwrap_or_default())
+        .show_apply_button(true)
+        .build();
+
+    api_key_row.connect_apply(glib::clone!(@strong sender => move |row| {
+        let api_key = row.text().trim().to_owned();
+        sender.emit(AppInput::SetApiKey((!api_key.is_empty()).then_some(api_key)));
+    }));
+
+    api_group.add(&api_key_row);
+
+    let timeouts_group = adw::PreferencesGroup::builder()
+        .title("Timeouts")
+        .description("Raise them on slow connections, failed requests are retried a few times.")
+        .build();
+    network_page.add(&timeouts_group);
+
+    let spin_button = |value: u64| {
+        gtk::SpinButton::builder()
+            .adjustment(&gtk::Adjustment::new(
+                value as f64,
+                1.0,
+                300.0,
+                1.0,
+                10.0,
+                0.0,
+            ))
+            .valign(gtk::Align::Center)
+            .build()
+    };
+    let connect_timeout = spin_button(settings.connect_timeout);
+    let request_timeout = spin_button(settings.request_timeout);
+
+    for (title, subtitle, spin_button) in [
+        (
+            "Connection Timeout",
+            "Seconds to wait for the connection",
+            &connect_timeout,
+        ),
+        (
+            "Request Timeout",
+            "Seconds to wait for the whole response",
+            &request_timeout,
+        ),
+    ] {
+        let row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(subtitle)
+            .build();
+        row.add_suffix(spin_button);
+        row.set_activatable_widget(Some(spin_button));
+        timeouts_group.add(&row);
+
+        spin_button.connect_value_changed(glib::clone!(
+            @strong sender, @weak connect_timeout, @weak request_timeout => move |_| {
+                sender.emit(AppInput::SetTimeouts {
+                    connect_timeout: connect_timeout.value() as u64,
+                    request_timeout: request_timeout.value() as u64,
+                });
+            }
+        ));
+    }
+
+    let queue_group = adw::PreferencesGroup::builder()
+        .title("Requests")
+        .description("Opening many questions at once queues their requests to spare the API.")
+        .build();
+    network_page.add(&queue_group);
+
+    let concurrency_button = gtk::SpinButton::builder()
+        .adjustment(&gtk::Adjustment::new(
+            settings.max_concurrent_requests as f64,
+            1.0,
+            16.0,
+            1.0,
+            4.0,
+            0.0,
+        ))
+        .valign(gtk::Align::Center)
+        .build();
+
+    concurrency_button.connect_value_changed(glib::clone!(@strong sender => move |spin_button| {
+        sender.emit(AppInput::SetMaxConcurrentRequests(spin_button.value() as usize));
+    }));
+
+    let concurrency_row = adw::ActionRow::builder()
+        .title("Parallel Requests")
+        .subtitle("Requests sent at the same time")
+        .build();
+    concurrency_row.add_suffix(&concurrency_button);
+    concurrency_row.set_activatable_widget(Some(&concurrency_button));
+    queue_group.add(&concurrency_row);
+
+    window
+}
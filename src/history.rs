@@ -0,0 +1,82 @@
+// Opened questions, newest first, saved as JSON in:
+// {user data dir}/stackbloatless/history.json
+
+use std::path::PathBuf;
+
+use relm4::gtk::glib;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+use crate::api::stackexchange::Id;
+use crate::storage;
+
+// Older entries are forgotten.
+const MAX_ENTRIES: usize = 1000;
+const MAX_SEARCH_TERMS: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub site: String,
+    pub question_id: Id,
+    pub title: String,
+    #[serde(with = "time::serde::timestamp")]
+    pub visited: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+    /// Terms entered in the search entry, newest first.
+    pub search_terms: Vec<String>,
+}
+
+pub fn history_path() -> PathBuf {
+    glib::user_data_dir()
+        .join("stackbloatless")
+        .join("history.json")
+}
+
+impl History {
+    /// Load the saved history, or an empty one if there is none.
+    pub fn load() -> Self {
+        storage::load_json(&history_path(), "history").unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save_json(&history_path(), self, "the history");
+    }
+
+    /// Move the question to the top, questions are only listed once.
+    pub fn record(&mut self, site: &str, question_id: Id, title: &str) {
+        self.entries
+            .retain(|entry| !(entry.site == site && entry.question_id == question_id));
+
+        self.entries.insert(
+            0,
+            HistoryEntry {
+                site: site.to_owned(),
+                question_id,
+                title: title.to_owned(),
+                visited: OffsetDateTime::now_utc(),
+            },
+        );
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    /// Move the search term to the top, terms are only listed once.
+    pub fn record_search(&mut self, term: &str) {
+        self.remove_search(term);
+        self.search_terms.insert(0, term.to_owned());
+        self.search_terms.truncate(MAX_SEARCH_TERMS);
+    }
+
+    pub fn remove_search(&mut self, term: &str) {
+        self.search_terms.retain(|search_term| search_term != term);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.search_terms.clear();
+    }
+}
@@ -2,9 +2,60 @@ use adw::prelude::*;
 use relm4::gtk;
 
 mod api;
+mod bookmarks;
 mod gui;
+mod history;
+mod search_provider;
+mod session;
+mod settings;
+mod storage;
 
 const APP_ID: &str = "io.github.zer0_x.stackbloatless";
+// How long the service started by GNOME Shell keeps running after its last search, in milliseconds.
+const SERVICE_INACTIVITY_TIMEOUT: u32 = 60_000;
+
+/// Question ids in the arguments are replaced by their `stackexchange://` uris, of the site of the
+/// last `--site` before them or the default one, so they're opened like links.
+fn translate_args(args: Vec<String>) -> Vec<String> {
+    let mut site = settings::Settings::load().default_site;
+    let mut translated = Vec::with_capacity(args.len());
+    let mut args = args.into_iter();
+
+    // The program name.
+    translated.extend(args.next());
+
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix("--site=") {
+            site = api::stackexchange::site_parameter(value);
+            continue;
+        }
+        if arg == "--site" {
+            if let Some(value) = args.next() {
+                site = api::stackexchange::site_parameter(&value);
+            }
+            continue;
+        }
+
+        let ids = arg
+            .split([';', ','])
+            .map(|id| id.trim().parse::<api::stackexchange::Id>())
+            .collect::<Result<Vec<_>, _>>();
+        match ids {
+            Ok(question_ids) => translated.push(
+                api::stackexchange::UriLink::Questions {
+                    site: site.clone(),
+                    question_ids,
+                    answer_id: None,
+                }
+                .to_uri(),
+            ),
+            // Links and the options of GApplication.
+            Err(_) => translated.push(arg),
+        }
+    }
+
+    translated
+}
 
 fn main() {
     let base_app = adw::Application::builder()
@@ -12,26 +63,63 @@ fn main() {
         .flags(relm4::gtk::gio::ApplicationFlags::HANDLES_OPEN)
         .build();
 
+    // Only listed in the help, it's handled by `translate_args`.
+    base_app.add_main_option(
+        "site",
+        gtk::glib::Char(0),
+        gtk::glib::OptionFlags::NONE,
+        gtk::glib::OptionArg::String,
+        "Site of the question ids after it, like askubuntu or au",
+        Some("SITE"),
+    );
+
     // Create a communication channel to send messages to the app component.
     let (sender, receiver) = relm4::channel::<gui::main_window::AppInput>();
 
     base_app.connect_open(
-        gtk::glib::clone!(@strong sender => move |_application, files, _hint| {
-            let uris = files.iter().map(|file| file.uri().to_string()).collect::<Vec<String>>();
+        gtk::glib::clone!(@strong sender => move |application, files, _hint| {
+            // Web links are translated to stackexchange:// uris.
+            let uris = files
+                .iter()
+                .map(|file| file.uri().to_string())
+                .map(|uri| api::stackexchange::parse_uri(&uri).map_or(uri, |link| link.to_uri()))
+                .collect::<Vec<String>>();
 
             for uri in uris {
                 sender.send(gui::main_window::AppInput::RequestPagesByUri(uri)).unwrap();
             }
+
+            // Links opened by another instance, or through D-Bus, show the window too.
+            application.activate();
         }),
     );
 
-    // FIX: Close remote instance after sending the `open` signal.
-
-    base_app.connect_startup(|application| {
-        application.activate();
+    // Only one instance runs, others forward their links to it with the `open` signal and exit.
+    // It's activated again when they're launched without any.
+    base_app.connect_activate(|application| {
+        if let Some(window) = application.active_window() {
+            window.present();
+        }
     });
 
+    base_app.connect_startup(gtk::glib::clone!(@strong sender => move |application| {
+        if let Some(connection) = application.dbus_connection() {
+            search_provider::register(&connection, sender.clone());
+        }
+
+        // Started by GNOME Shell to search, the window is shown once a result is chosen. Until then,
+        // it exits once the Shell stops searching.
+        if application
+            .flags()
+            .contains(gtk::gio::ApplicationFlags::IS_SERVICE)
+        {
+            application.set_inactivity_timeout(SERVICE_INACTIVITY_TIMEOUT);
+        } else {
+            application.activate();
+        }
+    }));
+
     relm4::RelmApp::from_app(base_app)
-        .with_args(std::env::args().collect::<Vec<String>>())
+        .with_args(translate_args(std::env::args().collect()))
         .run_async::<gui::main_window::AppModel>(gui::main_window::AppInit { receiver });
 }
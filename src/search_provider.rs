@@ -0,0 +1,306 @@
+// Results for the search of GNOME Shell, from the history, the bookmarks and the titles of the
+// default site. The Shell searches on every key press, so StackExchange is only searched once
+// typing pauses, to save the API quota.
+// https://developer.gnome.org/documentation/tutorials/search-provider.html
+
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+use relm4::gtk::{gio, glib, prelude::*};
+
+use crate::api::stackexchange::{Id, StackExchange, UriLink};
+use crate::bookmarks::{self, Bookmarks};
+use crate::gui::main_window::AppInput;
+use crate::history::{self, History};
+use crate::settings::Settings;
+
+const OBJECT_PATH: &str = "/io/github/zer0_x/stackbloatless/SearchProvider";
+// The Shell only shows the first few anyway.
+const MAX_RESULTS: usize = 10;
+// Shorter terms match too many titles to be worth a request.
+const MIN_REMOTE_SEARCH_LENGTH: usize = 3;
+// How long typing has to pause before StackExchange is searched.
+const REMOTE_SEARCH_DELAY: Duration = Duration::from_millis(400);
+
+const INTERFACE_XML: &str = r#"
+<node>
+  <interface name="org.gnome.Shell.SearchProvider2">
+    <method name="GetInitialResultSet">
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="as" name="results" direction="out"/>
+    </method>
+    <method name="GetSubsearchResultSet">
+      <arg type="as" name="previous_results" direction="in"/>
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="as" name="results" direction="out"/>
+    </method>
+    <method name="GetResultMetas">
+      <arg type="as" name="identifiers" direction="in"/>
+      <arg type="aa{sv}" name="metas" direction="out"/>
+    </method>
+    <method name="ActivateResult">
+      <arg type="s" name="identifier" direction="in"/>
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="u" name="timestamp" direction="in"/>
+    </method>
+    <method name="LaunchSearch">
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="u" name="timestamp" direction="in"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+/// A question of the results, its uri is their identifier.
+#[derive(Clone)]
+struct FoundQuestion {
+    uri: String,
+    site: String,
+    title: String,
+}
+
+fn question_uri(site: &str, question_id: Id) -> String {
+    UriLink::Questions {
+        site: site.to_owned(),
+        question_ids: vec![question_id],
+        answer_id: None,
+    }
+    .to_uri()
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// The questions the Shell was given, kept between its calls.
+struct SearchProvider {
+    // Visited questions, newest first, then the bookmarked ones that weren't visited.
+    local_questions: RefCell<Vec<FoundQuestion>>,
+    // When the history and the bookmarks were changed, they're loaded again once it changes.
+    // `None` until they're loaded.
+    local_modified: Cell<Option<(Option<SystemTime>, Option<SystemTime>)>>,
+    // Questions found by the last search of StackExchange, by uri.
+    remote_questions: RefCell<HashMap<String, FoundQuestion>>,
+    // Counts the searches, only the last one is sent to StackExchange.
+    searches: Cell<u64>,
+    // The one of the window, when the app is running.
+    stackexchange_client: Rc<StackExchange>,
+}
+
+thread_local! {
+    // D-Bus calls are answered on the main thread.
+    static SEARCH_PROVIDER: Rc<SearchProvider> = Rc::new(SearchProvider::new());
+}
+
+impl SearchProvider {
+    fn new() -> Self {
+        Self {
+            local_questions: RefCell::new(Vec::new()),
+            local_modified: Cell::new(None),
+            remote_questions: RefCell::new(HashMap::new()),
+            searches: Cell::new(0),
+            stackexchange_client: StackExchange::shared(),
+        }
+    }
+
+    fn local_questions(&self) -> Ref<'_, Vec<FoundQuestion>> {
+        let local_modified = (
+            modified(&history::history_path()),
+            modified(&bookmarks::bookmarks_path()),
+        );
+        if Some(local_modified) == self.local_modified.get() {
+            return self.local_questions.borrow();
+        }
+        self.local_modified.set(Some(local_modified));
+
+        let history = History::load()
+            .entries
+            .into_iter()
+            .map(|entry| (entry.site, entry.question_id, entry.title));
+        let bookmarks = Bookmarks::load()
+            .items
+            .into_iter()
+            .map(|bookmark| (bookmark.site, bookmark.question_id, bookmark.title));
+
+        let mut questions: Vec<FoundQuestion> = Vec::new();
+        for (site, question_id, title) in history.chain(bookmarks) {
+            let uri = question_uri(&site, question_id);
+            if questions.iter().all(|question| question.uri != uri) {
+                questions.push(FoundQuestion { uri, site, title });
+            }
+        }
+        *self.local_questions.borrow_mut() = questions;
+
+        self.local_questions.borrow()
+    }
+
+    /// Uris of the local questions with every term in their title.
+    fn search_local(&self, terms: &[String]) -> Vec<String> {
+        let terms: Vec<String> = terms.iter().map(|term| term.to_lowercase()).collect();
+
+        self.local_questions()
+            .iter()
+            .filter(|question| {
+                let title = question.title.to_lowercase();
+                terms.iter().all(|term| title.contains(term.as_str()))
+            })
+            .map(|question| question.uri.clone())
+            .take(MAX_RESULTS)
+            .collect()
+    }
+
+    /// Answer `invocation` with the local questions matching `terms`, followed by the titles
+    /// found on the default site once typing pauses.
+    fn search(self: Rc<Self>, terms: Vec<String>, invocation: gio::DBusMethodInvocation) {
+        let mut results = self.search_local(&terms);
+        let search = self.searches.get() + 1;
+        self.searches.set(search);
+
+        let text = terms.join(" ");
+        if results.len() >= MAX_RESULTS || text.chars().count() < MIN_REMOTE_SEARCH_LENGTH {
+            invocation.return_value(Some(&(results,).to_variant()));
+            return;
+        }
+
+        // The service isn't stopped while StackExchange is searched.
+        let hold = relm4::main_application().hold();
+        glib::MainContext::default().spawn_local(async move {
+            glib::timeout_future(REMOTE_SEARCH_DELAY).await;
+
+            // Typing went on, the Shell drops these results anyway.
+            if self.searches.get() == search {
+                let site = Settings::load().default_site;
+                match self
+                    .stackexchange_client
+                    .get_search_suggestions(&site, &text)
+                    .await
+                {
+                    Ok(questions) => {
+                        // Only the last results are shown.
+                        let mut remote_questions = self.remote_questions.borrow_mut();
+                        remote_questions.clear();
+                        for question in questions {
+                            let uri = question_uri(&question.site, question.question_id);
+                            if results.len() < MAX_RESULTS && !results.contains(&uri) {
+                                results.push(uri.clone());
+                            }
+                            remote_questions.insert(
+                                uri.clone(),
+                                FoundQuestion {
+                                    uri,
+                                    site: question.site,
+                                    title: question.title,
+                                },
+                            );
+                        }
+                    }
+                    Err(error) => eprintln!("Failed to search {site} for the Shell: {error}"),
+                }
+            }
+
+            invocation.return_value(Some(&(results,).to_variant()));
+            drop(hold);
+        });
+    }
+
+    fn result_metas(&self, identifiers: &[String]) -> Vec<HashMap<String, glib::Variant>> {
+        let local_questions = self.local_questions();
+        let remote_questions = self.remote_questions.borrow();
+
+        identifiers
+            .iter()
+            .filter_map(|identifier| {
+                let question = local_questions
+                    .iter()
+                    .find(|question| &question.uri == identifier)
+                    .or_else(|| remote_questions.get(identifier))?;
+
+                Some(HashMap::from([
+                    ("id".to_owned(), question.uri.to_variant()),
+                    ("name".to_owned(), question.title.to_variant()),
+                    ("description".to_owned(), question.site.to_variant()),
+                ]))
+            })
+            .collect()
+    }
+}
+
+/// Starts the window if the Shell only started the service, or raises it.
+fn present_window() {
+    let application = relm4::main_application();
+    application.activate();
+    if let Some(window) = application.active_window() {
+        window.present();
+    }
+}
+
+/// Answer the searches of GNOME Shell, chosen results are opened with `sender`.
+pub fn register(connection: &gio::DBusConnection, sender: relm4::Sender<AppInput>) {
+    let interface = gio::DBusNodeInfo::for_xml(INTERFACE_XML)
+        .ok()
+        .and_then(|node| node.lookup_interface("org.gnome.Shell.SearchProvider2"))
+        .expect("The search provider interface is valid");
+
+    let result = connection.register_object(
+        OBJECT_PATH,
+        &interface,
+        move |_, _, _, _, method, parameters, invocation| {
+            // Every call restarts the inactivity timeout of the service.
+            let _hold = relm4::main_application().hold();
+            let search_provider = SEARCH_PROVIDER.with(Rc::clone);
+
+            let terms = match method {
+                "GetInitialResultSet" => parameters.get::<(Vec<String>,)>().map(|(terms,)| terms),
+                "GetSubsearchResultSet" => parameters
+                    .get::<(Vec<String>, Vec<String>)>()
+                    .map(|(_, terms)| terms),
+                _ => None,
+            };
+            if let Some(terms) = terms {
+                search_provider.search(terms, invocation);
+                return;
+            }
+
+            let response = match method {
+                "GetResultMetas" => parameters.get::<(Vec<String>,)>().map(|(identifiers,)| {
+                    (search_provider.result_metas(&identifiers),).to_variant()
+                }),
+                "ActivateResult" => {
+                    parameters
+                        .get::<(String, Vec<String>, u32)>()
+                        .map(|(uri, _, _)| {
+                            present_window();
+                            sender.emit(AppInput::RequestPagesByUri(uri));
+                            ().to_variant()
+                        })
+                }
+                "LaunchSearch" => parameters.get::<(Vec<String>, u32)>().map(|(terms, _)| {
+                    present_window();
+                    sender.emit(AppInput::SubmitSearchTerm(terms.join(" ")));
+                    ().to_variant()
+                }),
+                _ => None,
+            };
+
+            match response {
+                Some(response) => invocation.return_value(Some(&response)),
+                None => invocation.return_dbus_error(
+                    "org.freedesktop.DBus.Error.InvalidArgs",
+                    &format!("Invalid call of {method}"),
+                ),
+            }
+        },
+        |_, _, _, _, _| ().to_variant(),
+        |_, _, _, _, _, _| false,
+    );
+
+    if let Err(error) = result {
+        eprintln!("Failed to register the GNOME Shell search provider: {error}");
+    }
+}
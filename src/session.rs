@@ -0,0 +1,74 @@
+// Open question tabs, saved as JSON on quit and restored on launch:
+// {user data dir}/stackbloatless/session.json
+// While running they are also saved now and then to recovery.json, which is removed on quit. If it
+// is still there on launch, the last run crashed and its tabs can be recovered.
+
+use std::fs;
+use std::path::PathBuf;
+
+use relm4::gtk::glib;
+use serde::{Deserialize, Serialize};
+
+use crate::api::stackexchange::Id;
+use crate::storage;
+
+/// A question tab, tabs are saved in their order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTab {
+    pub site: String,
+    pub question_id: Id,
+    pub pinned: bool,
+    /// 0 for the main window, detached windows are numbered from 1 in the order they were opened.
+    #[serde(default)]
+    pub window: u32,
+    #[serde(default)]
+    pub scroll_position: f64,
+    /// The highlighted answer, for tabs opened from a link to it.
+    #[serde(default)]
+    pub answer_id: Option<Id>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub tabs: Vec<SessionTab>,
+    /// Restored on the next launch even if restoring tabs is disabled.
+    pub restore: bool,
+}
+
+fn data_dir() -> PathBuf {
+    glib::user_data_dir().join("stackbloatless")
+}
+
+fn session_path() -> PathBuf {
+    data_dir().join("session.json")
+}
+
+fn recovery_path() -> PathBuf {
+    data_dir().join("recovery.json")
+}
+
+impl Session {
+    /// Load the saved session, or an empty one if there is none.
+    pub fn load() -> Self {
+        storage::load_json(&session_path(), "session").unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save_json(&session_path(), self, "the session");
+    }
+
+    /// The snapshot of a run that didn't quit, if the last one crashed.
+    pub fn load_recovery() -> Option<Self> {
+        storage::load_json(&recovery_path(), "session")
+    }
+
+    pub fn save_recovery(&self) {
+        storage::save_json(&recovery_path(), self, "the session");
+    }
+
+    /// Called on quit, there is nothing to recover after it.
+    pub fn remove_recovery() {
+        let _ = fs::remove_file(recovery_path());
+    }
+}
@@ -0,0 +1,162 @@
+// User settings, saved as JSON in: {user config dir}/stackbloatless/settings.json
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use relm4::gtk::glib;
+use serde::{Deserialize, Serialize};
+
+use crate::api::stackexchange::{Timeouts, DEFAULT_MAX_CONCURRENT_REQUESTS};
+use crate::storage;
+
+// Zoom levels of the content, in percent.
+pub const MIN_ZOOM: u32 = 50;
+pub const MAX_ZOOM: u32 = 300;
+pub const ZOOM_STEP: u32 = 10;
+
+/// Order of the answers of a question.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnswerSort {
+    #[default]
+    Votes,
+    Newest,
+    Oldest,
+    Active,
+}
+
+impl AnswerSort {
+    pub const ALL: [AnswerSort; 4] = [Self::Votes, Self::Newest, Self::Oldest, Self::Active];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Votes => "Votes",
+            Self::Newest => "Newest",
+            Self::Oldest => "Oldest",
+            Self::Active => "Recently Active",
+        }
+    }
+}
+
+/// Light or dark style of the app.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    /// Follow the system preference.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Self::System, Self::Light, Self::Dark];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::System => "Follow System",
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+        }
+    }
+}
+
+/// A tag whose new questions are followed through its feed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Subscription {
+    pub site: String,
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// API site parameter of the site opened on the home page and searched by default.
+    pub default_site: String,
+    pub theme: Theme,
+    /// Pango font description for the content of posts, like `Cantarell 13`.
+    pub content_font: Option<String>,
+    /// Pango font description for code blocks, like `Source Code Pro 11`. It's the default
+    /// monospace font at the size of the content when it's not set.
+    pub code_font: Option<String>,
+    /// Size of the content in percent, tabs are zoomed in and out from it.
+    pub zoom: u32,
+    pub answer_sort: AnswerSort,
+    /// Key registered on https://stackapps.com, it raises the daily quota of anonymous requests.
+    pub api_key: Option<String>,
+    /// Proxy for every request, like `socks5://127.0.0.1:9050`. When it's not set the
+    /// `http_proxy`, `https_proxy` and `all_proxy` environment variables are used.
+    pub proxy: Option<String>,
+    /// In seconds.
+    pub connect_timeout: u64,
+    /// In seconds, for the whole request.
+    pub request_timeout: u64,
+    /// Requests sent at the same time, others are queued.
+    pub max_concurrent_requests: usize,
+    pub subscriptions: Vec<Subscription>,
+    /// Send a desktop notification when subscriptions have new questions.
+    pub notify_subscriptions: bool,
+    /// Reopen the question tabs of the last session on launch, or start fresh.
+    pub restore_session: bool,
+    /// Minutes a question tab isn't looked at before its page is dropped to save memory, it's
+    /// shown again from the cache once it's selected. 0 keeps every tab loaded.
+    pub suspend_tabs_after: u32,
+    /// Ctrl+Tab goes through the tabs from the most recently used, instead of their order.
+    pub recent_tab_cycling: bool,
+    /// Size of the main window when it's not maximized, -1 for its natural size.
+    pub window_width: i32,
+    pub window_height: i32,
+    pub window_maximized: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let timeouts = Timeouts::default();
+
+        Self {
+            default_site: "stackoverflow".to_owned(),
+            theme: Theme::default(),
+            content_font: None,
+            code_font: None,
+            zoom: 100,
+            answer_sort: AnswerSort::default(),
+            api_key: None,
+            proxy: None,
+            connect_timeout: timeouts.connect.as_secs(),
+            request_timeout: timeouts.request.as_secs(),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            subscriptions: Vec::new(),
+            notify_subscriptions: false,
+            restore_session: true,
+            suspend_tabs_after: 30,
+            recent_tab_cycling: false,
+            window_width: -1,
+            window_height: -1,
+            window_maximized: false,
+        }
+    }
+}
+
+fn settings_path() -> PathBuf {
+    glib::user_config_dir()
+        .join("stackbloatless")
+        .join("settings.json")
+}
+
+impl Settings {
+    pub fn timeouts(&self) -> Timeouts {
+        Timeouts {
+            connect: Duration::from_secs(self.connect_timeout),
+            request: Duration::from_secs(self.request_timeout),
+        }
+    }
+
+    /// Load the saved settings, or the defaults if there are none.
+    pub fn load() -> Self {
+        storage::load_json(&settings_path(), "settings").unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save_json(&settings_path(), self, "settings");
+    }
+}
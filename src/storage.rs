@@ -0,0 +1,37 @@
+// Reading and writing the JSON files of the app, like the settings, the history and the session.
+
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json as json;
+
+/// Parse the JSON file at `path`, `None` if it's missing or invalid. `what` is how its content is
+/// called in error messages.
+pub fn load_json<T: DeserializeOwned>(path: &Path, what: &str) -> Option<T> {
+    let content = fs::read(path).ok()?;
+
+    match json::from_slice(&content) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            eprintln!("Ignoring invalid {what} in {}: {error}", path.display());
+            None
+        }
+    }
+}
+
+/// Save `value` as JSON to `path`. It's written next to it then moved over it, so a crash while
+/// writing doesn't leave a truncated file behind.
+pub fn save_json<T: Serialize>(path: &Path, value: &T, what: &str) {
+    let temporary_path = path.with_extension("json.tmp");
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&temporary_path, json::to_vec_pretty(value)?))
+        .and_then(|_| fs::rename(&temporary_path, path));
+
+    if let Err(error) = result {
+        eprintln!("Failed to save {what} to {}: {error}", path.display());
+    }
+}